@@ -0,0 +1,75 @@
+#![no_std]
+//! Event schema shared by the SnbFi contracts.
+//!
+//! Every event is published with the topics `(NAMESPACE, VERSION, name)` followed by an optional key,
+//! the address or id the event is about, e.g. `("SNBFI", "v1", "pay", subscriber)`. Indexers can
+//! subscribe to the namespace across all pools and the factory, and skip the versions they don't know.
+//! Changing the topics or the payload of an event means bumping VERSION.
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, IntoVal, Symbol, Topics, Val};
+
+pub const NAMESPACE: Symbol = symbol_short!("SNBFI");
+pub const VERSION: Symbol = symbol_short!("v1");
+
+// a subscriber paid a due, keyed by the subscriber, Payment
+pub const PAY: Symbol = symbol_short!("pay");
+// a subscriber won an iteration, keyed by the winner, Win
+pub const WIN: Symbol = symbol_short!("win");
+// the factory deployed a pool, keyed by the pool, Deployment
+pub const DEPLOYED: Symbol = symbol_short!("deployed");
+// the factory quarantined a pool wasm, keyed by the wasm hash, number of pools flagged
+pub const QUARANTINED: Symbol = symbol_short!("quarantin");
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Payment {
+    // iteration the due was paid for
+    pub iteration: u32,
+    // address the tokens came from
+    pub payer: Address,
+    // tokens transferred
+    pub amount: u32,
+    // paid after the deadline plus the grace period
+    pub late: bool
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Win {
+    pub iteration: u32,
+    // prize assigned to the winner
+    pub prize: u32
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Deployment {
+    // wasm the pool was deployed from
+    pub wasm_hash: BytesN<32>,
+    // address that deployed the pool
+    pub deployer: Address
+}
+
+// topics of the event `name` about `key`
+pub fn topics<K>(name: Symbol, key: K) -> (Symbol, Symbol, Symbol, K) {
+    (NAMESPACE, VERSION, name, key)
+}
+
+// topics of the event `name` when it isn't about a single address or id
+pub fn topic(name: Symbol) -> (Symbol, Symbol, Symbol) {
+    (NAMESPACE, VERSION, name)
+}
+
+pub fn publish<K, D>(e: &Env, name: Symbol, key: K, data: D)
+where
+    (Symbol, Symbol, Symbol, K): Topics,
+    D: IntoVal<Env, Val>,
+{
+    e.events().publish(topics(name, key), data);
+}
+
+pub fn publish_unkeyed<D>(e: &Env, name: Symbol, data: D)
+where
+    D: IntoVal<Env, Val>,
+{
+    e.events().publish(topic(name), data);
+}
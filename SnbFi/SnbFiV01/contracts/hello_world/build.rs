@@ -0,0 +1,26 @@
+use std::process::Command;
+
+// embed the build identifier returned by get_version: SNBFI_BUILD_ID when set, the git commit otherwise
+fn main() {
+    println!("cargo:rerun-if-env-changed=SNBFI_BUILD_ID");
+    for path in [git(&["rev-parse", "--git-path", "HEAD"]), git(&["rev-parse", "--git-path", "refs/heads"])].into_iter().flatten() {
+        println!("cargo:rerun-if-changed={}", path);
+    }
+    let build_id = std::env::var("SNBFI_BUILD_ID")
+        .ok()
+        .or_else(|| git(&["rev-parse", "--short=12", "HEAD"]))
+        .unwrap_or_else(|| "dev".into());
+    //returned as a Symbol, so it has to be a valid one
+    if build_id.is_empty() || build_id.len() > 32 || !build_id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        panic!("SNBFI_BUILD_ID must be 1 to 32 characters among a-z, A-Z, 0-9 and _");
+    }
+    println!("cargo:rustc-env=SNBFI_BUILD_ID={}", build_id);
+}
+
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8(output.stdout).ok()?.trim().into())
+}
@@ -0,0 +1,78 @@
+use soroban_sdk::{contractclient, Address, Env};
+
+use crate::{storage, DataKey, BALANCE_BUMP_AMOUNT, BALANCE_BUMP_THREASHOLD};
+
+// KYC or attestation provider vouching for the addresses that can take a seat
+#[contractclient(name = "AttestationClient")]
+pub trait AttestationInterface {
+    fn is_verified(e: Env, user: Address) -> bool;
+}
+
+pub fn get_attestation_contract(e: &Env) -> Option<Address> {
+    storage::get_instance(e, &DataKey::AttestationContract)
+}
+
+pub fn set_attestation_contract(e: &Env, contract: &Address) {
+    storage::set_instance(e, &DataKey::AttestationContract, contract);
+}
+
+// whether the attestation contract verified `user`, anyone passes when the pool has none
+pub fn is_verified(e: &Env, user: Address) -> bool {
+    match get_attestation_contract(e) {
+        Some(contract) => AttestationClient::new(e, &contract).is_verified(&user),
+        None => true,
+    }
+}
+
+// only addresses on the allowlist can take a seat when it is enabled
+pub fn is_allowlist_enabled(e: &Env) -> bool {
+    storage::get_instance(e, &DataKey::AllowlistEnabled).unwrap_or(false)
+}
+
+pub fn set_allowlist_enabled(e: &Env, enabled: bool) {
+    storage::set_instance(e, &DataKey::AllowlistEnabled, &enabled);
+}
+
+pub fn is_allowlisted(e: &Env, user: Address) -> bool {
+    e.storage()
+        .persistent()
+        .get(&DataKey::Allowlisted(user))
+        .unwrap_or(false)
+}
+
+pub fn set_allowlisted(e: &Env, user: Address, allowed: bool) {
+    let key = DataKey::Allowlisted(user);
+    if !allowed {
+        e.storage().persistent().remove(&key);
+        return;
+    }
+    e.storage().persistent().set(&key, &true);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_BUMP_THREASHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+// addresses flagged by the owner, e.g. known defaulters from previous pools
+pub fn is_denied(e: &Env, user: Address) -> bool {
+    e.storage()
+        .persistent()
+        .get(&DataKey::Denied(user))
+        .unwrap_or(false)
+}
+
+pub fn set_denied(e: &Env, user: Address, denied: bool) {
+    let key = DataKey::Denied(user);
+    if !denied {
+        e.storage().persistent().remove(&key);
+        return;
+    }
+    e.storage().persistent().set(&key, &true);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_BUMP_THREASHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+// whether `user` may take a seat, anyone can while the allowlist is off
+pub fn is_allowed(e: &Env, user: Address) -> bool {
+    !is_allowlist_enabled(e) || is_allowlisted(e, user)
+}
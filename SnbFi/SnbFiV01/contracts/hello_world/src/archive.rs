@@ -0,0 +1,54 @@
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{symbol_short, Address, Env, Map, Symbol, Vec};
+
+use crate::{
+    event, history, payer, stats, storage, PoolArchive, PoolStatus, State, BALANCE_BUMP_AMOUNT,
+    BALANCE_BUMP_THREASHOLD,
+};
+
+const ARCHIVE: Symbol = symbol_short!("ARCHIVE");
+
+pub fn get_archive(e: &Env) -> Option<PoolArchive> {
+    e.storage().persistent().get(&ARCHIVE)
+}
+
+// once a settled pool owes nothing to its members, its members, iterations and payment records are dropped
+// to stop paying rent on them, and a summary of the pool is kept in their place
+pub fn archive(e: &Env, state: &mut State) -> PoolArchive {
+    for subr in state.subcriber_map.values().iter() {
+        if subr.prize_paid != subr.prize_money || subr.dividend_balance > 0 || subr.credit_balance > 0 {
+            panic!("Members still have balances to claim");
+        }
+    }
+    //the winners in the order they won, a copy of the records can be checked against the hash
+    let mut winners: Vec<Address> = Vec::new(e);
+    for pool_iteration in state.pool_iteration_map.values().iter() {
+        winners.push_back(pool_iteration.winner);
+    }
+    let stats = stats::pool_stats(e, state);
+    let summary = PoolArchive {
+        iterations: state.pool_iteration_map.len(),
+        members: state.subcriber_map.len(),
+        total_collected: stats.total_collected,
+        total_prizes: stats.total_prizes,
+        total_dividends: stats.total_dividends,
+        winners_hash: e.crypto().sha256(&winners.to_xdr(e)).to_bytes(),
+        archived_at: e.ledger().timestamp(),
+    };
+
+    for address in state.subcriber_map.keys().iter() {
+        history::remove_history(e, address.clone());
+        payer::remove_payer_stats(e, address);
+    }
+    storage::remove_entries(e, state);
+    state.subcriber_map = Map::new(e);
+    state.pool_iteration_map = Map::new(e);
+    state.status = PoolStatus::Archived;
+
+    e.storage().persistent().set(&ARCHIVE, &summary);
+    e.storage()
+        .persistent()
+        .extend_ttl(&ARCHIVE, BALANCE_BUMP_THREASHOLD, BALANCE_BUMP_AMOUNT);
+    event::pool_archived(e, summary.clone());
+    summary
+}
@@ -0,0 +1,48 @@
+use soroban_sdk::{symbol_short, Env, Symbol};
+
+use crate::{dividend, penalty, storage, PoolParams, Subscriber, PERCENTAGE_MULTIPLIER};
+
+//most interest charged on arrears every iteration, 5%
+pub const MAX_ARREARS_INTEREST_BPS: u32 = 500;
+
+const RATE: Symbol = symbol_short!("ARRSRATE");
+const RECOVERED: Symbol = symbol_short!("ARRSPAID");
+
+// interest charged on the arrears of a member every iteration, in basis points
+pub fn get_rate(e: &Env) -> u32 {
+    storage::get_instance(e, &RATE).unwrap_or(0)
+}
+
+pub fn set_rate(e: &Env, rate_bps: u32) {
+    storage::set_instance(e, &RATE, &rate_bps);
+}
+
+// missed dues paid back so far, they were added to the pots as they came in
+pub fn get_recovered(e: &Env) -> u32 {
+    storage::get_instance(e, &RECOVERED).unwrap_or(0)
+}
+
+// close an iteration for the subscriber: interest accrues on the arrears carried through it, then the due
+// they missed, if any, joins the arrears. Interest counts against the member protection cap
+pub fn accrue(e: &Env, pool_params: &PoolParams, subr: &mut Subscriber, missed_due: u32) {
+    if subr.prev_due_amount > 0 {
+        let interest = (u64::from(subr.prev_due_amount) * u64::from(get_rate(e))
+            / u64::from(PERCENTAGE_MULTIPLIER)) as u32;
+        subr.arrears_interest += penalty::charge(e, pool_params, subr, interest);
+    }
+    subr.prev_due_amount += missed_due;
+}
+
+// apply `amount` to the arrears of the subscriber, interest first. The interest is kept with the penalties
+// and the dues go to the next pot. Returns the interest and the dues paid
+pub fn repay(e: &Env, subr: &mut Subscriber, amount: u32) -> (u32, u32) {
+    let interest = amount.min(subr.arrears_interest);
+    let principal = (amount - interest).min(subr.prev_due_amount);
+    subr.arrears_interest -= interest;
+    subr.prev_due_amount -= principal;
+    if principal > 0 {
+        dividend::carry(e, principal);
+        storage::set_instance(e, &RECOVERED, &(get_recovered(e) + principal));
+    }
+    (interest, principal)
+}
@@ -0,0 +1,82 @@
+use soroban_sdk::{symbol_short, token, Address, Env, Symbol};
+
+use crate::{dispute, event, PrizeAssignment, PrizeOffer, State, Subscriber, BALANCE_BUMP_AMOUNT, BALANCE_BUMP_THREASHOLD};
+
+const OFFER: Symbol = symbol_short!("PRIZEOFR");
+
+pub fn get_offer(e: &Env, winner: Address) -> Option<PrizeOffer> {
+    e.storage().persistent().get(&(OFFER, winner))
+}
+
+fn set_offer(e: &Env, winner: Address, offer: &PrizeOffer) {
+    let key = (OFFER, winner);
+    e.storage().persistent().set(&key, offer);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_BUMP_THREASHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+pub fn remove_offer(e: &Env, winner: Address) {
+    e.storage().persistent().remove(&(OFFER, winner));
+}
+
+// address the unclaimed prize of the subscriber is paid to, the winner unless they assigned it
+pub fn prize_holder(state: &State, winner: Address, subr: &Subscriber) -> Address {
+    match state.pool_iteration_map.get(subr.winner_at_iter).and_then(|pool_iteration| pool_iteration.prize_assignments.last()) {
+        Some(assignment) => assignment.to,
+        None => winner,
+    }
+}
+
+// let `buyer` take over the unclaimed prize of `winner` for `price`, the prize can only change hands once
+pub fn offer(e: &Env, state: &State, winner: Address, buyer: Address, price: u32) {
+    let subr: Subscriber = match state.subcriber_map.get(winner.clone()) {
+        Some(subr) => subr,
+        None => panic!("Subscriber is not in the pool"),
+    };
+    if subr.winner_at_iter == 0 {
+        panic!("Subscriber has not won yet");
+    }
+    if subr.prize_claimed {
+        panic!("Prize has already been claimed");
+    }
+    if !state.pool_iteration_map.get(subr.winner_at_iter).unwrap().prize_assignments.is_empty() {
+        panic!("Prize has already been assigned");
+    }
+    dispute::require_undisputed(e, subr.winner_at_iter);
+    match state.subcriber_map.get(buyer.clone()) {
+        Some(buyer_subr) if !buyer_subr.defaulted && buyer != winner => (),
+        _ => panic!("Buyer must be another member in good standing"),
+    }
+    set_offer(e, winner.clone(), &PrizeOffer { buyer: buyer.clone(), price, offered_at: e.ledger().timestamp() });
+    event::prize_offered(e, winner, buyer, price);
+}
+
+// settle the offer made by `winner`: the buyer pays the price to the winner and the prize is assigned to
+// them in the same call, so neither side can walk away with both
+pub fn accept(e: &Env, state: &mut State, winner: Address, buyer: Address) {
+    let offer = match get_offer(e, winner.clone()) {
+        Some(offer) if offer.buyer == buyer => offer,
+        _ => panic!("No prize offer to accept"),
+    };
+    let subr: Subscriber = state.subcriber_map.get(winner.clone()).unwrap();
+    if subr.prize_claimed {
+        panic!("Prize has already been claimed");
+    }
+    dispute::require_undisputed(e, subr.winner_at_iter);
+    remove_offer(e, winner.clone());
+    if offer.price > 0 {
+        let token_client = token::Client::new(e, &state.pool_params.token);
+        token_client.transfer(&buyer, &winner, &i128::from(offer.price));
+    }
+    let mut pool_iteration = state.pool_iteration_map.get(subr.winner_at_iter).unwrap();
+    pool_iteration.prize_assignments.push_back(PrizeAssignment {
+        from: winner.clone(),
+        to: buyer.clone(),
+        price: offer.price,
+        unclaimed: subr.prize_money - subr.prize_paid,
+        assigned_at: e.ledger().timestamp(),
+    });
+    state.pool_iteration_map.set(subr.winner_at_iter, pool_iteration);
+    event::prize_assigned(e, winner, buyer, subr.winner_at_iter, offer.price);
+}
@@ -0,0 +1,55 @@
+use soroban_sdk::{symbol_short, Address, Env};
+
+use crate::{storage, DataKey, BALANCE_BUMP_AMOUNT, BALANCE_BUMP_THREASHOLD};
+
+// Membership badges: one non-fungible token per seat, the token id being the slot index
+
+pub fn owner_of(e: &Env, token_id: u32) -> Option<Address> {
+    e.storage().persistent().get(&DataKey::BadgeOwner(token_id))
+}
+
+pub fn badge_of(e: &Env, holder: Address) -> Option<u32> {
+    e.storage().persistent().get(&DataKey::Badge(holder))
+}
+
+fn set_badge(e: &Env, holder: Address, token_id: u32) {
+    let owner_key = DataKey::BadgeOwner(token_id);
+    e.storage().persistent().set(&owner_key, &holder);
+    e.storage()
+        .persistent()
+        .extend_ttl(&owner_key, BALANCE_BUMP_THREASHOLD, BALANCE_BUMP_AMOUNT);
+    let badge_key = DataKey::Badge(holder);
+    e.storage().persistent().set(&badge_key, &token_id);
+    e.storage()
+        .persistent()
+        .extend_ttl(&badge_key, BALANCE_BUMP_THREASHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+// mint the badge of a new member on the next slot index
+pub fn mint(e: &Env, holder: Address) -> u32 {
+    let token_id: u32 = storage::get_instance(e, &DataKey::BadgeCount).unwrap_or(0);
+    storage::set_instance(e, &DataKey::BadgeCount, &(token_id + 1));
+    set_badge(e, holder.clone(), token_id);
+    e.events()
+        .publish((symbol_short!("badge"), symbol_short!("mint"), holder), token_id);
+    token_id
+}
+
+pub fn burn(e: &Env, holder: Address) {
+    if let Some(token_id) = badge_of(e, holder.clone()) {
+        e.storage().persistent().remove(&DataKey::Badge(holder.clone()));
+        e.storage().persistent().remove(&DataKey::BadgeOwner(token_id));
+        e.events()
+            .publish((symbol_short!("badge"), symbol_short!("burn"), holder), token_id);
+    }
+}
+
+// the badge follows the seat when it changes hands
+pub fn transfer(e: &Env, from: Address, to: Address) {
+    if let Some(token_id) = badge_of(e, from.clone()) {
+        e.storage().persistent().remove(&DataKey::Badge(from.clone()));
+        set_badge(e, to.clone(), token_id);
+        e.events()
+            .publish((symbol_short!("badge"), symbol_short!("transfer"), from, to), token_id);
+    }
+}
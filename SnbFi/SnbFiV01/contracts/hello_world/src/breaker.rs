@@ -0,0 +1,48 @@
+use soroban_sdk::Env;
+
+use crate::{event, guardian, storage, GovernanceKey, OutflowLimit, OutflowWindow};
+
+pub fn get_outflow_limit(e: &Env) -> Option<OutflowLimit> {
+    storage::get_instance(e, &GovernanceKey::OutflowLimit)
+}
+
+pub fn set_outflow_limit(e: &Env, limit: Option<OutflowLimit>) {
+    match limit {
+        Some(limit) => storage::set_instance(e, &GovernanceKey::OutflowLimit, &limit),
+        None => storage::remove_instance(e, &GovernanceKey::OutflowLimit),
+    }
+}
+
+// tokens paid out in the window running now
+pub fn get_window(e: &Env) -> OutflowWindow {
+    let window: OutflowWindow = storage::get_instance(e, &GovernanceKey::OutflowWindow)
+        .unwrap_or(OutflowWindow { start: 0, outflow: 0 });
+    match get_outflow_limit(e) {
+        Some(limit) if e.ledger().timestamp() >= window.start + limit.window_secs => OutflowWindow {
+            start: e.ledger().timestamp(),
+            outflow: 0,
+        },
+        _ => window,
+    }
+}
+
+// count a payout against the ceiling, pausing payouts once the window goes over it.
+// The payout taking it over still goes through, the ones after it wait for the guardians
+pub fn record_outflow(e: &Env, amount: i128) {
+    let limit = match get_outflow_limit(e) {
+        Some(limit) => limit,
+        None => return,
+    };
+    let mut window = get_window(e);
+    window.outflow += amount;
+    if window.outflow > limit.max_outflow {
+        guardian::set_paused(e, true);
+        event::breaker_tripped(e, window.outflow, limit.max_outflow);
+    }
+    storage::set_instance(e, &GovernanceKey::OutflowWindow, &window);
+}
+
+// the guardians looked into the outflow when they unpaused, counting starts over
+pub fn reset(e: &Env) {
+    storage::remove_instance(e, &GovernanceKey::OutflowWindow);
+}
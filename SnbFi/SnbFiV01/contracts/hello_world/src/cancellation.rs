@@ -0,0 +1,64 @@
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::{event, storage, DataKey, PoolStatus, State};
+
+pub fn get_cancel_votes(e: &Env) -> Vec<Address> {
+    storage::get_instance(e, &DataKey::CancelVotes).unwrap_or(Vec::new(e))
+}
+
+// count the vote of `subscriber`, cancelling the pool once a majority of the members still in good standing agree.
+// Returns true when the pool got cancelled
+pub fn vote(e: &Env, state: &mut State, subscriber: Address) -> bool {
+    let mut votes = get_cancel_votes(e);
+    if votes.contains(&subscriber) {
+        panic!("Subscriber has already voted");
+    }
+    votes.push_back(subscriber);
+    let members = state
+        .subcriber_map
+        .values()
+        .iter()
+        .filter(|subr| !subr.defaulted)
+        .count() as u32;
+    let votes_in_good_standing = votes
+        .iter()
+        .filter(|voter| {
+            //votes of addresses no longer holding a seat don't count
+            if let Some(subr) = state.subcriber_map.get(voter.clone()) {
+                !subr.defaulted
+            } else {
+                false
+            }
+        })
+        .count() as u32;
+    if votes_in_good_standing * 2 > members {
+        cancel(e, state);
+        return true;
+    }
+    storage::set_instance(e, &DataKey::CancelVotes, &votes);
+    false
+}
+
+pub fn cancel(e: &Env, state: &mut State) {
+    state.status = PoolStatus::Cancelled;
+    storage::remove_instance(e, &DataKey::CancelVotes);
+    event::cancelled(e, state.current_iteration);
+}
+
+// forget the vote of a member leaving the pool
+pub fn drop_vote(e: &Env, subscriber: Address) {
+    let mut votes = get_cancel_votes(e);
+    if let Some(index) = votes.first_index_of(&subscriber) {
+        votes.remove(index);
+        storage::set_instance(e, &DataKey::CancelVotes, &votes);
+    }
+}
+
+// the vote follows the seat to its new address
+pub fn move_vote(e: &Env, old: Address, new: Address) {
+    let mut votes = get_cancel_votes(e);
+    if let Some(index) = votes.first_index_of(&old) {
+        votes.set(index, new);
+        storage::set_instance(e, &DataKey::CancelVotes, &votes);
+    }
+}
@@ -0,0 +1,43 @@
+use soroban_sdk::{Env, Symbol, Vec};
+
+use crate::{storage, ConfigChange, ConfigValue, DataKey, State, BALANCE_BUMP_AMOUNT, BALANCE_BUMP_THREASHOLD};
+
+pub fn get_change_count(e: &Env) -> u32 {
+    storage::get_instance(e, &DataKey::ConfigChangeCount).unwrap_or(0)
+}
+
+// append a change made by the pool owner to the log, entries are never rewritten
+pub fn record(e: &Env, state: &State, key: Symbol, old_value: ConfigValue, new_value: ConfigValue) {
+    //changes apply from the next iteration before the pool starts, from the current one afterwards
+    let effective_iteration = state.current_iteration.max(1);
+    let change = ConfigChange {
+        key,
+        changed_by: state.pool_params.pool_owner.clone(),
+        timestamp: e.ledger().timestamp(),
+        old_value,
+        new_value,
+        effective_iteration,
+    };
+    let index = get_change_count(e);
+    let entry_key = DataKey::ConfigChange(index);
+    e.storage().persistent().set(&entry_key, &change);
+    e.storage()
+        .persistent()
+        .extend_ttl(&entry_key, BALANCE_BUMP_THREASHOLD, BALANCE_BUMP_AMOUNT);
+    storage::set_instance(e, &DataKey::ConfigChangeCount, &(index + 1));
+}
+
+// up to `limit` changes, oldest first, starting at index `start`
+pub fn get_history(e: &Env, start: u32, limit: u32) -> Vec<ConfigChange> {
+    let end = get_change_count(e).min(start.saturating_add(limit));
+    let mut history = Vec::new(e);
+    for index in start..end {
+        history.push_back(
+            e.storage()
+                .persistent()
+                .get(&DataKey::ConfigChange(index))
+                .unwrap(),
+        );
+    }
+    history
+}
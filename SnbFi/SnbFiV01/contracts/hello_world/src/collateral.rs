@@ -0,0 +1,63 @@
+use soroban_sdk::{token, Address, Env};
+
+use crate::{breaker, guardian, ledger, DataKey, LedgerAccount, PoolParams, BALANCE_BUMP_AMOUNT, BALANCE_BUMP_THREASHOLD};
+
+pub fn get_collateral(e: &Env, subscriber: Address) -> i128 {
+    e.storage()
+        .persistent()
+        .get(&DataKey::Collateral(subscriber))
+        .unwrap_or(0)
+}
+
+fn set_collateral(e: &Env, subscriber: Address, amount: i128) {
+    let key = DataKey::Collateral(subscriber);
+    e.storage().persistent().set(&key, &amount);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_BUMP_THREASHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+// move the collateral required by the pool from the subscriber into the contract
+pub fn deposit_collateral(e: &Env, pool_params: &PoolParams, subscriber: Address) {
+    if pool_params.collateral_amount == 0 {
+        return;
+    }
+    let token_client = token::Client::new(e, &pool_params.token);
+    token_client.transfer(
+        &subscriber,
+        &e.current_contract_address(),
+        &pool_params.collateral_amount,
+    );
+    set_collateral(e, subscriber, pool_params.collateral_amount);
+    ledger::receive(e, LedgerAccount::Collateral, pool_params.collateral_amount);
+}
+
+// send the collateral held for the subscriber back to them, an outflow like any other
+pub fn release_collateral(e: &Env, pool_params: &PoolParams, subscriber: Address) -> i128 {
+    let amount = take_collateral(e, subscriber.clone());
+    if amount > 0 {
+        guardian::require_not_paused(e);
+        let token_client = token::Client::new(e, &pool_params.token);
+        token_client.transfer(&e.current_contract_address(), &subscriber, &amount);
+        breaker::record_outflow(e, amount);
+        ledger::pay(e, LedgerAccount::Collateral, amount);
+    }
+    amount
+}
+
+// clear the collateral of the subscriber, leaving the tokens in the contract
+pub fn take_collateral(e: &Env, subscriber: Address) -> i128 {
+    let amount = get_collateral(e, subscriber.clone());
+    e.storage()
+        .persistent()
+        .remove(&DataKey::Collateral(subscriber));
+    amount
+}
+
+// hand the collateral held for `from` over to `to`, leaving the tokens in the contract
+pub fn move_collateral(e: &Env, from: Address, to: Address) {
+    let amount = take_collateral(e, from);
+    if amount > 0 {
+        set_collateral(e, to, amount);
+    }
+}
@@ -0,0 +1,50 @@
+use soroban_sdk::Env;
+
+use crate::{storage, DataKey, PERCENTAGE_MULTIPLIER};
+
+// cap on the foreman commission, in line with the usual chit fund regulations
+pub const MAX_COMMISSION_BPS: u32 = 500; // 5%
+
+// share of every pot credited to the owner, 0 when the pool charges no commission
+pub fn get_commission_bps(e: &Env) -> u32 {
+    storage::get_instance(e, &DataKey::CommissionBps).unwrap_or(0)
+}
+
+pub fn set_commission_bps(e: &Env, commission_bps: u32) {
+    storage::set_instance(e, &DataKey::CommissionBps, &commission_bps);
+}
+
+// commission credited to the owner and not withdrawn yet
+pub fn get_commission_balance(e: &Env) -> u32 {
+    storage::get_instance(e, &DataKey::CommissionBalance).unwrap_or(0)
+}
+
+fn set_commission_balance(e: &Env, balance: u32) {
+    storage::set_instance(e, &DataKey::CommissionBalance, &balance);
+}
+
+// commission due on a pot of `collected`
+pub fn commission_on(e: &Env, collected: u32) -> u32 {
+    (u64::from(collected) * u64::from(get_commission_bps(e)) / u64::from(PERCENTAGE_MULTIPLIER)) as u32
+}
+
+// credit the commission taken out of a pot to the owner
+pub fn charge(e: &Env, commission: u32) {
+    set_commission_balance(e, get_commission_balance(e) + commission);
+}
+
+// pay `amount` out of the owner's balance
+pub fn spend(e: &Env, amount: u32) {
+    let balance = get_commission_balance(e);
+    if balance < amount {
+        panic!("Not enough commission to pay from");
+    }
+    set_commission_balance(e, balance - amount);
+}
+
+// empty the owner's balance, returns the amount to transfer
+pub fn take_commission_balance(e: &Env) -> u32 {
+    let balance = get_commission_balance(e);
+    set_commission_balance(e, 0);
+    balance
+}
@@ -0,0 +1,81 @@
+use soroban_sdk::{token, Env};
+
+use crate::{
+    collateral, dispute, dividend, event, ledger, reconcile, shares, stats, LedgerAccount, PoolStatus,
+    SettlementSummary, State,
+};
+
+// close the books once every seat has had its turn: the dividend nobody was credited is shared out,
+// the collateral goes back and whatever is owed to the members has to be held by the contract
+pub fn settle(e: &Env, state: &mut State) -> SettlementSummary {
+    for iteration in state.pool_iteration_map.keys().iter() {
+        dispute::require_undisputed(e, iteration);
+    }
+    let dust_swept = sweep(e, state);
+    let mut collateral_returned = 0;
+    for (address, subr) in state.subcriber_map.iter() {
+        if collateral::get_collateral(e, address.clone()) == 0 {
+            continue;
+        }
+        if subr.defaulted {
+            panic!("Collateral of defaulted members has to be seized first");
+        }
+        collateral_returned += collateral::release_collateral(e, &state.pool_params, address);
+    }
+    require_claimable(e, state);
+    state.status = PoolStatus::Completed;
+
+    let stats = stats::pool_stats(e, state);
+    let summary = SettlementSummary {
+        iterations: state.pool_iteration_map.len(),
+        total_collected: stats.total_collected,
+        total_prizes: stats.total_prizes,
+        total_dividends: stats.total_dividends,
+        dust_swept,
+        collateral_returned,
+    };
+    event::pool_settled(e, summary.clone());
+    summary
+}
+
+// the dividend left over after the last iteration has no next pot to join, it is shared among the members in good standing.
+// Returns the amount swept
+fn sweep(e: &Env, state: &mut State) -> u32 {
+    let dust = dividend::take_carryover(e);
+    let members = state.active_shares;
+    if dust == 0 || members == 0 {
+        dividend::carry(e, dust);
+        return 0;
+    }
+    let share = dust / members;
+    for (address, mut subr) in state.subcriber_map.iter() {
+        if !subr.defaulted {
+            subr.dividend_balance += shares::scaled(&subr, share);
+            state.subcriber_map.set(address, subr);
+        }
+    }
+    //the remainder that can't be split goes to the owner
+    let owner = state.pool_params.pool_owner.clone();
+    if let Some(mut subr) = state.subcriber_map.get(owner.clone()) {
+        subr.dividend_balance += dust - share * members;
+        state.subcriber_map.set(owner, subr);
+    }
+    ledger::post(e, LedgerAccount::Pot, LedgerAccount::Dividends, i128::from(dust));
+    dust
+}
+
+// the prizes, dividends and credits not paid out yet, and the fees not withdrawn, have to be covered by the contract balance
+fn require_claimable(e: &Env, state: &State) {
+    let mut owed: i128 = 0;
+    for subr in state.subcriber_map.values().iter() {
+        owed += i128::from(subr.prize_money - subr.prize_paid)
+            + i128::from(subr.dividend_balance)
+            + i128::from(subr.credit_balance);
+    }
+    let fees = reconcile::fee_balances(e, state);
+    owed += i128::from(fees.commission_unclaimed) + i128::from(fees.protocol_fees_unwithdrawn);
+    let balance = token::Client::new(e, &state.pool_params.token).balance(&e.current_contract_address());
+    if balance < owed {
+        panic!("Pool can't cover the prizes and dividends still to claim");
+    }
+}
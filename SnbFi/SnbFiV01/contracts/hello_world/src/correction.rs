@@ -0,0 +1,111 @@
+use soroban_sdk::{Address, Env};
+
+use crate::{
+    assignment, dividend, event, freeze, loan, prize_pot, shares, storage, Correction, DividendPolicy, GovernanceKey,
+    State, Subscriber,
+};
+
+pub fn is_approved(e: &Env, iteration: u32) -> bool {
+    storage::has_instance(e, &GovernanceKey::CorrectionApproval(iteration))
+}
+
+// the guardians agreed the iteration can be corrected, once
+pub fn approve(e: &Env, iteration: u32) {
+    storage::set_instance(e, &GovernanceKey::CorrectionApproval(iteration), &true);
+}
+
+fn has_winner(state: &State, iteration: u32) -> bool {
+    state
+        .pool_iteration_map
+        .get(iteration)
+        .and_then(|pool_iteration| state.subcriber_map.get(pool_iteration.winner))
+        .is_some_and(|subr| subr.winner_at_iter == iteration)
+}
+
+// fix the winner or the prize of the latest iteration won, before any of the prize is paid out.
+// The dividend already credited stays with the members, a change of prize is balanced against the dividend left over
+pub fn correct(e: &Env, state: &mut State, iteration: u32, winner: Address, prize_amount: u32) -> Correction {
+    if !is_approved(e, iteration) {
+        panic!("Correction has not been approved by the guardians");
+    }
+    if !has_winner(state, iteration) {
+        panic!("Iteration has no winner yet");
+    }
+    //later iterations build on the outcome of this one
+    if iteration != state.current_iteration
+        && (iteration + 1 != state.current_iteration || has_winner(state, state.current_iteration))
+    {
+        panic!("Only the latest winner can be corrected");
+    }
+    let mut pool_iteration = state.pool_iteration_map.get(iteration).unwrap();
+    let winner_before = pool_iteration.winner.clone();
+    let mut subr: Subscriber = state.subcriber_map.get(winner_before.clone()).unwrap();
+    if subr.prize_paid > 0 {
+        panic!("Prize has already been paid out");
+    }
+    if assignment::prize_holder(state, winner_before.clone(), &subr) != winner_before
+        || assignment::get_offer(e, winner_before.clone()).is_some()
+    {
+        panic!("Prize has been offered to another member");
+    }
+    if subr.loan_outstanding != subr.loan_principal {
+        panic!("Prize loan is being repaid");
+    }
+    let pot = prize_pot(e, &pool_iteration);
+    if prize_amount > pot || pot - prize_amount < pool_iteration.dividend_distributed {
+        panic!("Prize exceeds the dividend left over");
+    }
+    let prize_before = pool_iteration.prize_money;
+    let dividend_amount = pot - prize_amount;
+    if state.pool_params.dividend_policy != DividendPolicy::OwnerDiscretion {
+        carry_difference(e, state, iteration, pool_iteration.dividend_amount, dividend_amount);
+    }
+    pool_iteration.dividend_amount = dividend_amount;
+    pool_iteration.prize_money = prize_amount;
+
+    if winner != winner_before {
+        let mut incoming: Subscriber = match state.subcriber_map.get(winner.clone()) {
+            Some(subr) => subr,
+            None => panic!("Subscriber is not in the pool"),
+        };
+        if incoming.winner_at_iter != 0 {
+            panic!("Subscriber is already a winner");
+        }
+        if incoming.defaulted {
+            panic!("Subscriber has defaulted");
+        }
+        freeze::require_not_frozen(e, &winner);
+        incoming.winner_at_iter = iteration;
+        if loan::get_loan_interest(e).is_some() {
+            incoming.loan_principal = loan::principal_for(state, iteration, shares::scaled(&incoming, pool_iteration.due_amount));
+            incoming.loan_outstanding = incoming.loan_principal;
+        }
+        subr.winner_at_iter = 0;
+        subr.prize_money = 0;
+        subr.loan_principal = 0;
+        subr.loan_outstanding = 0;
+        state.subcriber_map.set(winner_before.clone(), subr);
+        subr = incoming;
+        pool_iteration.winner = winner.clone();
+    }
+    subr.prize_money = prize_amount;
+    state.subcriber_map.set(winner.clone(), subr);
+    state.pool_iteration_map.set(iteration, pool_iteration);
+    storage::remove_instance(e, &GovernanceKey::CorrectionApproval(iteration));
+
+    let correction = Correction { winner_before, winner_after: winner, prize_before, prize_after: prize_amount };
+    event::iteration_corrected(e, iteration, correction.clone());
+    correction
+}
+
+// the dividend left over was carried into the iteration opened next, or kept for the next pot after the last one
+fn carry_difference(e: &Env, state: &mut State, iteration: u32, before: u32, after: u32) {
+    if state.current_iteration > iteration {
+        let mut next = state.pool_iteration_map.get(state.current_iteration).unwrap();
+        next.carried_in = next.carried_in + after - before;
+        state.pool_iteration_map.set(state.current_iteration, next);
+    } else {
+        let carried = dividend::take_carryover(e);
+        dividend::carry(e, carried + after - before);
+    }
+}
@@ -0,0 +1,132 @@
+use soroban_sdk::{contractimpl, Address, Bytes, BytesN, Env, Map, String, Vec};
+
+use crate::{
+    badge, new_subscriber, payer, storage, DividendIndex, DividendPolicy, Frequency, HelloContract, HelloContractClient,
+    PoolIterationParams, PoolParams, PoolStatus, State, DEFAULT_MAX_MISSED_ITERATIONS, INTIALIZED,
+};
+
+const DEMO_SUBSCRIBERS: u32 = 4;
+const DEMO_SUB_AMOUNT: u32 = 100;
+const DEMO_COMPLETED_ITERATIONS: u32 = 2;
+// amount bid away by each demo winner, shared as dividend
+const DEMO_DISCOUNT: u32 = 40;
+
+#[contractimpl]
+impl HelloContract {
+    //
+    // Local sandbox helper, only built with the `demo` feature. Fills an empty contract with a
+    // small weekly pool, two completed iterations and a third one open for payments. The pool
+    // uses the native XLM token, but the demo balances are bookkeeping only.
+    //
+    pub fn bootstrap_demo(e: Env) -> Vec<Address> {
+        if storage::has_instance(&e, &INTIALIZED) {
+            panic!("Pool is already initialized");
+        }
+
+        let members = generate_members(&e);
+        let owner = members.get(0).unwrap();
+        let mut state = State {
+            pool_params: PoolParams {
+                name: String::from_str(&e, "Demo pool"),
+                description: String::from_str(&e, "Weekly sandbox pool with four members"),
+                no_of_subs: DEMO_SUBSCRIBERS,
+                frequency: Frequency::WEEK,
+                sub_amount: DEMO_SUB_AMOUNT,
+                pool_owner: owner.clone(),
+                grace_period_secs: 0,
+                max_missed_iterations: DEFAULT_MAX_MISSED_ITERATIONS,
+                token: native_token(&e),
+                collateral_amount: 0,
+                min_reputation: 0,
+                join_deadline: None,
+                dividend_policy: DividendPolicy::EqualAmongNonWinners,
+            },
+            current_iteration: DEMO_COMPLETED_ITERATIONS + 1,
+            subcriber_map: Map::new(&e),
+            pool_iteration_map: Map::new(&e),
+            shortfall: 0,
+            seized_collateral: 0,
+            penalties_collected: 0,
+            status: PoolStatus::Active,
+            insurance_fund: 0,
+            paid_out: 0,
+            started_at: e.ledger().timestamp(),
+            active_shares: 0,
+            eligible_shares: 0,
+            contributions: 0,
+            defaulted_shares: Map::new(&e),
+            dividend_index: DividendIndex { per_share: 0, per_token: 0, released: 0 },
+        };
+        let loaded = state.clone();
+        for member in members.iter() {
+            state.subcriber_map.set(member.clone(), new_subscriber());
+            badge::mint(&e, member);
+        }
+
+        let pot = DEMO_SUB_AMOUNT * DEMO_SUBSCRIBERS;
+        for iteration in 1..=DEMO_COMPLETED_ITERATIONS + 1 {
+            let mut pool_iteration = PoolIterationParams {
+                current_iteration: iteration,
+                amount_collected: 0,
+                winner: owner.clone(),
+                prize_money: 0,
+                dividend_amount: 0,
+                commission: 0,
+                protocol_fee: 0,
+                carried_in: 0,
+                discounts: 0,
+                yield_earned: 0,
+                reserve_cut: 0,
+                reserve_backfill: 0,
+                dividend_distributed: 0,
+                start_time: e.ledger().timestamp(),
+                due_amount: DEMO_SUB_AMOUNT,
+                prize_assignments: Vec::new(&e),
+            };
+            // the last iteration is left open
+            if iteration <= DEMO_COMPLETED_ITERATIONS {
+                for member in members.iter() {
+                    let mut subr = state.subcriber_map.get(member.clone()).unwrap();
+                    subr.last_paid_iter = iteration;
+                    subr.total_paid += DEMO_SUB_AMOUNT;
+                    state.subcriber_map.set(member.clone(), subr);
+                    payer::record_payment(&e, member.clone(), member, iteration, DEMO_SUB_AMOUNT);
+                }
+                let winner = members.get(iteration).unwrap();
+                let mut subr = state.subcriber_map.get(winner.clone()).unwrap();
+                subr.winner_at_iter = iteration;
+                subr.prize_money = pot - DEMO_DISCOUNT;
+                state.subcriber_map.set(winner.clone(), subr);
+
+                pool_iteration.amount_collected = pot;
+                pool_iteration.winner = winner;
+                pool_iteration.prize_money = pot - DEMO_DISCOUNT;
+                pool_iteration.dividend_amount = DEMO_DISCOUNT;
+            }
+            state.pool_iteration_map.set(iteration, pool_iteration);
+        }
+
+        storage::save_state(&e, &loaded, &state);
+        storage::set_instance(&e, &INTIALIZED, &true);
+        members
+    }
+}
+
+// deterministic member addresses derived from this contract, so no keys are needed
+fn generate_members(e: &Env) -> Vec<Address> {
+    let mut members = Vec::new(e);
+    for index in 0..DEMO_SUBSCRIBERS {
+        let mut salt = [0u8; 32];
+        salt[28..].copy_from_slice(&index.to_be_bytes());
+        let salt: BytesN<32> = Bytes::from_array(e, &salt).try_into().unwrap();
+        members.push_back(e.deployer().with_current_contract(salt).deployed_address());
+    }
+    members
+}
+
+// address of the native XLM Stellar Asset Contract on the current network
+fn native_token(e: &Env) -> Address {
+    // XDR of `Asset::Native`
+    let native_asset = Bytes::from_array(e, &[0u8; 4]);
+    e.deployer().with_stellar_asset(native_asset).deployed_address()
+}
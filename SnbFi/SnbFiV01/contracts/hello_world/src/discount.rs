@@ -0,0 +1,32 @@
+use soroban_sdk::Env;
+
+use crate::{frequency_to_secs, storage, DataKey, EarlyDiscount, PoolIterationParams, PoolParams, PERCENTAGE_MULTIPLIER};
+
+// cap on the discount given for paying early
+pub const MAX_EARLY_DISCOUNT_BPS: u32 = 500; // 5%
+
+// discount for early payments, None when the pool doesn't offer one
+pub fn get_early_discount(e: &Env) -> Option<EarlyDiscount> {
+    storage::get_instance(e, &DataKey::EarlyDiscount)
+}
+
+pub fn set_early_discount(e: &Env, discount: Option<EarlyDiscount>) {
+    match discount {
+        Some(discount) => storage::set_instance(e, &DataKey::EarlyDiscount, &discount),
+        None => storage::remove_instance(e, &DataKey::EarlyDiscount),
+    }
+}
+
+// discount on a due of `due` paid now, 0 once the early window of the iteration has passed
+pub fn discount_on(e: &Env, pool_params: &PoolParams, pool_iteration: &PoolIterationParams, due: u32) -> u32 {
+    let discount = match get_early_discount(e) {
+        Some(discount) => discount,
+        None => return 0,
+    };
+    let window = frequency_to_secs(&pool_params.frequency) * u64::from(discount.window_bps)
+        / u64::from(PERCENTAGE_MULTIPLIER);
+    if e.ledger().timestamp() > pool_iteration.start_time + window {
+        return 0;
+    }
+    (u64::from(due) * u64::from(discount.discount_bps) / u64::from(PERCENTAGE_MULTIPLIER)) as u32
+}
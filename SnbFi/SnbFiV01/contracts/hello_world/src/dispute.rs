@@ -0,0 +1,99 @@
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::{
+    event, guardian, storage, Dispute, DisputeOutcome, DisputeStatus, GovernanceKey, State, BALANCE_BUMP_AMOUNT,
+    BALANCE_BUMP_THREASHOLD,
+};
+
+pub fn get_dispute_count(e: &Env) -> u32 {
+    storage::get_instance(e, &GovernanceKey::DisputeCount).unwrap_or(0)
+}
+
+pub fn get_dispute(e: &Env, id: u32) -> Option<Dispute> {
+    e.storage().persistent().get(&GovernanceKey::Dispute(id))
+}
+
+fn set_dispute(e: &Env, dispute: &Dispute) {
+    let key = GovernanceKey::Dispute(dispute.id);
+    e.storage().persistent().set(&key, dispute);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_BUMP_THREASHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+// disputes on `iteration` still waiting for a resolution
+pub fn open_disputes(e: &Env, iteration: u32) -> u32 {
+    storage::get_instance(e, &GovernanceKey::OpenDisputes(iteration)).unwrap_or(0)
+}
+
+fn set_open_disputes(e: &Env, iteration: u32, count: u32) {
+    if count == 0 {
+        storage::remove_instance(e, &GovernanceKey::OpenDisputes(iteration));
+    } else {
+        storage::set_instance(e, &GovernanceKey::OpenDisputes(iteration), &count);
+    }
+}
+
+// the prize of an iteration is held back while it is disputed
+pub fn require_undisputed(e: &Env, iteration: u32) {
+    if open_disputes(e, iteration) > 0 {
+        panic!("Payout is disputed");
+    }
+}
+
+// open a dispute on `iteration`, returns its id
+pub fn raise(e: &Env, subscriber: Address, iteration: u32, reason_code: u32) -> u32 {
+    let id = get_dispute_count(e);
+    let dispute = Dispute {
+        id,
+        subscriber: subscriber.clone(),
+        iteration,
+        reason_code,
+        raised_at: e.ledger().timestamp(),
+        status: DisputeStatus::Open,
+    };
+    set_dispute(e, &dispute);
+    storage::set_instance(e, &GovernanceKey::DisputeCount, &(id + 1));
+    set_open_disputes(e, iteration, open_disputes(e, iteration) + 1);
+    event::dispute_raised(e, subscriber, id, iteration, reason_code);
+    id
+}
+
+// the guardians settle disputes when the pool has them, the owner picked the winner being disputed otherwise
+pub fn require_resolver(e: &Env, state: &State, resolver: &Address) {
+    let allowed = match guardian::get_guardians(e) {
+        Some(set) => set.guardians.contains(resolver),
+        None => *resolver == state.pool_params.pool_owner,
+    };
+    if !allowed {
+        panic!("Address can't resolve disputes");
+    }
+}
+
+// record the outcome and lift the hold on the payout once no other dispute is open
+pub fn resolve(e: &Env, id: u32, outcome: DisputeOutcome) {
+    let mut dispute = match get_dispute(e, id) {
+        Some(dispute) => dispute,
+        None => panic!("Dispute doesn't exist"),
+    };
+    if dispute.status != DisputeStatus::Open {
+        panic!("Dispute has already been resolved");
+    }
+    dispute.status = match outcome {
+        DisputeOutcome::Dismissed => DisputeStatus::Dismissed,
+        DisputeOutcome::Upheld => DisputeStatus::Upheld,
+    };
+    set_dispute(e, &dispute);
+    set_open_disputes(e, dispute.iteration, open_disputes(e, dispute.iteration) - 1);
+    event::dispute_resolved(e, id, outcome);
+}
+
+// up to `limit` disputes, oldest first, starting at id `start`
+pub fn get_disputes(e: &Env, start: u32, limit: u32) -> Vec<Dispute> {
+    let end = get_dispute_count(e).min(start.saturating_add(limit));
+    let mut disputes = Vec::new(e);
+    for id in start..end {
+        disputes.push_back(get_dispute(e, id).unwrap());
+    }
+    disputes
+}
@@ -0,0 +1,84 @@
+use soroban_sdk::{Address, Env};
+
+use crate::{shares, storage, DataKey, DividendIndex, DividendPolicy, State, Subscriber};
+
+// precision of the dividend index per token paid in
+pub const TOKEN_INDEX_SCALE: u128 = 1_000_000_000;
+
+// dividend waiting to be added to the pot of the next iteration
+pub fn get_carryover(e: &Env) -> u32 {
+    storage::get_instance(e, &DataKey::Carryover).unwrap_or(0)
+}
+
+// keep what the members weren't credited for the next pot, rounding remainders included
+pub fn carry(e: &Env, amount: u32) {
+    if amount > 0 {
+        set_carryover(e, get_carryover(e) + amount);
+    }
+}
+
+fn set_carryover(e: &Env, carryover: u32) {
+    storage::set_instance(e, &DataKey::Carryover, &carryover);
+}
+
+// hand the carried dividend to the iteration being opened
+pub fn take_carryover(e: &Env) -> u32 {
+    let carryover = get_carryover(e);
+    set_carryover(e, 0);
+    carryover
+}
+
+// share the dividend of an iteration according to the pool policy, by moving the dividend index. The members are
+// credited when they are next read. `winner_shares` are the shares of the member who just won, still counted
+// among the members yet to win until the pool is saved. Returns the amount shared out
+pub fn distribute(state: &mut State, dividend: u32, winner_shares: u32) -> u32 {
+    match state.pool_params.dividend_policy {
+        DividendPolicy::EqualAmongNonWinners => {
+            let recipients = state.eligible_shares - winner_shares;
+            if recipients == 0 {
+                return 0;
+            }
+            let share = dividend / recipients;
+            state.dividend_index.per_share += share;
+            share * recipients
+        }
+        DividendPolicy::ProRataByContribution => {
+            if state.contributions == 0 {
+                return 0;
+            }
+            let per_token = u128::from(dividend) * TOKEN_INDEX_SCALE / u128::from(state.contributions);
+            state.dividend_index.per_token += per_token;
+            //the fractions of a token left on each member stay in the pot
+            (per_token * u128::from(state.contributions) / TOKEN_INDEX_SCALE) as u32
+        }
+        // the whole dividend is left over for the next pot
+        DividendPolicy::CarryToNextPot => 0,
+        // the owner allocates the dividend later on
+        DividendPolicy::OwnerDiscretion => 0,
+    }
+}
+
+// credit the subscriber what the dividend index grew by since they were last credited, for the dividends they have
+// a part in. Winning, defaulting and paying in only happen to a member who was read, so they held all along
+pub fn settle(index: &DividendIndex, subr: &mut Subscriber) {
+    let last = subr.dividend_index.clone();
+    if !subr.defaulted {
+        let mut credit = shares::scaled(subr, index.released - last.released);
+        if subr.winner_at_iter == 0 {
+            credit += shares::scaled(subr, index.per_share - last.per_share);
+        }
+        credit += ((index.per_token - last.per_token) * u128::from(subr.total_paid) / TOKEN_INDEX_SCALE) as u32;
+        subr.dividend_balance += credit;
+    }
+    subr.dividend_index = index.clone();
+}
+
+// credit the dividend of the owner's choosing to a subscriber
+pub fn credit(state: &mut State, subscriber: Address, amount: u32) {
+    let mut subr: Subscriber = match state.subcriber_map.get(subscriber.clone()) {
+        Some(subr) => subr,
+        None => panic!("Subscriber is not in the pool"),
+    };
+    subr.dividend_balance += amount;
+    state.subcriber_map.set(subscriber, subr);
+}
@@ -0,0 +1,84 @@
+use soroban_sdk::{symbol_short, token, Address, Env, Symbol};
+
+use crate::{
+    breaker, event, guardian, ledger, storage, EmergencyWithdrawal, GovernanceKey, GuardianAction, LedgerAccount,
+    PoolParams,
+};
+
+// delay between proposing an emergency withdrawal and being able to carry it out
+pub const EMERGENCY_DELAY_SECS: u64 = 72 * 60 * 60;
+
+const WITHDRAWN: Symbol = symbol_short!("EMERGOUT");
+
+// tokens taken out of the pool by the emergency withdrawals carried out so far
+pub fn get_withdrawn(e: &Env) -> i128 {
+    storage::get_instance(e, &WITHDRAWN).unwrap_or(0)
+}
+
+pub fn get_pending(e: &Env) -> Option<EmergencyWithdrawal> {
+    storage::get_instance(e, &GovernanceKey::EmergencyWithdrawal)
+}
+
+// announce a withdrawal of `amount` to `to`, executable once the delay has passed
+pub fn propose(e: &Env, to: Address, amount: i128) -> EmergencyWithdrawal {
+    if amount <= 0 {
+        panic!("Amount must be positive");
+    }
+    if get_pending(e).is_some() {
+        panic!("An emergency withdrawal is already pending");
+    }
+    let proposed_at = e.ledger().timestamp();
+    let withdrawal = EmergencyWithdrawal {
+        to,
+        amount,
+        proposed_at,
+        executable_at: proposed_at + EMERGENCY_DELAY_SECS,
+    };
+    storage::set_instance(e, &GovernanceKey::EmergencyWithdrawal, &withdrawal);
+    guardian::clear_approvals(e, &GuardianAction::VetoWithdrawal);
+    event::emergency_proposed(e, &withdrawal);
+    withdrawal
+}
+
+// carry out the pending withdrawal once members had the whole delay to react
+pub fn execute(e: &Env, pool_params: &PoolParams) -> EmergencyWithdrawal {
+    let withdrawal = match get_pending(e) {
+        Some(withdrawal) => withdrawal,
+        None => panic!("No emergency withdrawal is pending"),
+    };
+    if e.ledger().timestamp() < withdrawal.executable_at {
+        panic!("Emergency withdrawal is still timelocked");
+    }
+    guardian::require_not_paused(e);
+    storage::remove_instance(e, &GovernanceKey::EmergencyWithdrawal);
+    token::Client::new(e, &pool_params.token).transfer(
+        &e.current_contract_address(),
+        &withdrawal.to,
+        &withdrawal.amount,
+    );
+    breaker::record_outflow(e, withdrawal.amount);
+    //the withdrawal isn't earmarked, it comes out of the pot
+    ledger::pay(e, LedgerAccount::Pot, withdrawal.amount);
+    storage::set_instance(e, &WITHDRAWN, &(get_withdrawn(e) + withdrawal.amount));
+    event::emergency_executed(e, &withdrawal);
+    withdrawal
+}
+
+// drop the pending withdrawal
+pub fn cancel(e: &Env) {
+    event::emergency_cancelled(e, &take_pending(e));
+}
+
+// drop the pending withdrawal on the guardians' say
+pub fn veto(e: &Env) {
+    event::emergency_vetoed(e, &take_pending(e));
+}
+
+fn take_pending(e: &Env) -> EmergencyWithdrawal {
+    let withdrawal = match get_pending(e) {
+        Some(withdrawal) => withdrawal,
+        None => panic!("No emergency withdrawal is pending"),
+    };
+    storage::remove_instance(e, &GovernanceKey::EmergencyWithdrawal);
+    withdrawal
+}
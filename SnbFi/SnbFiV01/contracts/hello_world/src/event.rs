@@ -0,0 +1,211 @@
+use soroban_sdk::{symbol_short, Address, Bytes, BytesN, Env, String, Symbol, Vec};
+
+use crate::{Correction, DisputeOutcome, EmergencyWithdrawal, GuardianAction, PaymentRecord, PendingChange, PoolArchive, PoolTerms, SettlementSummary, SlashSettlement};
+
+// all the events follow the schema of snbfi_events: (SNBFI, v1, name, key) topics
+
+pub(crate) fn paid(e: &Env, subscriber: Address, record: &PaymentRecord) {
+    let payment = snbfi_events::Payment {
+        iteration: record.iteration,
+        payer: record.payer.clone(),
+        amount: record.amount,
+        late: record.late,
+    };
+    snbfi_events::publish(e, snbfi_events::PAY, subscriber, payment);
+}
+
+pub(crate) fn won(e: &Env, winner: Address, iteration: u32, prize: u32) {
+    snbfi_events::publish(e, snbfi_events::WIN, winner, snbfi_events::Win { iteration, prize });
+}
+
+pub(crate) fn defaulted(e: &Env, subscriber: Address, missed_iterations: u32, shortfall: u32) {
+    snbfi_events::publish(e, symbol_short!("default"), subscriber, (missed_iterations, shortfall));
+}
+
+pub(crate) fn strategy_degraded(e: &Env, strategy: Address, iteration: u32) {
+    snbfi_events::publish(e, symbol_short!("degraded"), strategy, iteration);
+}
+
+pub(crate) fn seats_drawn(e: &Env, seated: Vec<Address>) {
+    snbfi_events::publish_unkeyed(e, symbol_short!("seats"), seated);
+}
+
+pub(crate) fn rotated(e: &Env, old: Address, new: Address) {
+    snbfi_events::publish(e, symbol_short!("rotated"), old, new);
+}
+
+pub(crate) fn prize_claimed(e: &Env, winner: Address, iteration: u32, prize: u32, note: Option<Bytes>) {
+    snbfi_events::publish(e, symbol_short!("claim"), winner, (iteration, prize, note));
+}
+
+pub(crate) fn cancelled(e: &Env, iterations: u32) {
+    snbfi_events::publish_unkeyed(e, symbol_short!("cancelled"), iterations);
+}
+
+pub(crate) fn insurance_claimed(e: &Env, subscriber: Address, iteration: u32, amount: u32) {
+    snbfi_events::publish(e, symbol_short!("insured"), subscriber, (iteration, amount));
+}
+
+pub(crate) fn promoted(e: &Env, user: Address) {
+    snbfi_events::publish(e, symbol_short!("promoted"), user, ());
+}
+
+pub(crate) fn slot_transferred(e: &Env, from: Address, to: Address) {
+    snbfi_events::publish(e, symbol_short!("transfer"), from, to);
+}
+
+pub(crate) fn shortfall_approved(e: &Env, iteration: u32, collected: u32, expected: u32, reason: String) {
+    snbfi_events::publish(e, symbol_short!("shortfall"), iteration, (collected, expected, reason));
+}
+
+pub(crate) fn fee_accrued(e: &Env, iteration: u32, fee: u32, treasury_balance: u32) {
+    snbfi_events::publish(e, symbol_short!("fee"), iteration, (fee, treasury_balance));
+}
+
+pub(crate) fn due_collected(e: &Env, subscriber: Address, iteration: u32, amount: u32) {
+    snbfi_events::publish(e, symbol_short!("collected"), subscriber, (iteration, amount));
+}
+
+pub(crate) fn collection_failed(e: &Env, subscriber: Address, iteration: u32, reason: Symbol) {
+    snbfi_events::publish(e, symbol_short!("nocollect"), subscriber, (iteration, reason));
+}
+
+pub(crate) fn denylist_updated(e: &Env, user: Address, denied: bool) {
+    snbfi_events::publish(e, symbol_short!("denylist"), user, denied);
+}
+
+pub(crate) fn emergency_proposed(e: &Env, withdrawal: &EmergencyWithdrawal) {
+    snbfi_events::publish(e, symbol_short!("emergency"), withdrawal.to.clone(), (withdrawal.amount, withdrawal.executable_at));
+}
+
+pub(crate) fn emergency_executed(e: &Env, withdrawal: &EmergencyWithdrawal) {
+    snbfi_events::publish(e, symbol_short!("emrgexec"), withdrawal.to.clone(), withdrawal.amount);
+}
+
+pub(crate) fn emergency_cancelled(e: &Env, withdrawal: &EmergencyWithdrawal) {
+    snbfi_events::publish(e, symbol_short!("emrgcancl"), withdrawal.to.clone(), withdrawal.amount);
+}
+
+pub(crate) fn change_proposed(e: &Env, id: u32, pending: &PendingChange) {
+    snbfi_events::publish(e, symbol_short!("proposed"), id, (pending.change.clone(), pending.executable_at));
+}
+
+pub(crate) fn change_cancelled(e: &Env, id: u32) {
+    snbfi_events::publish(e, symbol_short!("withdrawn"), id, ());
+}
+
+pub(crate) fn emergency_vetoed(e: &Env, withdrawal: &EmergencyWithdrawal) {
+    snbfi_events::publish(e, symbol_short!("emrgveto"), withdrawal.to.clone(), withdrawal.amount);
+}
+
+pub(crate) fn guardian_approved(e: &Env, guardian: Address, action: GuardianAction) {
+    snbfi_events::publish(e, symbol_short!("approved"), guardian, action);
+}
+
+pub(crate) fn guardian_action(e: &Env, action: GuardianAction) {
+    snbfi_events::publish_unkeyed(e, symbol_short!("guardian"), action);
+}
+
+pub(crate) fn breaker_tripped(e: &Env, outflow: i128, max_outflow: i128) {
+    snbfi_events::publish_unkeyed(e, symbol_short!("tripped"), (outflow, max_outflow));
+}
+
+pub(crate) fn dispute_raised(e: &Env, subscriber: Address, id: u32, iteration: u32, reason_code: u32) {
+    snbfi_events::publish(e, symbol_short!("dispute"), subscriber, (id, iteration, reason_code));
+}
+
+pub(crate) fn dispute_resolved(e: &Env, id: u32, outcome: DisputeOutcome) {
+    snbfi_events::publish(e, symbol_short!("resolved"), id, outcome);
+}
+
+pub(crate) fn expulsion_proposed(e: &Env, subscriber: Address, proposer: Address) {
+    snbfi_events::publish(e, symbol_short!("expelprop"), subscriber, proposer);
+}
+
+pub(crate) fn expulsion_vote(e: &Env, subscriber: Address, voter: Address) {
+    snbfi_events::publish(e, symbol_short!("expelvote"), subscriber, voter);
+}
+
+pub(crate) fn expelled(e: &Env, subscriber: Address, seized: i128, no_of_subs: u32) {
+    snbfi_events::publish(e, symbol_short!("expelled"), subscriber, (seized, no_of_subs));
+}
+
+pub(crate) fn terms_proposed(e: &Env, id: u32, terms: PoolTerms) {
+    snbfi_events::publish(e, symbol_short!("terms"), id, terms);
+}
+
+pub(crate) fn terms_vote(e: &Env, id: u32, voter: Address, approve: bool) {
+    snbfi_events::publish(e, symbol_short!("termsvote"), id, (voter, approve));
+}
+
+pub(crate) fn terms_decided(e: &Env, id: u32, approved: bool) {
+    snbfi_events::publish(e, symbol_short!("termsdone"), id, approved);
+}
+
+pub(crate) fn reserve_released(e: &Env, released: u32, share: u32) {
+    snbfi_events::publish_unkeyed(e, symbol_short!("reserve"), (released, share));
+}
+
+pub(crate) fn slashed(e: &Env, subscriber: Address, settlement: SlashSettlement) {
+    snbfi_events::publish(e, symbol_short!("slashed"), subscriber, settlement);
+}
+
+pub(crate) fn successor_created(e: &Env, successor: Address) {
+    snbfi_events::publish_unkeyed(e, symbol_short!("successor"), successor);
+}
+
+pub(crate) fn prize_offered(e: &Env, winner: Address, buyer: Address, price: u32) {
+    snbfi_events::publish(e, symbol_short!("prizeofr"), winner, (buyer, price));
+}
+
+pub(crate) fn prize_assigned(e: &Env, winner: Address, buyer: Address, iteration: u32, price: u32) {
+    snbfi_events::publish(e, symbol_short!("prizesold"), winner, (buyer, iteration, price));
+}
+
+pub(crate) fn profile_set(e: &Env, subscriber: Address, display_name: String) {
+    snbfi_events::publish(e, symbol_short!("profile"), subscriber, display_name);
+}
+
+pub(crate) fn profile_cleared(e: &Env, subscriber: Address) {
+    snbfi_events::publish(e, symbol_short!("profilclr"), subscriber, ());
+}
+
+pub(crate) fn dues_reminder(e: &Env, iteration: u32, unpaid: Vec<Address>) {
+    snbfi_events::publish(e, symbol_short!("duesoon"), iteration, unpaid);
+}
+
+pub(crate) fn operation_approved(e: &Env, owner: Address, id: BytesN<32>) {
+    snbfi_events::publish(e, symbol_short!("opapprove"), owner, id);
+}
+
+pub(crate) fn operation_executed(e: &Env, id: BytesN<32>) {
+    snbfi_events::publish_unkeyed(e, symbol_short!("opexec"), id);
+}
+
+pub(crate) fn member_frozen(e: &Env, subscriber: Address) {
+    snbfi_events::publish(e, symbol_short!("frozen"), subscriber, ());
+}
+
+pub(crate) fn member_unfrozen(e: &Env, subscriber: Address) {
+    snbfi_events::publish(e, symbol_short!("unfrozen"), subscriber, ());
+}
+
+pub(crate) fn member_removed(e: &Env, subscriber: Address, refunded: i128) {
+    snbfi_events::publish(e, symbol_short!("removed"), subscriber, refunded);
+}
+
+pub(crate) fn member_substituted(e: &Env, leaving: Address, incoming: Address, arrears_paid: u32) {
+    snbfi_events::publish(e, symbol_short!("subst"), leaving, (incoming, arrears_paid));
+}
+
+pub(crate) fn iteration_corrected(e: &Env, iteration: u32, correction: Correction) {
+    snbfi_events::publish(e, symbol_short!("corrected"), iteration, correction);
+}
+
+pub(crate) fn pool_settled(e: &Env, summary: SettlementSummary) {
+    snbfi_events::publish_unkeyed(e, symbol_short!("settled"), summary);
+}
+
+pub(crate) fn pool_archived(e: &Env, summary: PoolArchive) {
+    snbfi_events::publish_unkeyed(e, symbol_short!("archived"), summary);
+}
@@ -0,0 +1,115 @@
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::{
+    badge, cancellation, collateral, event, ledger, slashing, sponsorship, storage, GovernanceKey, LedgerAccount,
+    State, Subscriber,
+};
+
+pub fn get_votes(e: &Env, subscriber: Address) -> Vec<Address> {
+    storage::get_instance(e, &GovernanceKey::ExpelVotes(subscriber)).unwrap_or(Vec::new(e))
+}
+
+// members without arrears, the ones who get a say on expulsions
+pub fn is_paid_up(state: &State, subr: &Subscriber) -> bool {
+    !subr.defaulted && subr.last_paid_iter + 1 >= state.current_iteration
+}
+
+// open a vote on expelling `subscriber`, counting the vote of the proposer
+pub fn propose(e: &Env, state: &mut State, proposer: Address, subscriber: Address) -> bool {
+    if storage::has_instance(e, &GovernanceKey::ExpelVotes(subscriber.clone())) {
+        panic!("Expulsion has already been proposed");
+    }
+    event::expulsion_proposed(e, subscriber.clone(), proposer.clone());
+    vote(e, state, proposer, subscriber)
+}
+
+// count the vote of `voter`, expelling the subscriber once a majority of the paid-up members agree.
+// Returns true when the subscriber got expelled
+pub fn vote(e: &Env, state: &mut State, voter: Address, subscriber: Address) -> bool {
+    let mut votes = get_votes(e, subscriber.clone());
+    if votes.contains(&voter) {
+        panic!("Subscriber has already voted");
+    }
+    votes.push_back(voter.clone());
+    event::expulsion_vote(e, subscriber.clone(), voter);
+    //members who fell behind since they voted no longer count
+    let members = state
+        .subcriber_map
+        .values()
+        .iter()
+        .filter(|subr| is_paid_up(state, subr))
+        .count() as u32;
+    let votes_paid_up = votes
+        .iter()
+        .filter(|voter| {
+            //votes of addresses no longer holding a seat don't count
+            if let Some(subr) = state.subcriber_map.get(voter.clone()) {
+                is_paid_up(state, &subr)
+            } else {
+                false
+            }
+        })
+        .count() as u32;
+    if votes_paid_up * 2 > members {
+        expel(e, state, subscriber);
+        return true;
+    }
+    storage::set_instance(e, &GovernanceKey::ExpelVotes(subscriber), &votes);
+    false
+}
+
+// take the seat away, keeping the collateral and credit of the subscriber to make up for the dues they missed
+fn expel(e: &Env, state: &mut State, subscriber: Address) {
+    let subr = state.subcriber_map.get(subscriber.clone()).unwrap();
+    let seized = collateral::take_collateral(e, subscriber.clone()) + i128::from(subr.credit_balance);
+    ledger::post(e, LedgerAccount::Members, LedgerAccount::Collateral, i128::from(subr.credit_balance));
+    slashing::settle(e, state, subscriber.clone(), seized);
+    state.subcriber_map.remove(subscriber.clone());
+    drop_votes(e, subscriber.clone());
+    cancellation::drop_vote(e, subscriber.clone());
+
+    //the sponsor paid for this member only
+    sponsorship::refund(e, &state.pool_params, subscriber.clone());
+    //one turn fewer is left when the seat hadn't won yet
+    if subr.winner_at_iter == 0 {
+        state.pool_params.no_of_subs -= 1;
+    }
+    badge::burn(e, subscriber.clone());
+    event::expelled(e, subscriber, seized, state.pool_params.no_of_subs);
+}
+
+// the open votes on and by the seat follow it to its new address
+pub fn move_votes(e: &Env, old: Address, new: Address) {
+    let against = get_votes(e, old.clone());
+    if !against.is_empty() {
+        storage::remove_instance(e, &GovernanceKey::ExpelVotes(old.clone()));
+        storage::set_instance(e, &GovernanceKey::ExpelVotes(new.clone()), &against);
+    }
+    //the index of the members is only rewritten once the seat is saved, the other members may not be loaded
+    let mut subscribers = storage::get_member_addresses(e);
+    subscribers.push_back(new.clone());
+    for subscriber in subscribers.iter() {
+        let mut votes = get_votes(e, subscriber.clone());
+        if let Some(index) = votes.first_index_of(&old) {
+            votes.set(index, new.clone());
+            storage::set_instance(e, &GovernanceKey::ExpelVotes(subscriber), &votes);
+        }
+    }
+}
+
+// forget the open vote on a member leaving the pool, and the votes they cast
+pub fn drop_votes(e: &Env, subscriber: Address) {
+    storage::remove_instance(e, &GovernanceKey::ExpelVotes(subscriber.clone()));
+    for other in storage::get_member_addresses(e).iter() {
+        let mut votes = get_votes(e, other.clone());
+        if let Some(index) = votes.first_index_of(&subscriber) {
+            votes.remove(index);
+            //an expulsion nobody backs anymore is closed
+            if votes.is_empty() {
+                storage::remove_instance(e, &GovernanceKey::ExpelVotes(other));
+            } else {
+                storage::set_instance(e, &GovernanceKey::ExpelVotes(other), &votes);
+            }
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::{badge, collateral, event, new_subscriber, storage, DataKey, FairLaunch, State};
+
+pub fn get_fair_launch(e: &Env) -> Option<FairLaunch> {
+    storage::get_instance(e, &DataKey::FairLaunch)
+}
+
+pub fn set_fair_launch(e: &Env, launch: &FairLaunch) {
+    storage::set_instance(e, &DataKey::FairLaunch, launch);
+}
+
+// seat a random selection of the registrations in the free slots and refund everyone else.
+// Returns the seated addresses.
+pub fn close(e: &Env, state: &mut State) -> Vec<Address> {
+    let launch = match get_fair_launch(e) {
+        Some(launch) => launch,
+        None => panic!("No fair launch is open"),
+    };
+    if e.ledger().timestamp() <= launch.window_end {
+        panic!("Registration window is still open");
+    }
+
+    let free_seats = state
+        .pool_params
+        .no_of_subs
+        .saturating_sub(state.subcriber_map.len());
+    let mut registrations = launch.registrations;
+    e.prng().shuffle(&mut registrations);
+
+    let mut seated = Vec::new(e);
+    for (index, user) in registrations.iter().enumerate() {
+        if (index as u32) < free_seats {
+            state.subcriber_map.set(user.clone(), new_subscriber());
+            badge::mint(e, user.clone());
+            seated.push_back(user);
+        } else {
+            collateral::release_collateral(e, &state.pool_params, user);
+        }
+    }
+
+    storage::remove_instance(e, &DataKey::FairLaunch);
+    event::seats_drawn(e, seated.clone());
+    seated
+}
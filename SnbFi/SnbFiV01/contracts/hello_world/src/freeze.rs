@@ -0,0 +1,42 @@
+use soroban_sdk::{symbol_short, Address, Env, Symbol, Vec};
+
+use crate::{event, storage};
+
+const FROZEN: Symbol = symbol_short!("FROZEN");
+
+// members under investigation, there are few at a time so they are kept in one list
+pub fn get_frozen(e: &Env) -> Vec<Address> {
+    storage::get_instance(e, &FROZEN).unwrap_or(Vec::new(e))
+}
+
+pub fn is_frozen(e: &Env, member: &Address) -> bool {
+    get_frozen(e).contains(member)
+}
+
+pub fn freeze(e: &Env, member: Address) {
+    let mut frozen = get_frozen(e);
+    if frozen.contains(&member) {
+        panic!("Subscriber is already frozen");
+    }
+    frozen.push_back(member.clone());
+    storage::set_instance(e, &FROZEN, &frozen);
+    event::member_frozen(e, member);
+}
+
+pub fn unfreeze(e: &Env, member: Address) {
+    let mut frozen = get_frozen(e);
+    let index = match frozen.first_index_of(&member) {
+        Some(index) => index,
+        None => panic!("Subscriber is not frozen"),
+    };
+    frozen.remove(index);
+    storage::set_instance(e, &FROZEN, &frozen);
+    event::member_unfrozen(e, member);
+}
+
+// frozen members keep paying their dues but can't win or take anything out of the pool
+pub fn require_not_frozen(e: &Env, member: &Address) {
+    if is_frozen(e, member) {
+        panic!("Subscriber is frozen");
+    }
+}
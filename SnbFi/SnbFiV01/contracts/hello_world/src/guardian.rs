@@ -0,0 +1,83 @@
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::{breaker, correction, emergency, event, storage, GovernanceKey, GuardianAction, GuardianSet};
+
+pub fn get_guardians(e: &Env) -> Option<GuardianSet> {
+    storage::get_instance(e, &GovernanceKey::Guardians)
+}
+
+pub fn set_guardians(e: &Env, guardians: Option<GuardianSet>) {
+    match guardians {
+        Some(guardians) => storage::set_instance(e, &GovernanceKey::Guardians, &guardians),
+        None => storage::remove_instance(e, &GovernanceKey::Guardians),
+    }
+}
+
+pub fn is_paused(e: &Env) -> bool {
+    storage::get_instance(e, &GovernanceKey::Paused).unwrap_or(false)
+}
+
+pub fn set_paused(e: &Env, paused: bool) {
+    storage::set_instance(e, &GovernanceKey::Paused, &paused);
+}
+
+// tokens only leave the pool while it isn't paused
+pub fn require_not_paused(e: &Env) {
+    if is_paused(e) {
+        panic!("Payouts are paused");
+    }
+}
+
+pub fn get_approvals(e: &Env, action: &GuardianAction) -> Vec<Address> {
+    storage::get_instance(e, &GovernanceKey::GuardianApprovals(action.clone())).unwrap_or(Vec::new(e))
+}
+
+// approvals are given for the situation at hand, they don't carry over once it changes
+pub fn clear_approvals(e: &Env, action: &GuardianAction) {
+    storage::remove_instance(e, &GovernanceKey::GuardianApprovals(action.clone()));
+}
+
+// count the approval of `guardian`, taking the action once the threshold is reached.
+// Returns true when the action was taken
+pub fn approve(e: &Env, guardian: Address, action: GuardianAction) -> bool {
+    let set = match get_guardians(e) {
+        Some(set) => set,
+        None => panic!("Pool has no guardians"),
+    };
+    if !set.guardians.contains(&guardian) {
+        panic!("Address is not a guardian");
+    }
+    match action {
+        GuardianAction::Pause if is_paused(e) => panic!("Payouts are already paused"),
+        GuardianAction::Unpause if !is_paused(e) => panic!("Payouts are not paused"),
+        GuardianAction::VetoWithdrawal if emergency::get_pending(e).is_none() => {
+            panic!("No emergency withdrawal is pending")
+        }
+        GuardianAction::CorrectIteration(iteration) if correction::is_approved(e, iteration) => {
+            panic!("Correction is already approved")
+        }
+        _ => {}
+    }
+    let mut approvals = get_approvals(e, &action);
+    if approvals.contains(&guardian) {
+        panic!("Guardian has already approved");
+    }
+    approvals.push_back(guardian.clone());
+    event::guardian_approved(e, guardian, action.clone());
+    if approvals.len() < set.threshold {
+        storage::set_instance(e, &GovernanceKey::GuardianApprovals(action), &approvals);
+        return false;
+    }
+    clear_approvals(e, &action);
+    match action {
+        GuardianAction::Pause => set_paused(e, true),
+        GuardianAction::Unpause => {
+            set_paused(e, false);
+            breaker::reset(e);
+        }
+        GuardianAction::VetoWithdrawal => emergency::veto(e),
+        GuardianAction::CorrectIteration(iteration) => correction::approve(e, iteration),
+    }
+    event::guardian_action(e, action);
+    true
+}
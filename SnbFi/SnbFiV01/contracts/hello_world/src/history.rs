@@ -0,0 +1,72 @@
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::{DataKey, PaymentRecord, BALANCE_BUMP_AMOUNT, BALANCE_BUMP_THREASHOLD};
+
+pub fn get_payment_count(e: &Env, subscriber: Address) -> u32 {
+    e.storage()
+        .persistent()
+        .get(&DataKey::PaymentCount(subscriber))
+        .unwrap_or(0)
+}
+
+fn set_payment_count(e: &Env, subscriber: Address, count: u32) {
+    let key = DataKey::PaymentCount(subscriber);
+    e.storage().persistent().set(&key, &count);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_BUMP_THREASHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+fn set_payment(e: &Env, subscriber: Address, index: u32, record: &PaymentRecord) {
+    let key = DataKey::Payment(subscriber, index);
+    e.storage().persistent().set(&key, record);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_BUMP_THREASHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+// append a payment to the history of the subscriber
+pub fn record(e: &Env, subscriber: Address, record: &PaymentRecord) {
+    let index = get_payment_count(e, subscriber.clone());
+    set_payment(e, subscriber.clone(), index, record);
+    set_payment_count(e, subscriber, index + 1);
+}
+
+// up to `limit` payments of the subscriber, oldest first, starting at index `start`
+pub fn get_history(e: &Env, subscriber: Address, start: u32, limit: u32) -> Vec<PaymentRecord> {
+    let end = get_payment_count(e, subscriber.clone()).min(start.saturating_add(limit));
+    let mut history = Vec::new(e);
+    for index in start..end {
+        history.push_back(
+            e.storage()
+                .persistent()
+                .get(&DataKey::Payment(subscriber.clone(), index))
+                .unwrap(),
+        );
+    }
+    history
+}
+
+// keep the history of `from` under the address `to`
+pub fn move_history(e: &Env, from: Address, to: Address) {
+    let count = get_payment_count(e, from.clone());
+    for index in 0..count {
+        let key = DataKey::Payment(from.clone(), index);
+        let record: PaymentRecord = e.storage().persistent().get(&key).unwrap();
+        e.storage().persistent().remove(&key);
+        set_payment(e, to.clone(), index, &record);
+    }
+    if count > 0 {
+        e.storage().persistent().remove(&DataKey::PaymentCount(from));
+        set_payment_count(e, to, count);
+    }
+}
+
+// drop the history of the subscriber once the pool is archived
+pub fn remove_history(e: &Env, subscriber: Address) {
+    let count = get_payment_count(e, subscriber.clone());
+    for index in 0..count {
+        e.storage().persistent().remove(&DataKey::Payment(subscriber.clone(), index));
+    }
+    e.storage().persistent().remove(&DataKey::PaymentCount(subscriber));
+}
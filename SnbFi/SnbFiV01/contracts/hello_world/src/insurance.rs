@@ -0,0 +1,73 @@
+use soroban_sdk::{Address, Env};
+
+use crate::{
+    event, ledger, payer, storage, DataKey, LedgerAccount, SeatInsurance, State, BALANCE_BUMP_AMOUNT,
+    BALANCE_BUMP_THREASHOLD,
+};
+
+// premium added to every due of an insured subscriber, 0 when the pool doesn't offer insurance
+pub fn get_premium(e: &Env) -> u32 {
+    storage::get_instance(e, &DataKey::InsurancePremium).unwrap_or(0)
+}
+
+pub fn set_premium(e: &Env, premium: u32) {
+    storage::set_instance(e, &DataKey::InsurancePremium, &premium);
+}
+
+pub fn get_seat_insurance(e: &Env, subscriber: Address) -> Option<SeatInsurance> {
+    e.storage()
+        .persistent()
+        .get(&DataKey::SeatInsurance(subscriber))
+}
+
+pub fn set_seat_insurance(e: &Env, subscriber: Address, insurance: &SeatInsurance) {
+    let key = DataKey::SeatInsurance(subscriber);
+    e.storage().persistent().set(&key, insurance);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_BUMP_THREASHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+pub fn move_seat_insurance(e: &Env, from: Address, to: Address) {
+    if let Some(insurance) = get_seat_insurance(e, from.clone()) {
+        e.storage().persistent().remove(&DataKey::SeatInsurance(from));
+        set_seat_insurance(e, to, &insurance);
+    }
+}
+
+// put the premium of an insured subscriber into the fund, returns the premium to collect with the due
+pub fn collect_premium(e: &Env, state: &mut State, subscriber: Address) -> u32 {
+    let mut insurance = match get_seat_insurance(e, subscriber.clone()) {
+        Some(insurance) => insurance,
+        None => return 0,
+    };
+    let premium = get_premium(e);
+    insurance.premiums_paid += premium;
+    set_seat_insurance(e, subscriber, &insurance);
+    state.insurance_fund += premium;
+    premium
+}
+
+// pay a missed due of an insured subscriber out of the fund, once per cycle.
+// Returns true when the due was covered
+pub fn cover_missed_due(e: &Env, state: &mut State, subscriber: Address, iteration: u32, due: u32) -> bool {
+    let mut insurance = match get_seat_insurance(e, subscriber.clone()) {
+        Some(insurance) => insurance,
+        None => return false,
+    };
+    if insurance.claims > 0 || state.insurance_fund < due {
+        return false;
+    }
+    state.insurance_fund -= due;
+    ledger::post(e, LedgerAccount::Insurance, LedgerAccount::Pot, i128::from(due));
+    insurance.claims += 1;
+    insurance.covered_iteration = iteration;
+    set_seat_insurance(e, subscriber.clone(), &insurance);
+
+    let mut pool_iteration = state.pool_iteration_map.get(iteration).unwrap();
+    pool_iteration.amount_collected += due;
+    state.pool_iteration_map.set(iteration, pool_iteration);
+    payer::record_due(e, due);
+    event::insurance_claimed(e, subscriber, iteration, due);
+    true
+}
@@ -0,0 +1,44 @@
+use soroban_sdk::{symbol_short, Env, Symbol, Vec};
+
+use crate::{payer, reconcile, State};
+
+// the contract holds at least the tokens its buckets account for
+pub const BALANCE: Symbol = symbol_short!("balance");
+// the contract holds at least what it owes the members: unpaid prizes, dividends and collateral
+pub const SOLVENT: Symbol = symbol_short!("solvent");
+// the dues collected by the iterations are the dues recorded as paid
+pub const COLLECTED: Symbol = symbol_short!("collected");
+
+// names of the invariants that don't hold, empty when the pool is consistent
+pub fn violations(e: &Env, state: &State) -> Vec<Symbol> {
+    let mut violated = Vec::new(e);
+    let report = reconcile::report(e, state);
+    if report.discrepancy < 0 {
+        violated.push_back(BALANCE);
+    }
+
+    let owed: i128 = state
+        .subcriber_map
+        .values()
+        .iter()
+        .map(|subr| {
+            let unpaid_prize = if subr.winner_at_iter != 0 { subr.prize_money - subr.prize_paid } else { 0 };
+            i128::from(unpaid_prize) + i128::from(subr.dividend_balance)
+        })
+        .sum::<i128>()
+        + reconcile::held_collateral(e, state);
+    if report.actual_balance < owed {
+        violated.push_back(SOLVENT);
+    }
+
+    let collected: u32 = state
+        .pool_iteration_map
+        .values()
+        .iter()
+        .map(|pool_iteration| pool_iteration.amount_collected)
+        .sum();
+    if collected != payer::get_recorded_dues(e) {
+        violated.push_back(COLLECTED);
+    }
+    violated
+}
@@ -0,0 +1,78 @@
+use soroban_sdk::{symbol_short, vec, Env, Symbol, Vec};
+
+use crate::{storage, LedgerAccount, LedgerBalance, TrialBalance};
+
+const LEDGER: Symbol = symbol_short!("LEDGER");
+
+// accounts holding tokens, their balance grows with debits. The others are what the tokens are held for
+fn is_asset(account: &LedgerAccount) -> bool {
+    matches!(account, LedgerAccount::Cash | LedgerAccount::Lent | LedgerAccount::Unsettled)
+}
+
+fn accounts(e: &Env) -> Vec<LedgerAccount> {
+    vec![
+        e,
+        LedgerAccount::Cash,
+        LedgerAccount::Lent,
+        LedgerAccount::Unsettled,
+        LedgerAccount::Pot,
+        LedgerAccount::Dividends,
+        LedgerAccount::Fees,
+        LedgerAccount::Collateral,
+        LedgerAccount::Insurance,
+        LedgerAccount::Members,
+    ]
+}
+
+pub fn get_balance(e: &Env, account: LedgerAccount) -> LedgerBalance {
+    storage::get_instance(e, &(LEDGER, account.clone()))
+        .unwrap_or(LedgerBalance { account, debits: 0, credits: 0, balance: 0 })
+}
+
+fn set_balance(e: &Env, entry: &LedgerBalance) {
+    storage::set_instance(e, &(LEDGER, entry.account.clone()), entry);
+}
+
+// record `amount` moving from the `credit` account to the `debit` account
+pub fn post(e: &Env, debit: LedgerAccount, credit: LedgerAccount, amount: i128) {
+    if amount <= 0 {
+        return;
+    }
+    let mut debited = get_balance(e, debit);
+    debited.debits += amount;
+    debited.balance += if is_asset(&debited.account) { amount } else { -amount };
+    set_balance(e, &debited);
+    let mut credited = get_balance(e, credit);
+    credited.credits += amount;
+    credited.balance += if is_asset(&credited.account) { -amount } else { amount };
+    set_balance(e, &credited);
+}
+
+// tokens entering the contract for `account`
+pub fn receive(e: &Env, account: LedgerAccount, amount: i128) {
+    post(e, LedgerAccount::Cash, account, amount);
+}
+
+// tokens leaving the contract out of `account`
+pub fn pay(e: &Env, account: LedgerAccount, amount: i128) {
+    post(e, account, LedgerAccount::Cash, amount);
+}
+
+// every account with its postings, the debits have to add up to the credits
+pub fn trial_balance(e: &Env) -> TrialBalance {
+    let mut balances = Vec::new(e);
+    let mut total_debits = 0;
+    let mut total_credits = 0;
+    for account in accounts(e).iter() {
+        let entry = get_balance(e, account);
+        total_debits += entry.debits;
+        total_credits += entry.credits;
+        balances.push_back(entry);
+    }
+    TrialBalance {
+        accounts: balances,
+        total_debits,
+        total_credits,
+        balanced: total_debits == total_credits,
+    }
+}
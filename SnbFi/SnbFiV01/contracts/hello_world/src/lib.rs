@@ -1,6 +1,7 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Env, Symbol, Address};
-use soroban_sdk::Map;
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, panic_with_error, symbol_short, token, Bytes, BytesN, Env, String, Symbol, Address, Vec};
+use soroban_sdk::{IntoVal, Map, Val};
+use soroban_sdk::xdr::ToXdr;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
@@ -10,6 +11,19 @@ pub enum Frequency {
     MONTH
 }
 
+const DAY_IN_SECS: u64 = 86_400;
+const WEEK_IN_SECS: u64 = 7 * DAY_IN_SECS;
+const MONTH_IN_SECS: u64 = 30 * DAY_IN_SECS;
+
+// length of one iteration window in seconds
+pub(crate) fn frequency_to_secs(frequency: &Frequency) -> u64 {
+    match frequency {
+        Frequency::DAY => DAY_IN_SECS,
+        Frequency::WEEK => WEEK_IN_SECS,
+        Frequency::MONTH => MONTH_IN_SECS,
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
 pub struct Subscriber {
@@ -17,13 +31,146 @@ pub struct Subscriber {
     pub winner_at_iter: u32,
     //prize money won by the subscriber
     pub prize_money: u32,
-    // previous due amount of the subscriber, not including the current due amount
-    pub prev_due_amount: u32
+    // dues the subscriber missed in closed iterations and hasn't paid back yet, not including the current due amount
+    pub prev_due_amount: u32,
+    // interest accrued on the missed dues and not paid yet
+    pub arrears_interest: u32,
+    // last iteration the subscriber paid the due for, 0 if nothing was paid yet
+    pub last_paid_iter: u32,
+    // number of payments made after the iteration deadline plus the grace period
+    pub late_payments: u32,
+    // number of iterations the subscriber did not pay for
+    pub missed_iterations: u32,
+    // subscriber stopped paying and is no longer eligible to win
+    pub defaulted: bool,
+    // co-signer registered at join time who can cover overdue dues
+    pub guarantor: Option<Address>,
+    // penalties and interest charged to the subscriber during the cycle
+    pub penalties_paid: u32,
+    // the prize won by the subscriber has been paid out in full
+    pub prize_claimed: bool,
+    // part of the prize paid out so far, it is released in tranches when the pool vests prizes
+    pub prize_paid: u32,
+    // dues paid for the subscriber so far, refunded if the pool gets cancelled
+    pub total_paid: u32,
+    // dividends credited to the subscriber and not claimed yet
+    pub dividend_balance: u32,
+    // dues owed by the subscriber when the prize was won, lent to them if prizes are loans
+    pub loan_principal: u32,
+    // part of the loan not repaid yet, every due paid after winning repays it
+    pub loan_outstanding: u32,
+    // interest paid on the loan on top of the dues
+    pub loan_interest_paid: u32,
+    // iteration the subscriber has paid part of the due for, 0 if none
+    pub partial_iter: u32,
+    // part of the due of `partial_iter` paid so far
+    pub partial_amount: u32,
+    // payments that only covered part of a due
+    pub partial_payments: u32,
+    // tokens sent over the dues, used for the next dues
+    pub credit_balance: u32,
+    // address the subscriber allowed to pay dues on their behalf, the seat stays theirs
+    pub payer: Option<Address>,
+    // shares committed to: the due, the odds of winning and the dividend are multiplied by it
+    pub shares: u32,
+    // dividend index of the pool when the dividends were last credited to the subscriber
+    pub dividend_index: DividendIndex
+}
+
+// Subscriber with default values, used when someone joins the pool
+fn new_subscriber() -> Subscriber {
+    Subscriber {
+        winner_at_iter: 0,
+        prev_due_amount: 0,
+        arrears_interest: 0,
+        prize_money: 0,
+        last_paid_iter: 0,
+        late_payments: 0,
+        missed_iterations: 0,
+        defaulted: false,
+        guarantor: None,
+        penalties_paid: 0,
+        prize_claimed: false,
+        prize_paid: 0,
+        total_paid: 0,
+        dividend_balance: 0,
+        loan_principal: 0,
+        loan_outstanding: 0,
+        loan_interest_paid: 0,
+        partial_iter: 0,
+        partial_amount: 0,
+        partial_payments: 0,
+        credit_balance: 0,
+        payer: None,
+        shares: 1,
+        dividend_index: DividendIndex { per_share: 0, per_token: 0, released: 0 }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum PoolStatus {
+    // members can join, the first iteration hasn't started
+    Open,
+    // iterations are running
+    Active,
+    // the pool was called off, members can claim their refunds
+    Cancelled,
+    // every member had their turn and the pool was settled, what is left to claim stays claimable
+    Completed,
+    // everything was claimed and only a summary of the pool is kept
+    Archived
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum DividendPolicy {
+    // split equally among the members in good standing who haven't won yet
+    EqualAmongNonWinners,
+    // split among the members in good standing in proportion to the dues they paid
+    ProRataByContribution,
+    // added to the pot of the next iteration
+    CarryToNextPot,
+    // held until the owner allocates it
+    OwnerDiscretion
+}
+
+// terms a pool is set up with, the ones kept in PoolParams are documented there
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PoolConfig {
+    pub name: String,
+    pub description: String,
+    pub no_of_subs: u32,
+    pub sub_amount: u32,
+    pub frequency: Frequency,
+    pub grace_period_secs: u64,
+    pub token: Address,
+    pub collateral_amount: i128,
+    pub join_deadline: Option<u64>,
+    pub dividend_policy: DividendPolicy,
+    // contract asked whether addresses passed KYC before they take a seat, None to let anyone in
+    pub attestation: Option<Address>
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct ProtocolFee {
+    // share of every pot accrued to the treasury
+    pub fee_bps: u32,
+    // address the accrued fees are withdrawn to
+    pub treasury: Address,
+    // address allowed to withdraw the accrued fees
+    pub treasurer: Address
 }
 
 #[derive(Clone,Debug, Eq, PartialEq)]
 #[contracttype]
 pub struct PoolParams {
+    // human-readable name shown in pool lists
+    pub name: String,
+    // short description of the pool
+    pub description: String,
     // number of subscribers in the pool
     pub no_of_subs: u32,
     // frequency of the pool
@@ -31,9 +178,44 @@ pub struct PoolParams {
     // subscription amount
     pub sub_amount : u32,
     // pool was initiated by the owner
-    pub pool_owner : Address
+    pub pool_owner : Address,
+    // seconds after the iteration deadline during which a payment still counts as on-time
+    pub grace_period_secs: u64,
+    // missed iterations after which a subscriber is automatically marked as defaulted
+    pub max_missed_iterations: u32,
+    // token used for dues and collateral
+    pub token: Address,
+    // collateral every subscriber deposits on join, 0 when the pool doesn't require any
+    pub collateral_amount: i128,
+    // reputation an applicant needs to join the pool, 0 lets anyone in
+    pub min_reputation: u32,
+    // ledger timestamp after which nobody can join, the pool can be aborted if it isn't full by then
+    pub join_deadline: Option<u64>,
+    // how the dividend of every iteration is shared
+    pub dividend_policy: DividendPolicy
 }
 
+// missed iterations tolerated before a subscriber is marked as defaulted, unless the owner changes it
+pub const DEFAULT_MAX_MISSED_ITERATIONS: u32 = 2;
+
+// longest pool name and description accepted, in bytes
+pub const MAX_NAME_LEN: u32 = 64;
+pub const MAX_DESCRIPTION_LEN: u32 = 256;
+
+// time after an iteration deadline the owner has to pick its winner before any member can force a draw
+pub const FORCE_DRAW_TIMEOUT_SECS: u64 = 3 * DAY_IN_SECS;
+
+// most iterations returned by a single get_iterations call
+pub const MAX_ITERATIONS_PER_QUERY: u32 = 50;
+// most subscribers returned by a single get_subscribers call
+pub const MAX_SUBSCRIBERS_PER_QUERY: u32 = 50;
+//most dues collect_all_due pulls in one call, so it stays within the instruction budget
+pub const MAX_COLLECTIONS_PER_CALL: u32 = 25;
+//version of the storage layout and entry points, bumped with every upgrade that changes them
+pub const SCHEMA_VERSION: u32 = 1;
+//commit the contract was built from, see build.rs
+const BUILD_ID: &str = env!("SNBFI_BUILD_ID");
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
 pub struct PoolIterationParams {
@@ -46,175 +228,4385 @@ pub struct PoolIterationParams {
     //prize money to winner
     pub prize_money: u32,
     // dividend amount in the pool
-    pub dividend_amount: u32
+    pub dividend_amount: u32,
+    // owner commission taken out of the pot before the prize and dividend
+    pub commission: u32,
+    // protocol fee taken out of the pot before the prize and dividend
+    pub protocol_fee: u32,
+    // dividend carried over from the previous iteration, added to this pot
+    pub carried_in: u32,
+    // part of the dividend credited to the members
+    pub dividend_distributed: u32,
+    // early payment discounts given on the dues, funded from the dividend
+    pub discounts: u32,
+    // yield earned lending the pot out before the payout, added to the dividend
+    pub yield_earned: u32,
+    // part of the pot set aside for the insurance reserve
+    pub reserve_cut: u32,
+    // dues missing from the pot made up for by the reserve
+    pub reserve_backfill: u32,
+    // ledger timestamp at which the iteration was started
+    pub start_time: u64,
+    // due every subscriber pays for this iteration, in the settlement token
+    pub due_amount: u32,
+    // sale of the unclaimed prize to another member, empty unless the winner sold it
+    pub prize_assignments: Vec<PrizeAssignment>
 }
 
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum IterationStatus {
+    // not started yet, the window is projected from the frequency
+    Upcoming,
+    // started and still accepting on-time payments
+    Open,
+    // past its deadline or followed by a newer iteration
+    Closed
+}
 
-pub trait SnbPoolTrait {
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct ScheduleEntry {
+    pub iteration: u32,
+    // ledger timestamp at which the iteration opens
+    pub open_ts: u64,
+    // last ledger timestamp at which a payment is on-time, grace period included
+    pub deadline_ts: u64,
+    pub status: IterationStatus
+}
 
-    fn get_state(env: Env) -> State;
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct ResourceEstimate {
+    // number of subscribers currently in the pool
+    pub subscribers: u32,
+    // number of iterations recorded so far
+    pub iterations: u32,
+    // size in bytes of the instance entry holding the pool without its members and iterations
+    pub pool_entry_bytes: u32,
+    // size in bytes of the largest member and iteration entries today
+    pub member_entry_bytes: u32,
+    pub iteration_entry_bytes: u32,
+    // size in bytes of the largest member and iteration entries once all iterations have been closed
+    pub projected_member_bytes: u32,
+    pub projected_iteration_bytes: u32,
+    // ledger entries read and written when an iteration is closed
+    pub close_read_entries: u32,
+    pub close_write_entries: u32
+}
 
-    //Initialise pool 
-    fn initialize(e: Env,  user: Address, no_of_subs: u32, amount: u32, frequency: Frequency);
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PoolStats {
+    // number of subscribers currently in the pool
+    pub subscribers: u32,
+    // dues collected over every iteration so far
+    pub total_collected: u32,
+    // prizes assigned to winners so far
+    pub total_prizes: u32,
+    // collected dues left over after the prizes
+    pub total_dividends: u32,
+    // late fees and interest paid by subscribers
+    pub penalties_collected: u32,
+    // share of the dues so far paid on-time, missed ones included
+    pub on_time_rate_bps: u32,
+    // iterations of the cycle not started yet
+    pub remaining_iterations: u32
+}
 
-    //join the pool
-    fn join(e: Env, user: Address);
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PayerStats {
+    // address the payments came from
+    pub payer: Address,
+    // number of contributions made by this payer
+    pub payments: u32,
+    // total amount contributed by this payer
+    pub total_amount: u32,
+    // last iteration this payer contributed to
+    pub last_iteration: u32
+}
 
-    //Set pool winner
-    fn set_pool_winner(e: Env, iteration: u32, prize_amount: u32, subscriber: Address);
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PaymentRecord {
+    // iteration the payment was made for
+    pub iteration: u32,
+    // address the tokens came from
+    pub payer: Address,
+    // tokens transferred, late fee and premium included, early discount taken off
+    pub amount: u32,
+    // early payment discount given on the due
+    pub discount: u32,
+    // ledger timestamp of the payment
+    pub timestamp: u64,
+    // paid after the deadline plus the grace period
+    pub late: bool
+}
 
-    //Get pool winner of a specific iteration
-    fn get_pool_winner(e: Env, iteration: u32) -> Address;
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum SnapshotSection {
+    Subscribers,
+    Iterations,
+    Payments
+}
 
-    //Get subscriber details
-    fn get_subscriber_details(e: Env, subscriber_address: Address) -> Subscriber;
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum SnapshotEntry {
+    Subscriber(Address, Subscriber),
+    Iteration(u32, PoolIterationParams),
+    // subscriber, index in the payment history of the subscriber, payment
+    Payment(Address, u32, PaymentRecord)
+}
 
-    //start a new iteration
-    fn start_new_iteration(e: Env, iteration: u32, dummyAddress: Address);
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct SnapshotPage {
+    // layout of the entries, see SNAPSHOT_VERSION
+    pub version: u32,
+    pub entries: Vec<SnapshotEntry>,
+    // cursor of the next page, 0 once the section is exported
+    pub next_cursor: u32,
+    // entries in the section
+    pub total: u32
+}
 
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum FallbackPolicy {
+    // draw a winner uniformly at random among the candidates
+    RandomDraw,
+    // refuse to pick a winner until the strategy is fixed
+    Halt
 }
 
- 
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct WinnerStrategy {
+    // external contract selecting the winner, the built-in random draw is used when not set
+    pub contract: Option<Address>,
+    // what to do when the strategy contract call fails
+    pub fallback: FallbackPolicy
+}
 
-pub trait Reputation {
-    fn addReputation(e: Env,  subscriber: Address, reputation: u32);
-    fn getReputation(e: Env,  subscriber: Address) -> u32;
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct EarlyDiscount {
+    // discount on the due, in basis points of it
+    pub discount_bps: u32,
+    // part of the iteration window, from its start, in which payments get the discount
+    pub window_bps: u32
 }
 
-const STATE: Symbol = symbol_short!("STATE");
-const INTIALIZED: Symbol = symbol_short!("INITD");
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PenaltyConfig {
+    // fee charged on a late payment
+    pub late_fee_bps: u32,
+    // cap on penalties plus interest a member pays over the cycle, relative to their total dues
+    pub max_penalty_bps: u32
+}
 
+#[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
+pub struct ProtectionLimits {
+    // fee charged on a late payment
+    pub late_fee_bps: u32,
+    // cap on penalties plus interest a member pays over the cycle, relative to their total dues
+    pub max_penalty_bps: u32,
+    // the cap as an amount, for a member paying every iteration of the cycle
+    pub max_penalty_amount: u32
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct State {
-      pub pool_params: PoolParams,
-      pub current_iteration: u32,
-      // A map data structure from subscriber address to subscriber
-      pub subcriber_map: Map<Address, Subscriber>,
-  
-      // A map data structure from iteration to PoolIterationParams
-      pub pool_iteration_map: Map<u32, PoolIterationParams>  
+#[contracttype]
+pub struct ReputationDecay {
+    // length of the period after which inactive reputation starts to fade
+    pub period_secs: u64,
+    // reputation lost for every full period without a reputation change
+    pub points_per_period: u32
 }
 
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct SwapConfig {
+    // router converting the other tokens into the pool token
+    pub router: Address,
+    // tokens dues can be paid in besides the pool token
+    pub tokens: Vec<Address>,
+    // most the swap can cost over the router quote, in basis points of it
+    pub max_slippage_bps: u32
+}
 
-#[contract]
-pub struct HelloContract;
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum ParamChange {
+    // owner commission in basis points
+    Commission(u32),
+    // late fee and cap on penalties
+    ProtectionLimits(PenaltyConfig),
+    // token dues and collateral are paid in, only before the pool starts and holds any of it
+    Token(Address)
+}
 
-#[contractimpl]
-impl SnbPoolTrait for HelloContract {
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PendingChange {
+    pub change: ParamChange,
+    // ledger timestamp of the proposal
+    pub proposed_at: u64,
+    // earliest ledger timestamp it can be carried out at
+    pub executable_at: u64
+}
 
-    fn get_state(env: Env) -> State {
-        //let mut sampleAddress = String::from("GCCVPYFOHY7ZB7557JKENAX62LUAPLMGIWNZJAFV2MITK6T32V37KEJU");
-        env.storage().instance().get(&STATE).unwrap()
-    }
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct GuardianSet {
+    // addresses sharing the emergency role
+    pub guardians: Vec<Address>,
+    // approvals needed to take an action
+    pub threshold: u32
+}
 
-    fn initialize(e: Env, user: Address, no_of_subs: u32, amount: u32, frequency: Frequency) {
-        //Check if the pool is already initialized
-        let initialized = e.storage().instance().get(&INTIALIZED).unwrap_or_default();
-        if initialized {
-            panic!("Pool is already initialized");
-        }
-        let mut state = Self::get_state(e.clone());
-        user.require_auth();
-         //Create a PoolParams instance
-         let pool_params = PoolParams {
-            no_of_subs: no_of_subs,
-            frequency: frequency,
-            sub_amount: amount,
-            pool_owner: user.clone()
-        };
-        state.pool_params = pool_params;
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct OwnerCouncil {
+    // addresses sharing the pool owner role
+    pub owners: Vec<Address>,
+    // approvals an admin operation needs before it can be carried out
+    pub threshold: u32
+}
 
-        //Add owner to the subscriber list
-        let subscriber = Subscriber {
-            winner_at_iter: 0,
-            prev_due_amount: 0,
-            prize_money: 0
-        };
-        state.subcriber_map.set(user.clone(), subscriber);
-    
-        
-        //save the state in the storage
-        e.storage().instance().set(&STATE, &state);
-        e.storage().instance().set(&INTIALIZED, &true);
-    }
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Correction {
+    pub winner_before: Address,
+    pub winner_after: Address,
+    pub prize_before: u32,
+    pub prize_after: u32
+}
 
-    fn join(e: Env, user: Address)  {
-        let mut state = Self::get_state(e.clone());
-        // check if the subscriber is already in the pool. Only one address allowed in apool
-        if state.subcriber_map.contains_key(user.clone()) {
-            panic!("Subscriber is already in the pool");
-        }
-        user.require_auth();
-        // Create Subscriber instance with default values
-        let subscriber = Subscriber {
-            winner_at_iter: 0,
-            prev_due_amount: 0,
-            prize_money: 0
-        };
-        //Save the subscriber in the subcriber_map
-        state.subcriber_map.set(user, subscriber);
-        e.storage().instance().set(&STATE, &state);
-    }
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct SettlementSummary {
+    // iterations the pool ran
+    pub iterations: u32,
+    // dues collected over every iteration
+    pub total_collected: u32,
+    // prizes assigned to the winners
+    pub total_prizes: u32,
+    // collected dues left over after the prizes
+    pub total_dividends: u32,
+    // dividend left over after the last iteration, shared among the members
+    pub dust_swept: u32,
+    // collateral sent back to the members
+    pub collateral_returned: i128
+}
 
-    fn set_pool_winner(e: Env, iteration: u32, prize_amount: u32, subscriber: Address) {
-        let mut state = Self::get_state(e.clone());
-        // get the subscriber details for given address
-        let mut subr:Subscriber = state.subcriber_map.get(subscriber.clone()).unwrap();
-        // check if the subscriber is already marked as a winner
-        if subr.winner_at_iter != 0 {
-            panic!("Subscriber is already a winner");
-        }
-        //set iterarion for this subscriber
-        subr.winner_at_iter = iteration;
-        //set prize money for this subscriber
-        subr.prize_money = prize_amount;
-        //save the subscriber in the subcriber_map
-        state.subcriber_map.set(subscriber.clone(), subr);
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PoolArchive {
+    // iterations the pool ran
+    pub iterations: u32,
+    // members the pool had at the end
+    pub members: u32,
+    // dues collected over every iteration
+    pub total_collected: u32,
+    // prizes assigned to the winners
+    pub total_prizes: u32,
+    // collected dues left over after the prizes
+    pub total_dividends: u32,
+    // sha256 of the XDR of the winners, in the order of the iterations they won
+    pub winners_hash: BytesN<32>,
+    // ledger timestamp the pool was archived at
+    pub archived_at: u64
+}
 
-        // get the pool iteration details
-        let mut pool_iteration:PoolIterationParams = state.pool_iteration_map.get(iteration).unwrap();
-        // set the winner for the given iteration
-        pool_iteration.winner = subscriber.clone();
-        // set the prize money for the given iteration
-        pool_iteration.prize_money = prize_amount;
-        //set iteration to the pool iteration map
-        pool_iteration.dividend_amount = pool_iteration.amount_collected - prize_amount;
-        state.pool_iteration_map.set(iteration, pool_iteration);
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum GuardianAction {
+    // stop tokens leaving the pool
+    Pause,
+    // let them leave again
+    Unpause,
+    // drop the pending emergency withdrawal
+    VetoWithdrawal,
+    // let the owner fix the winner or the prize of an iteration
+    CorrectIteration(u32)
+}
 
-        //save the state in the storage
-        e.storage().instance().set(&STATE, &state);
-    }
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct OutflowLimit {
+    // length of the window payouts are counted over
+    pub window_secs: u64,
+    // most that can be paid out in a window before payouts pause
+    pub max_outflow: i128
+}
 
-    fn get_pool_winner(e: Env, iteration: u32) -> Address {
-        let mut state = Self::get_state(e.clone());
-        //return iteration winner
-        return  state.pool_iteration_map.get(iteration).unwrap().winner
-    }
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct OutflowWindow {
+    // ledger timestamp the window started at
+    pub start: u64,
+    // tokens paid out since
+    pub outflow: i128
+}
 
-    fn get_subscriber_details(e: Env, subscriber_address: Address) -> Subscriber {
-        let mut state = Self::get_state(e.clone());
-        //get subscriber details by address
-        return  state.subcriber_map.get(subscriber_address).unwrap();
-    }
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PoolTerms {
+    // due per iteration
+    pub sub_amount: u32,
+    pub frequency: Frequency,
+    // number of seats, one iteration each
+    pub no_of_subs: u32
+}
 
-    //start new iteration
-    fn start_new_iteration(e: Env, iteration: u32, dummyAddress: Address) {
-        let mut state = Self::get_state(e.clone());
-        //create a new instance of PoolIterationParams
-        let pool_iteration = PoolIterationParams {
-            current_iteration: iteration,
-            amount_collected: 0,
-            winner: dummyAddress.clone(),
-            prize_money: 0,
-            dividend_amount: 0
-        };
-        //save the pool iteration in the pool_iteration_map
-        state.pool_iteration_map.set(iteration, pool_iteration);
-        //save the pool iteration map in the storage
-        e.storage().instance().set(&STATE, &state);
-    }
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct TermsProposal {
+    pub id: u32,
+    // terms the pool switches to once the members approve them
+    pub terms: PoolTerms,
+    // members who approved the change
+    pub approvals: Vec<Address>,
+    // members who rejected it
+    pub rejections: Vec<Address>,
+    // ledger timestamp of the proposal
+    pub proposed_at: u64
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum DisputeOutcome {
+    // the complaint doesn't hold, the payout goes ahead
+    Dismissed,
+    // the complaint holds, the remedy is applied separately
+    Upheld
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum DisputeStatus {
+    // waiting for a resolution, the payout of the iteration is held back
+    Open,
+    Dismissed,
+    Upheld
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Dispute {
+    pub id: u32,
+    // member who raised it
+    pub subscriber: Address,
+    // iteration whose payout is disputed
+    pub iteration: u32,
+    // reason given, as a code agreed off-chain
+    pub reason_code: u32,
+    // ledger timestamp it was raised at
+    pub raised_at: u64,
+    pub status: DisputeStatus
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct EmergencyWithdrawal {
+    // address receiving the tokens
+    pub to: Address,
+    pub amount: i128,
+    // ledger timestamp of the proposal
+    pub proposed_at: u64,
+    // earliest ledger timestamp it can be carried out at
+    pub executable_at: u64
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct IdleDeposit {
+    // iteration whose pot the funds belong to
+    pub iteration: u32,
+    // pot funds lent out through the yield adapter
+    pub amount: u32
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct AccountingCurrency {
+    // oracle quoting the pool token in the reference currency
+    pub oracle: Address,
+    // due per iteration in the reference currency, at the oracle precision
+    pub reference_amount: u32,
+    // oldest price the pool converts dues at, in seconds
+    pub max_price_age_secs: u64
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Sponsorship {
+    // address that put up the tokens and gets back what is left
+    pub sponsor: Address,
+    // tokens left for the dues of the subscriber
+    pub balance: u32,
+    // tokens used for the dues so far
+    pub drawn: u32
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct SeatInsurance {
+    // premiums paid into the insurance fund so far
+    pub premiums_paid: u32,
+    // missed dues paid by the fund, at most one per cycle
+    pub claims: u32,
+    // iteration the fund paid for, 0 if none
+    pub covered_iteration: u32,
+    // part of the claim paid back to the fund out of the collateral seized from the subscriber
+    pub repaid: u32
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct ReminderRound {
+    // iteration the reminders are for
+    pub iteration: u32,
+    // day they were last sent on, counted from the epoch
+    pub day: u64,
+    // position of the first unpaid member the next page lists
+    pub next: u32,
+    // every unpaid member was listed that day
+    pub done: bool
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Profile {
+    // name the member is shown under
+    pub display_name: String,
+    // hash of a contact handle shared off-chain, empty when the member gave none
+    pub contact_hash: Bytes
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Referral {
+    // address that brought the member in
+    pub referrer: Address,
+    // reward offered when the member joined
+    pub reward: u32,
+    // the reward was paid to the referrer
+    pub paid: bool
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct AssetHolding {
+    // token the dues were paid in
+    pub token: Address,
+    // amount of the token the pool holds
+    pub held: i128,
+    // pool tokens it still has to be converted into
+    pub pending: u32
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Arrears {
+    // dues missed in closed iterations
+    pub principal: u32,
+    // interest accrued on them
+    pub interest: u32
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PrizeOffer {
+    // member the winner offers the unclaimed prize to
+    pub buyer: Address,
+    // tokens the buyer pays the winner for it
+    pub price: u32,
+    // ledger timestamp at which the offer was made
+    pub offered_at: u64
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PrizeAssignment {
+    // winner who sold the prize
+    pub from: Address,
+    // member the rest of the prize is paid to
+    pub to: Address,
+    // tokens the buyer paid the winner
+    pub price: u32,
+    // part of the prize not paid out yet when it was sold
+    pub unclaimed: u32,
+    // ledger timestamp of the sale
+    pub assigned_at: u64
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PoolRecord {
+    // wasm the pool was deployed from
+    pub wasm_hash: BytesN<32>,
+    // address that deployed the pool
+    pub deployer: Address,
+    // the wasm of the pool was quarantined after the deployment
+    pub flagged: bool
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct SlashSettlement {
+    // collateral and credit taken from the defaulted subscriber
+    pub seized: i128,
+    // paid into the current pot for the due of the subscriber
+    pub to_pot: u32,
+    // paid back to the insurance fund for the due it covered
+    pub to_insurance: u32,
+    // credited to the members in good standing in equal shares
+    pub to_members: u32,
+    // remainder of the shares, kept with the seized collateral
+    pub kept: i128
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct BucketBalance {
+    // internal account the tokens are held for
+    pub name: Symbol,
+    pub amount: i128
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct ReconciliationReport {
+    // token the buckets are held in
+    pub token: Address,
+    // tokens the contract should hold for each internal account
+    pub buckets: Vec<BucketBalance>,
+    // sum of the buckets
+    pub expected_balance: i128,
+    // token balance of the contract
+    pub actual_balance: i128,
+    // actual minus expected balance, negative when the pool is short
+    pub discrepancy: i128
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum LedgerAccount {
+    // pool tokens held by the contract
+    Cash,
+    // pot funds lent out through the yield adapter
+    Lent,
+    // dues held in other tokens, valued at the pool tokens they convert into
+    Unsettled,
+    Pot,
+    Dividends,
+    // commission, protocol fees and penalties
+    Fees,
+    // collateral of the members and applicants, seized collateral included
+    Collateral,
+    // insurance fund and reserve
+    Insurance,
+    // credit and sponsorships held for the members
+    Members
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct LedgerBalance {
+    pub account: LedgerAccount,
+    pub debits: i128,
+    pub credits: i128,
+    // debits minus credits for the token accounts, credits minus debits for the others
+    pub balance: i128
 }
 
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct TrialBalance {
+    pub accounts: Vec<LedgerBalance>,
+    pub total_debits: i128,
+    pub total_credits: i128,
+    // true when every posting debited as much as it credited
+    pub balanced: bool
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PrizeLoan {
+    // dues the winner still owed when the prize was paid
+    pub principal: u32,
+    // part of the principal not repaid yet
+    pub outstanding: u32,
+    // interest charged on every repayment, in basis points of it
+    pub interest_bps: u32,
+    // interest paid so far
+    pub interest_paid: u32,
+    // due repaying the loan every iteration
+    pub installment: u32,
+    // iterations left to repay the loan
+    pub remaining_installments: u32
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PaymentPreview {
+    pub iteration: u32,
+    // part of the due still to pay
+    pub due: u32,
+    // paid after the deadline plus the grace period
+    pub late: bool,
+    pub late_fee: u32,
+    // insurance premium added to the due
+    pub premium: u32,
+    // interest on the prize loan repaid with the due
+    pub loan_interest: u32,
+    // early payment discount taken off
+    pub discount: u32,
+    // covered by the sponsorship of the subscriber
+    pub sponsored: u32,
+    // covered by the credit of the subscriber
+    pub from_credit: u32,
+    // tokens the payer transfers
+    pub transfer: u32
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct JoinPreview {
+    // collateral transferred on joining
+    pub collateral: i128,
+    // due of every iteration at the current price
+    pub due: u32,
+    // seats still free
+    pub seats_left: u32
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PayoutPreview {
+    pub iteration: u32,
+    // dues collected so far
+    pub collected: u32,
+    // owner commission taken off the pot
+    pub commission: u32,
+    // protocol fee taken off the pot
+    pub protocol_fee: u32,
+    // dividend carried from the previous iteration
+    pub carried_in: u32,
+    // early payment discounts given on the dues
+    pub discounts: u32,
+    // part of the pot set aside for the reserve
+    pub reserve_cut: u32,
+    // dues missing from the pot the reserve makes up for
+    pub reserve_backfill: u32,
+    // largest prize, what the winner doesn't get goes to the dividend. Yield from idle funds comes on top
+    pub pot: u32
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct FeeBalances {
+    // commission taken out of the pots so far, recorded per iteration
+    pub commission_charged: u32,
+    // commission the owner hasn't claimed yet
+    pub commission_unclaimed: u32,
+    // protocol fees taken out of the pots so far, recorded per iteration
+    pub protocol_fees_charged: u32,
+    // protocol fees the treasurer hasn't withdrawn yet
+    pub protocol_fees_unwithdrawn: u32,
+    // late fees and interest paid on top of the dues, never part of a pot
+    pub penalties_collected: u32
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum ConfigValue {
+    // the setting was not configured
+    Unset,
+    Bool(bool),
+    U32(u32),
+    U64(u64),
+    Address(Address),
+    Strategy(WinnerStrategy),
+    Penalty(PenaltyConfig),
+    Decay(ReputationDecay),
+    Currency(AccountingCurrency),
+    Swap(SwapConfig),
+    Discount(EarlyDiscount),
+    Guardians(GuardianSet),
+    Outflow(OutflowLimit),
+    Terms(PoolTerms),
+    Council(OwnerCouncil)
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct ConfigChange {
+    // setting that was changed
+    pub key: Symbol,
+    // address that made the change
+    pub changed_by: Address,
+    // ledger timestamp of the change
+    pub timestamp: u64,
+    pub old_value: ConfigValue,
+    pub new_value: ConfigValue,
+    // first iteration the new value applies to
+    pub effective_iteration: u32
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct FairLaunch {
+    // registrations are accepted until this ledger timestamp
+    pub window_end: u64,
+    // addresses waiting for the seat draw
+    pub registrations: Vec<Address>
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    // every seat of the pool is taken
+    PoolFull = 1
+}
+
+pub const PERCENTAGE_MULTIPLIER: u32 = 10_000; // 1 unit = 0.01%
+pub(crate) const BALANCE_BUMP_AMOUNT: u32 = 518400; // 30 days
+pub(crate) const BALANCE_BUMP_THREASHOLD: u32 = 120960; // 7 days
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    PayerStats(Address),   // Vec<PayerStats>
+    Collateral(Address),   // i128
+    WinnerStrategy,        // WinnerStrategy
+    Reputation(Address),   // u32
+    PenaltyConfig,         // PenaltyConfig
+    FairLaunch,            // FairLaunch
+    ReputationContract,    // Address
+    AccountingCurrency,    // AccountingCurrency
+    ReputationDecay,       // ReputationDecay
+    ReputationUpdatedAt(Address), // u64
+    ConfigChangeCount,     // u32
+    ConfigChange(u32),     // ConfigChange
+    PrivacyMode,           // bool
+    WinnerNote(Address),   // Bytes
+    CancelVotes,           // Vec<Address>
+    InsurancePremium,      // u32
+    SeatInsurance(Address), // SeatInsurance
+    Waitlist,              // Vec<Address>
+    BadgeCount,            // u32
+    Badge(Address),        // u32
+    BadgeOwner(u32),       // Address
+    PaymentCount(Address), // u32
+    Payment(Address, u32), // PaymentRecord
+    MinSubscribers,        // u32
+    ShortfallApproval(u32), // String
+    CommissionBps,         // u32
+    CommissionBalance,     // u32
+    ProtocolFee,           // ProtocolFee
+    TreasuryBalance,       // u32
+    Carryover,             // u32
+    VestingTranches,       // u32
+    LoanInterestBps,       // u32
+    EarlyDiscount,         // EarlyDiscount
+    Sponsorship(Address),  // Sponsorship
+    AllowlistEnabled,      // bool
+    Allowlisted(Address),  // bool
+    Denied(Address),       // bool
+    AttestationContract,   // Address
+    SwapConfig,            // SwapConfig
+    YieldAdapter,          // Address
+    IdleDeposit            // IdleDeposit
+}
+
+// keys of the oversight features, kept apart from DataKey which is at the variant limit of a contract type
+#[derive(Clone)]
+#[contracttype]
+pub enum GovernanceKey {
+    EmergencyWithdrawal,   // EmergencyWithdrawal
+    TimelockDelay,         // u64
+    PendingChangeCount,    // u32
+    PendingChange(u32),    // PendingChange
+    Guardians,             // GuardianSet
+    Paused,                // bool
+    GuardianApprovals(GuardianAction), // Vec<Address>
+    OutflowLimit,          // OutflowLimit
+    OutflowWindow,         // OutflowWindow
+    DisputeCount,          // u32
+    Dispute(u32),          // Dispute
+    OpenDisputes(u32),     // u32
+    ExpelVotes(Address),   // Vec<Address>
+    TermsProposalCount,    // u32
+    TermsProposal,         // TermsProposal
+    OwnerCouncil,          // OwnerCouncil
+    OwnerApprovals(BytesN<32>), // Vec<Address>
+    CorrectionApproval(u32), // bool
+    PendingOperations      // Vec<BytesN<32>>
+}
+
+
+pub trait SnbPoolTrait {
+
+    //Deprecated, reads the entry of every member and iteration and outgrows the read limits of large pools.
+    //Use get_pool_state along with get_subscribers and get_iterations instead
+    fn get_state(env: Env) -> State;
+
+    //Get the pool without its members and iterations
+    fn get_pool_state(e: Env) -> PoolState;
+
+    //Get the parameters the pool was set up with
+    fn get_pool_params(e: Env) -> PoolParams;
+
+    //Get the iteration the pool is in, 0 before it starts
+    fn get_current_iteration(e: Env) -> u32;
+
+    //Get a started iteration
+    fn get_iteration(e: Env, iteration: u32) -> PoolIterationParams;
+
+    //Get the keys of the persistent entries holding the members and iterations, to restore the archived ones
+    //of a pool left idle past their TTL
+    fn restore_hints(e: Env) -> Vec<Val>;
+
+    //Initialise pool 
+    //an optional struct can't be a field of a contracttype, so the protocol fee comes apart from the config
+    fn initialize(e: Env, user: Address, config: PoolConfig, protocol_fee: Option<ProtocolFee>);
+
+    //Get the name of the pool
+    fn get_name(e: Env) -> String;
+
+    //Get the description of the pool
+    fn get_description(e: Env) -> String;
+
+    //Get the token contract dues and collateral are paid in, any SEP-41 token including the native XLM asset contract
+    fn get_token(e: Env) -> Address;
+
+    //join the pool, optionally naming the referrer who brought the user in
+    fn join(e: Env, user: Address, guarantor: Option<Address>, referrer: Option<Address>);
+
+    //Only let addresses on the allowlist take a seat, before the pool starts
+    fn set_allowlist_enabled(e: Env, enabled: bool);
+
+    //Check if seats are restricted to the allowlist
+    fn is_allowlist_enabled(e: Env) -> bool;
+
+    //Add addresses to the allowlist, only the owner can do it
+    fn add_to_allowlist(e: Env, users: Vec<Address>);
+
+    //Remove an address from the allowlist, only the owner can do it
+    fn remove_from_allowlist(e: Env, user: Address);
+
+    //Check if an address is on the allowlist
+    fn is_allowlisted(e: Env, user: Address) -> bool;
+
+    //Bar an address from taking or handing over a seat, only the owner can do it
+    fn add_to_denylist(e: Env, user: Address);
+
+    //Lift the bar on an address, only the owner can do it
+    fn remove_from_denylist(e: Env, user: Address);
+
+    //Check if an address is on the denylist
+    fn is_denied(e: Env, user: Address) -> bool;
+
+    //Get the contract verifying the addresses that take a seat, if any
+    fn get_attestation_contract(e: Env) -> Option<Address>;
+
+    //Seat several members at once before the pool starts, only the owner can do it and every member signs for their seat
+    fn batch_add_subscribers(e: Env, members: Vec<Address>);
+
+    //Leave the pool before it starts, getting the collateral back
+    fn leave(e: Env, user: Address);
+
+    //Take a member out of the pool before it starts, returning their collateral and freeing their seat. Only the owner can do it
+    fn remove_member(e: Env, subscriber: Address);
+
+    //Commit to a number of shares before the pool starts: the due, the odds of winning and the dividend scale with it
+    fn set_shares(e: Env, subscriber: Address, shares: u32);
+
+    //Queue for a seat once every seat is taken, depositing the pool collateral
+    fn join_waitlist(e: Env, user: Address);
+
+    //Leave the waitlist, getting the collateral back
+    fn leave_waitlist(e: Env, user: Address);
+
+    //Get the addresses waiting for a seat, first in line first
+    fn get_waitlist(e: Env) -> Vec<Address>;
+
+    //Cancel the pool before the first iteration, only the owner can do it
+    fn cancel_pool(e: Env);
+
+    //Cancel a pool that isn't full at its join deadline so members can claim their refunds, anyone can call it
+    fn abort_unfilled(e: Env);
+
+    //Vote to cancel the pool, it gets cancelled once a majority of the members in good standing agree
+    fn vote_cancel(e: Env, subscriber: Address);
+
+    //Return the dues paid by the subscriber, less any prize received, and their collateral once the pool is cancelled
+    fn claim_refund(e: Env, subscriber: Address);
+
+    //Set the winner of an iteration and their prize, only the owner can do it. The next iteration is opened by start_new_iteration
+    fn set_pool_winner(e: Env, iteration: u32, prize_amount: u32, subscriber: Address);
+
+    //Get pool winner of a specific iteration
+    fn get_pool_winner(e: Env, iteration: u32) -> Address;
+
+    //Let a winner be picked for an iteration that hasn't collected every due, only the owner can do it and must give the reason
+    fn approve_shortfall(e: Env, iteration: u32, reason: String);
+
+    //Get the reason the owner gave for picking a winner of an underfunded iteration, if any
+    fn get_shortfall_approval(e: Env, iteration: u32) -> Option<String>;
+
+    //Pay out the prize won by the subscriber, or the tranches unlocked so far when prizes vest
+    fn claim_prize(e: Env, subscriber: Address);
+
+    //Offer the unclaimed prize of the winner to another member for a price, only the winner can do it
+    fn offer_prize(e: Env, winner: Address, buyer: Address, price: u32);
+
+    //Withdraw the offer of the winner's prize
+    fn cancel_prize_offer(e: Env, winner: Address);
+
+    //Get the open offer of the winner's prize, if any
+    fn get_prize_offer(e: Env, winner: Address) -> Option<PrizeOffer>;
+
+    //Pay the price of the offer to the winner and take over their unclaimed prize, only the buyer can do it
+    fn accept_prize_offer(e: Env, buyer: Address, winner: Address);
+
+    //Release prizes in equal tranches, one per iteration starting with the winning one, before the pool starts. 1 pays them at once
+    fn set_prize_vesting(e: Env, tranches: u32);
+
+    //Get the number of tranches prizes are released in
+    fn get_prize_vesting(e: Env) -> u32;
+
+    //Treat prizes as loans of the remaining dues of the winner, repaid with interest in basis points, before the pool starts. None turns it off
+    fn set_prize_loan(e: Env, interest_bps: Option<u32>);
+
+    //Get the interest charged on prize loans, None when prizes aren't loans
+    fn get_prize_loan_interest(e: Env) -> Option<u32>;
+
+    //Get the loan the subscriber took with the prize, if any
+    fn get_prize_loan(e: Env, subscriber: Address) -> Option<PrizeLoan>;
+
+    //Give a discount to dues paid early in the iteration window, funded from the dividend, before the pool starts. None stops it
+    fn set_early_discount(e: Env, discount: Option<EarlyDiscount>);
+
+    //Get the discount given for paying early, if any
+    fn get_early_discount(e: Env) -> Option<EarlyDiscount>;
+
+    //Get the dividend left over so far, added to the pot of the next iteration when it opens
+    fn get_carryover(e: Env) -> u32;
+
+    //Pay out the dividends credited to the subscriber
+    fn claim_dividends(e: Env, subscriber: Address);
+
+    //Credit part of the dividend of an iteration to a subscriber, only the owner can do it when the pool shares dividends at their discretion
+    fn allocate_dividend(e: Env, iteration: u32, subscriber: Address, amount: u32);
+
+    //Hand the winner notes out only in the claim event of their owner, before the pool starts
+    fn set_privacy_mode(e: Env, enabled: bool);
+
+    //Leave a note, encrypted for the subscriber, to be emitted when they claim their prize
+    fn set_winner_note(e: Env, subscriber: Address, note: Bytes);
+
+    //Set the display name and contact hash the subscriber is recognized by, at most MAX_DISPLAY_NAME_LEN bytes of name
+    fn set_profile(e: Env, subscriber: Address, display_name: String, contact_hash: Option<BytesN<32>>);
+
+    //Get the profile of the subscriber, if they set one
+    fn get_profile(e: Env, subscriber: Address) -> Option<Profile>;
+
+    //Remove the profile of a subscriber, only the owner can do it to moderate abusive names
+    fn clear_profile(e: Env, subscriber: Address);
+
+    //Publish a reminder listing up to MAX_REMINDERS_PER_CALL members yet to pay the current iteration, anyone can do it.
+    //Once a day, the calls of a day page through the unpaid members
+    fn emit_reminders(e: Env) -> Vec<Address>;
+
+    //Get the started iterations numbered from `from` to `to`, both included, at most MAX_ITERATIONS_PER_QUERY of them
+    fn get_iterations(e: Env, from: u32, to: u32) -> Vec<PoolIterationParams>;
+
+    //Get subscriber details
+    fn get_subscriber_details(e: Env, subscriber_address: Address) -> Subscriber;
+
+    //Get up to limit subscribers with their details, skipping the first offset ones, at most MAX_SUBSCRIBERS_PER_QUERY of them
+    fn get_subscribers(e: Env, offset: u32, limit: u32) -> Vec<(Address, Subscriber)>;
+
+    //Let the pool start once min_subscribers have joined instead of waiting for every seat, before the pool starts
+    fn set_min_subscribers(e: Env, min_subscribers: u32);
+
+    //Get the number of members the pool needs to start
+    fn get_min_subscribers(e: Env) -> u32;
+
+    //Lock the member list and open the first iteration, only the owner can do it once enough members have joined
+    fn start_pool(e: Env);
+
+    //Close the current iteration once its winner is set, or without one once its deadline and grace period have passed, and open the next one. Only the owner can do it
+    fn start_new_iteration(e: Env, iteration: u32);
+
+    //pay the due of a subscriber for the given iteration, or part of it when an amount is given. Anything over the due is kept as a credit.
+    //The payer registered by the subscriber can pay instead of them
+    fn pay_due(e: Env, subscriber: Address, iteration: u32, amount: Option<u32>, payer: Option<Address>);
+
+    //Allow an address to pay the dues of the subscriber on their behalf, None revokes it
+    fn set_payer(e: Env, subscriber: Address, payer: Option<Address>);
+
+    //Pull the due of a subscriber for the current iteration out of the allowance they gave the pool, anyone can do it
+    fn collect_due(e: Env, subscriber: Address);
+
+    //Pull the dues of up to MAX_COLLECTIONS_PER_CALL subscribers for the current iteration out of their allowances, anyone can do it.
+    //Members that can't be collected are skipped with an event saying why. Returns the number of dues collected
+    fn collect_all_due(e: Env, subscribers: Vec<Address>) -> u32;
+
+    //Get the credit a subscriber has left from paying more than owed, used for the next dues
+    fn get_credit_balance(e: Env, subscriber: Address) -> u32;
+
+    //Return the credit a subscriber has left once the pool has completed or was cancelled
+    fn withdraw_credit(e: Env, subscriber: Address);
+
+    //Get the window of every iteration of the cycle, projecting the ones not started yet
+    fn get_pool_schedule(e: Env) -> Vec<ScheduleEntry>;
+
+    //Get what the subscriber has to pay now for the latest iteration, fees and premiums included
+    fn get_due_amount(e: Env, subscriber: Address) -> i128;
+
+    //Charge interest in basis points on the missed dues of members every iteration, before the pool starts
+    fn set_arrears_interest(e: Env, interest_bps: u32);
+
+    //Get the interest charged on missed dues every iteration
+    fn get_arrears_interest(e: Env) -> u32;
+
+    //Get the dues the subscriber missed and the interest accrued on them
+    fn get_arrears(e: Env, subscriber: Address) -> Arrears;
+
+    //Pay back missed dues, the interest first. None pays all of them
+    fn pay_arrears(e: Env, subscriber: Address, amount: Option<u32>);
+
+    //Get totals over the pool so far, for dashboards
+    fn get_pool_stats(e: Env) -> PoolStats;
+
+    //Compare the tokens the pool accounts for in each bucket with the token balance of the contract
+    fn reconcile(e: Env) -> ReconciliationReport;
+
+    //Get the fees taken out of the pots and what is left of them to withdraw, kept apart from the pot accounting
+    fn get_fee_balances(e: Env) -> FeeBalances;
+
+    //Get the balance of every internal ledger account with the totals of the postings, which have to match
+    fn trial_balance(e: Env) -> TrialBalance;
+
+    //Check the internal consistency of the pool and return the names of the invariants violated, empty when it holds
+    fn check_invariants(e: Env) -> Vec<Symbol>;
+
+    //Preview what joining takes from the user, panics with the reason the user can't join
+    fn preview_join(e: Env, user: Address) -> JoinPreview;
+
+    //Preview the charges and the transfer of paying the rest of the due of the current iteration
+    fn preview_pay(e: Env, user: Address) -> PaymentPreview;
+
+    //Preview the fees and the pot of an iteration if its winner were picked now
+    fn preview_winner_payout(e: Env, iteration: u32) -> PayoutPreview;
+
+    //Set the part of every pot, in basis points of the dues collected, set aside to make up for defaulters, before the pool starts.
+    //What is left of the reserve is shared between the members once the pool completes
+    fn set_reserve_rate(e: Env, rate_bps: u32);
+
+    //Get the part of every pot set aside for the reserve, in basis points
+    fn get_reserve_rate(e: Env) -> u32;
+
+    //Get the tokens held in the reserve
+    fn get_reserve_balance(e: Env) -> u32;
+
+    //Set the reward paid out of the owner commission to the referrer of every member who completes the pool without defaulting, before the pool starts
+    fn set_referral_reward(e: Env, reward: u32);
+
+    //Get the reward offered to referrers
+    fn get_referral_reward(e: Env) -> u32;
+
+    //Get who referred a member and the reward they are owed
+    fn get_referral(e: Env, referred: Address) -> Option<Referral>;
+
+    //Pay the referrer of a member their reward once the pool completed, only the referrer can do it
+    fn claim_referral_reward(e: Env, referred: Address);
+
+    //Deploy a new pool with the parameters of this completed pool through the factory this pool was deployed from, only the owner can do it
+    fn create_successor(e: Env, factory: Address) -> Address;
+
+    //Get the pool that succeeds this one, if any
+    fn get_successor(e: Env) -> Option<Address>;
+
+    //Get the pool this one succeeds, if any
+    fn get_predecessor(e: Env) -> Option<Address>;
+
+    //Join the successor pool, carrying over the reputation and collateral of the member
+    fn opt_into_successor(e: Env, member: Address);
+
+    //Link the pool to the pool it succeeds and the reputation ledger it used, only the owner can do it before the pool starts
+    fn link_predecessor(e: Env, predecessor: Address, reputation_contract: Option<Address>);
+
+    //Credit a member the reputation they earned in the predecessor, only the predecessor can do it
+    fn carry_over_reputation(e: Env, member: Address, reputation: u32);
+
+    //Dry-run closing an iteration for the current members and report the size of each stored entry, today and once every iteration is closed, and the entries closing touches
+    fn estimate_resources(e: Env) -> ResourceEstimate;
+
+    //Set the number of missed iterations after which a subscriber defaults, before the pool starts
+    fn set_max_missed_iterations(e: Env, max_missed_iterations: u32);
+
+    //Set the reputation applicants need to join the pool, before the pool starts
+    fn set_min_reputation(e: Env, min_reputation: u32);
+
+    //Mark a subscriber as defaulted
+    fn mark_defaulted(e: Env, subscriber: Address);
+
+    //Get up to limit payments made for a subscriber, oldest first, starting at the start-th payment
+    fn get_payment_history(e: Env, subscriber: Address, start: u32, limit: u32) -> Vec<PaymentRecord>;
+
+    //Get the addresses that paid dues for a subscriber with their aggregated contributions
+    fn get_payer_stats(e: Env, subscriber: Address) -> Vec<PayerStats>;
+
+    //Let the guarantor pay the overdue due of a subscriber for the latest iteration
+    fn cover_due(e: Env, guarantor: Address, subscriber: Address);
+
+    //Offer seat insurance for the given premium per iteration, before the pool starts. 0 stops offering it
+    fn set_insurance_premium(e: Env, premium: u32);
+
+    //Insure the seat of a subscriber: the premium is added to every due and the fund pays one missed due per cycle
+    fn buy_seat_insurance(e: Env, subscriber: Address);
+
+    //Get the seat insurance of a subscriber, if any
+    fn get_seat_insurance(e: Env, subscriber: Address) -> Option<SeatInsurance>;
+
+    //Get the id of the membership badge held by an address, if any
+    fn get_badge(e: Env, holder: Address) -> Option<u32>;
+
+    //Get the holder of a membership badge
+    fn badge_owner(e: Env, token_id: u32) -> Address;
+
+    //Deposit tokens earmarked for the dues of a subscriber, they are used before the subscriber pays anything
+    fn sponsor(e: Env, sponsor: Address, subscriber: Address, amount: u32);
+
+    //Get the sponsorship of a subscriber, if any
+    fn get_sponsorship(e: Env, subscriber: Address) -> Option<Sponsorship>;
+
+    //Return what is left of the sponsorship of a subscriber to the sponsor once the pool has completed or was cancelled
+    fn refund_sponsorship(e: Env, subscriber: Address);
+
+    //Get the collateral held for a subscriber
+    fn get_collateral(e: Env, subscriber: Address) -> i128;
+
+    //Return the collateral to the subscriber once the pool has completed
+    fn withdraw_collateral(e: Env, subscriber: Address);
+
+    //Seize the collateral of a defaulted subscriber: it pays their due in the current pot, then repays the insurance fund,
+    //and the rest is credited to the members in good standing
+    fn seize_collateral(e: Env, subscriber: Address) -> SlashSettlement;
+
+    //Set the contract selecting winners and the fallback used when it fails
+    fn set_winner_strategy(e: Env, strategy: WinnerStrategy);
+
+    //Get the winner strategy of the pool
+    fn get_winner_strategy(e: Env) -> WinnerStrategy;
+
+    //Select the winner of an iteration among the subscribers that haven't won yet, the whole pot is the prize
+    fn draw_winner(e: Env, iteration: u32) -> Address;
+
+    //Draw the winner of an iteration at random when the owner hasn't picked one FORCE_DRAW_TIMEOUT_SECS after its deadline, any member can do it
+    fn force_draw(e: Env, caller: Address, iteration: u32) -> Address;
+
+    //Set the owner commission taken out of every pot, in basis points up to MAX_COMMISSION_BPS, before the pool starts
+    fn set_commission(e: Env, commission_bps: u32);
+
+    //Get the owner commission in basis points
+    fn get_commission(e: Env) -> u32;
+
+    //Withdraw the commission credited to the owner
+    fn claim_commission(e: Env);
+
+    //Get the protocol fee of the pool, if any
+    fn get_protocol_fee(e: Env) -> Option<ProtocolFee>;
+
+    //Get the protocol fees accrued and not withdrawn yet
+    fn get_treasury_balance(e: Env) -> u32;
+
+    //Send the accrued protocol fees to the treasury, only the treasurer can do it
+    fn withdraw_treasury(e: Env);
+
+    //Set the late fee and the cap on penalties per member, both in basis points, before the pool starts
+    fn set_protection_limits(e: Env, late_fee_bps: u32, max_penalty_bps: u32);
+
+    //Get the late fee and the cap on penalties per member
+    fn get_protection_limits(e: Env) -> ProtectionLimits;
+
+    //Collect registrations until window_end and assign the free seats by random draw, instead of first-come-first-served joins
+    fn open_fair_launch(e: Env, window_end: u64);
+
+    //Register for the fair-launch seat draw, depositing the pool collateral
+    fn register_for_seat(e: Env, user: Address);
+
+    //Draw the free seats among the registrations once the window is over, refunding the others
+    fn close_fair_launch(e: Env) -> Vec<Address>;
+
+    //Get the open fair-launch window, if any
+    fn get_fair_launch(e: Env) -> Option<FairLaunch>;
+
+    //Share reputation through a ledger contract which has registered this pool, None keeps it local
+    fn set_reputation_contract(e: Env, contract: Option<Address>);
+
+    //Get the reputation ledger contract, if any
+    fn get_reputation_contract(e: Env) -> Option<Address>;
+
+    //Make reputation fade for every period it isn't updated, before the pool starts. None keeps it forever
+    fn set_reputation_decay(e: Env, decay: Option<ReputationDecay>);
+
+    //Get the reputation decay of the pool, if any
+    fn get_reputation_decay(e: Env) -> Option<ReputationDecay>;
+
+    //Get the reputation of a subscriber after decay
+    fn get_effective_reputation(e: Env, subscriber: Address) -> u32;
+
+    //Account dues in a reference currency, converted into the pool token at the start of every iteration. None settles sub_amount as is
+    fn set_accounting_currency(e: Env, currency: Option<AccountingCurrency>);
+
+    //Get the reference currency the pool accounts in, if any
+    fn get_accounting_currency(e: Env) -> Option<AccountingCurrency>;
+
+    //Accept dues in other tokens, swapped into the pool token through the router within the slippage limit, before the pool starts. None stops it
+    fn set_swap_config(e: Env, config: Option<SwapConfig>);
+
+    //Get the tokens the pool accepts besides its own and how they are swapped, if any
+    fn get_swap_config(e: Env) -> Option<SwapConfig>;
+
+    //Pay the rest of the due of a subscriber in one of the accepted tokens, swapped into the pool token. Returns the amount of `token` spent
+    fn pay_due_with_token(e: Env, subscriber: Address, iteration: u32, token: Address) -> i128;
+
+    //Keep dues paid in the other tokens as they are and convert them into the pool token at payout, before the pool starts
+    fn set_deferred_settlement(e: Env, enabled: bool);
+
+    //Check if dues paid in the other tokens are converted at payout
+    fn is_deferred_settlement(e: Env) -> bool;
+
+    //Get how much of every other token the pool holds and the pool tokens it still has to be converted into
+    fn get_asset_holdings(e: Env) -> Vec<AssetHolding>;
+
+    //Convert the held dues into the pool token ahead of the payout, anyone can do it. Returns the pool tokens bought
+    fn settle_contributions(e: Env) -> u32;
+
+    //Lend the idle pot out through an adapter contract until the payout, before the pool starts. None stops it
+    fn set_yield_adapter(e: Env, adapter: Option<Address>);
+
+    //Get the adapter the pot is lent out through, if any
+    fn get_yield_adapter(e: Env) -> Option<Address>;
+
+    //Move the pot collected so far in the current iteration to the yield adapter, only the owner can do it. Returns the amount moved
+    fn deposit_idle_funds(e: Env) -> u32;
+
+    //Get the pot funds currently with the yield adapter, if any
+    fn get_idle_deposit(e: Env) -> Option<IdleDeposit>;
+
+    //Announce a withdrawal of stuck tokens to an address, only the owner can do it and it can be carried out after a 72 hour delay
+    fn propose_emergency_withdraw(e: Env, to: Address, amount: i128) -> EmergencyWithdrawal;
+
+    //Carry out the emergency withdrawal once its delay has passed, only the owner can do it
+    fn execute_emergency_withdraw(e: Env);
+
+    //Drop the pending emergency withdrawal, only the owner can do it
+    fn cancel_emergency_withdraw(e: Env);
+
+    //Get the emergency withdrawal waiting for its delay, if any
+    fn get_emergency_withdrawal(e: Env) -> Option<EmergencyWithdrawal>;
+
+    //Give the members notice of fee, penalty and token changes, which then go through propose and execute, before the pool starts
+    fn set_timelock_delay(e: Env, delay_secs: u64);
+
+    //Get the notice given on fee, penalty and token changes, in seconds
+    fn get_timelock_delay(e: Env) -> u64;
+
+    //Queue a fee, penalty or token change until the notice is over, only the owner can do it. Returns the id of the change
+    fn propose_param_change(e: Env, change: ParamChange) -> u32;
+
+    //Carry out a queued change once its notice is over, only the owner can do it
+    fn execute_param_change(e: Env, id: u32);
+
+    //Drop a queued change, only the owner can do it
+    fn cancel_param_change(e: Env, id: u32);
+
+    //Get a queued change, if it is still pending
+    fn get_pending_change(e: Env, id: u32) -> Option<PendingChange>;
+
+    //Let a set of guardians pause payouts or veto an emergency withdrawal once enough of them approve, before the pool starts. None removes them
+    fn set_guardians(e: Env, guardians: Option<GuardianSet>);
+
+    //Get the guardians of the pool and the approvals they need, if any
+    fn get_guardians(e: Env) -> Option<GuardianSet>;
+
+    //Approve an action as a guardian, taking it once the threshold is reached. Returns true when the action was taken
+    fn guardian_approve(e: Env, guardian: Address, action: GuardianAction) -> bool;
+
+    //Get the guardians who approved an action not taken yet
+    fn get_guardian_approvals(e: Env, action: GuardianAction) -> Vec<Address>;
+
+    //Settle the pool once every member had their turn: the dividend left over is shared out, the collateral goes back,
+    //and the pool moves to Completed once everything owed is covered. Anyone can call it
+    fn settle_pool(e: Env) -> SettlementSummary;
+
+    //Drop the members, iterations and payment records of a settled pool once everything was claimed, keeping a summary of it.
+    //Anyone can call it
+    fn archive_pool(e: Env) -> PoolArchive;
+
+    //Get the summary of the pool, once archived
+    fn get_archive(e: Env) -> Option<PoolArchive>;
+
+    //Fix a mistaken winner or prize of the latest iteration won, before any of its prize is paid out. Only the owner can do it,
+    //once the guardians approved the correction of the iteration
+    fn correct_iteration(e: Env, iteration: u32, winner: Address, prize_amount: u32) -> Correction;
+
+    //Keep a member from winning or claiming while an issue with them is investigated, without pausing the pool. Only the owner can do it
+    fn freeze_member(e: Env, subscriber: Address);
+
+    //Let a frozen member win and claim again
+    fn unfreeze_member(e: Env, subscriber: Address);
+
+    //Get the members that are frozen
+    fn get_frozen_members(e: Env) -> Vec<Address>;
+
+    //Share the pool owner role among a council, admin operations then need its approvals instead of the owner's signature. None hands the role back to the owner
+    fn set_owner_council(e: Env, council: Option<OwnerCouncil>);
+
+    //Get the owner council and the approvals it needs, if any
+    fn get_owner_council(e: Env) -> Option<OwnerCouncil>;
+
+    //Approve an admin operation as a council owner. The operation is the entry point name followed by its arguments, and can be carried out once approved by the threshold. Returns the approvals it has
+    fn approve_operation(e: Env, owner: Address, operation: Vec<Val>) -> u32;
+
+    //Get the council owners who approved an operation not carried out yet
+    fn get_operation_approvals(e: Env, operation: Vec<Val>) -> Vec<Address>;
+
+    //Check if payouts are paused
+    fn is_paused(e: Env) -> bool;
+
+    //Pause payouts when more than max_outflow leaves the pool within a window, until the guardians unpause them, before the pool starts. None turns it off
+    fn set_outflow_limit(e: Env, limit: Option<OutflowLimit>);
+
+    //Get the ceiling on payouts per window, if any
+    fn get_outflow_limit(e: Env) -> Option<OutflowLimit>;
+
+    //Get the tokens paid out in the current window
+    fn get_outflow_window(e: Env) -> OutflowWindow;
+
+    //Dispute the outcome of an iteration as a member, holding back its prize until the dispute is resolved. Returns the id of the dispute
+    fn raise_dispute(e: Env, subscriber: Address, iteration: u32, reason_code: u32) -> u32;
+
+    //Resolve a dispute, only a guardian can do it or the owner when the pool has no guardians
+    fn resolve_dispute(e: Env, resolver: Address, dispute_id: u32, outcome: DisputeOutcome);
+
+    //Get a dispute by its id
+    fn get_dispute(e: Env, dispute_id: u32) -> Dispute;
+
+    //Get up to limit disputes, oldest first, starting at the start-th one
+    fn get_disputes(e: Env, start: u32, limit: u32) -> Vec<Dispute>;
+
+    //Propose expelling a defaulted member as a paid-up member, counting as a vote for it. Returns true when the member got expelled
+    fn propose_expulsion(e: Env, proposer: Address, subscriber: Address) -> bool;
+
+    //Vote for expelling a defaulted member, who loses the seat and collateral once a majority of the paid-up members agree. Returns true when the member got expelled
+    fn vote_expel(e: Env, voter: Address, subscriber: Address) -> bool;
+
+    //Get the members who voted for expelling a subscriber
+    fn get_expel_votes(e: Env, subscriber: Address) -> Vec<Address>;
+
+    //Propose new due, frequency or size before the pool starts, applied once a majority of the members who joined approve. Returns the id of the proposal
+    fn propose_terms(e: Env, terms: PoolTerms) -> u32;
+
+    //Approve or reject the open proposal as a member
+    fn vote_on_proposal(e: Env, voter: Address, proposal_id: u32, approve: bool);
+
+    //Withdraw the open proposal, only the owner can do it
+    fn cancel_terms_proposal(e: Env);
+
+    //Get the proposal open for a vote, if any
+    fn get_terms_proposal(e: Env) -> Option<TermsProposal>;
+
+    //Get up to limit configuration changes, oldest first, starting at the start-th change
+    fn get_config_history(e: Env, start: u32, limit: u32) -> Vec<ConfigChange>;
+
+    //Get the schema version of the contract and the identifier of the build it runs
+    fn get_version(e: Env) -> (u32, Symbol);
+
+    //Export a page of subscribers, iterations or payments for indexers, starting at cursor 0 and following next_cursor
+    fn export_snapshot(e: Env, section: SnapshotSection, cursor: u32) -> SnapshotPage;
+
+    //Move the seat of a member, with their collateral, payments, winnings and reputation, to a new address
+    fn rotate_address(e: Env, old: Address, new: Address);
+
+    //Hand the slot of a member, with their dues history and winner status, to a new member approved by the owner
+    fn transfer_slot(e: Env, from: Address, to: Address);
+
+    //Replace a member who has to drop out with a new one approved by the owner, who takes over their dues history and winner status
+    //and pays the dues they missed. The leaving member doesn't have to sign
+    fn substitute_member(e: Env, leaving: Address, incoming: Address);
+
+}
+
+ 
+
+#[allow(non_snake_case)]
+pub trait Reputation {
+    fn addReputation(e: Env,  subscriber: Address, reputation: u32);
+    fn getReputation(e: Env,  subscriber: Address) -> u32;
+}
+
+const INTIALIZED: Symbol = symbol_short!("INITD");
+
+// dividends shared out so far, summed up per unit they are shared by. Closing an iteration only moves the index,
+// members are credited what it grew by since they were last read
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DividendIndex {
+    // per share of the members who haven't won or defaulted
+    pub per_share: u32,
+    // per token paid in by the members who haven't defaulted, scaled by dividend::TOKEN_INDEX_SCALE
+    pub per_token: u128,
+    // reserve released per share of the members who haven't defaulted
+    pub released: u32
+}
+
+// the pool as kept in its instance entry, State without the members and iterations, which have entries of their own
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolState {
+    pub pool_params: PoolParams,
+    pub current_iteration: u32,
+    pub shortfall: u32,
+    pub seized_collateral: i128,
+    pub penalties_collected: u32,
+    pub status: PoolStatus,
+    pub insurance_fund: u32,
+    pub paid_out: u32,
+    pub started_at: u64,
+    pub active_shares: u32,
+    pub eligible_shares: u32,
+    pub contributions: u64,
+    pub defaulted_shares: Map<u32, u32>,
+    pub dividend_index: DividendIndex
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct State {
+      pub pool_params: PoolParams,
+      pub current_iteration: u32,
+      // A map data structure from subscriber address to subscriber
+      pub subcriber_map: Map<Address, Subscriber>,
+  
+      // A map data structure from iteration to PoolIterationParams
+      pub pool_iteration_map: Map<u32, PoolIterationParams>,
+
+      // dues missed by defaulted subscribers that the pool will not collect
+      pub shortfall: u32,
+
+      // collateral taken from defaulted subscribers
+      pub seized_collateral: i128,
+
+      // late fees and interest paid by subscribers
+      pub penalties_collected: u32,
+
+      // lifecycle stage of the pool
+      pub status: PoolStatus,
+
+      // premiums paid by insured subscribers, not yet used to cover missed dues
+      pub insurance_fund: u32,
+
+      // prizes and refunded dues paid out of the collected dues
+      pub paid_out: u32,
+
+      // ledger timestamp at which the pool was started, 0 before
+      pub started_at: u64,
+
+      // shares of the members who haven't defaulted, as of the last save
+      pub active_shares: u32,
+
+      // shares of the members who haven't won or defaulted, as of the last save
+      pub eligible_shares: u32,
+
+      // dues paid in by the members who haven't defaulted, as of the last save
+      pub contributions: u64,
+
+      // shares of the defaulted members by the last iteration they paid for, as of the last save
+      pub defaulted_shares: Map<u32, u32>,
+
+      // dividends shared out so far
+      pub dividend_index: DividendIndex
+}
+
+
+#[contract]
+pub struct HelloContract;
+
+#[contractimpl]
+impl SnbPoolTrait for HelloContract {
+
+    fn get_state(env: Env) -> State {
+        //let mut sampleAddress = String::from("GCCVPYFOHY7ZB7557JKENAX62LUAPLMGIWNZJAFV2MITK6T32V37KEJU");
+        storage::load_state(&env)
+    }
+
+    fn get_pool_state(e: Env) -> PoolState {
+        storage::get_pool_state(&e)
+    }
+
+    fn get_pool_params(e: Env) -> PoolParams {
+        storage::get_pool_params(&e)
+    }
+
+    fn get_current_iteration(e: Env) -> u32 {
+        storage::get_current_iteration(&e)
+    }
+
+    fn get_iteration(e: Env, iteration: u32) -> PoolIterationParams {
+        match storage::get_iteration(&e, iteration) {
+            Some(pool_iteration) => pool_iteration,
+            None => panic!("Iteration has not been started"),
+        }
+    }
+
+    fn restore_hints(e: Env) -> Vec<Val> {
+        storage::restore_hints(&e)
+    }
+
+    fn initialize(e: Env, user: Address, config: PoolConfig, protocol_fee: Option<ProtocolFee>) {
+        //Check if the pool is already initialized
+        let initialized = storage::get_instance(&e, &INTIALIZED).unwrap_or_default();
+        if initialized {
+            panic!("Pool is already initialized");
+        }
+        user.require_auth();
+        let PoolConfig {
+            name,
+            description,
+            no_of_subs,
+            sub_amount,
+            frequency,
+            grace_period_secs,
+            token,
+            collateral_amount,
+            join_deadline,
+            dividend_policy,
+            attestation
+        } = config;
+        if collateral_amount < 0 {
+            panic!("Collateral amount can't be negative");
+        }
+        require_token(&e, &token);
+        if name.is_empty() || name.len() > MAX_NAME_LEN {
+            panic!("Pool name must be between 1 and 64 bytes");
+        }
+        if description.len() > MAX_DESCRIPTION_LEN {
+            panic!("Pool description can't exceed 256 bytes");
+        }
+        if let Some(join_deadline) = join_deadline {
+            if join_deadline <= e.ledger().timestamp() {
+                panic!("Join deadline must be in the future");
+            }
+        }
+        if let Some(attestation) = attestation {
+            access::set_attestation_contract(&e, &attestation);
+        }
+        if let Some(protocol_fee) = protocol_fee {
+            if protocol_fee.fee_bps > treasury::MAX_PROTOCOL_FEE_BPS {
+                panic!("Protocol fee can't exceed 1%");
+            }
+            treasury::set_protocol_fee(&e, &protocol_fee);
+        }
+         //Create a PoolParams instance
+         let pool_params = PoolParams {
+            name,
+            description,
+            no_of_subs,
+            frequency,
+            sub_amount,
+            pool_owner: user.clone(),
+            grace_period_secs,
+            max_missed_iterations: DEFAULT_MAX_MISSED_ITERATIONS,
+            token,
+            collateral_amount,
+            min_reputation: 0,
+            join_deadline,
+            dividend_policy
+        };
+        //Create an empty state for the pool
+        let mut state = State {
+            pool_params,
+            current_iteration: 0,
+            subcriber_map: Map::new(&e),
+            pool_iteration_map: Map::new(&e),
+            shortfall: 0,
+            seized_collateral: 0,
+            penalties_collected: 0,
+            status: PoolStatus::Open,
+            insurance_fund: 0,
+            paid_out: 0,
+            started_at: 0,
+            //the members are counted as they are saved
+            active_shares: 0,
+            eligible_shares: 0,
+            contributions: 0,
+            defaulted_shares: Map::new(&e),
+            dividend_index: DividendIndex { per_share: 0, per_token: 0, released: 0 }
+        };
+        //nothing of the pool is stored yet
+        let loaded = state.clone();
+
+        //Add owner to the subscriber list
+        state.subcriber_map.set(user.clone(), new_subscriber());
+        collateral::deposit_collateral(&e, &state.pool_params, user.clone());
+        badge::mint(&e, user.clone());
+    
+        
+        //save the state in the storage
+        storage::save_state(&e, &loaded, &state);
+        storage::set_instance(&e, &INTIALIZED, &true);
+    }
+
+    fn get_name(e: Env) -> String {
+        storage::get_pool_params(&e).name
+    }
+
+    fn get_description(e: Env) -> String {
+        storage::get_pool_params(&e).description
+    }
+
+    fn get_token(e: Env) -> Address {
+        storage::get_pool_params(&e).token
+    }
+
+    fn join(e: Env, user: Address, guarantor: Option<Address>, referrer: Option<Address>)  {
+        let mut state = storage::load_pool(&e);
+        let loaded = state.clone();
+        add_subscriber(&e, &mut state, user.clone(), guarantor);
+        storage::save_state(&e, &loaded, &state);
+        if let Some(referrer) = referrer {
+            referral::record(&e, user, referrer);
+        }
+    }
+
+    fn set_allowlist_enabled(e: Env, enabled: bool) {
+        let state = storage::load_pool(&e);
+        require_owner_before_start(&e, &state, (Symbol::new(&e, "set_allowlist_enabled"), enabled));
+        changelog::record(&e, &state, symbol_short!("allowlist"), ConfigValue::Bool(access::is_allowlist_enabled(&e)), ConfigValue::Bool(enabled));
+        access::set_allowlist_enabled(&e, enabled);
+    }
+
+    fn is_allowlist_enabled(e: Env) -> bool {
+        access::is_allowlist_enabled(&e)
+    }
+
+    fn add_to_allowlist(e: Env, users: Vec<Address>) {
+        let pool_params = storage::get_pool_params(&e);
+        multisig::require_owner(&e, &pool_params, (Symbol::new(&e, "add_to_allowlist"), users.clone()));
+        for user in users.iter() {
+            access::set_allowlisted(&e, user, true);
+        }
+    }
+
+    fn remove_from_allowlist(e: Env, user: Address) {
+        let pool_params = storage::get_pool_params(&e);
+        multisig::require_owner(&e, &pool_params, (Symbol::new(&e, "remove_from_allowlist"), user.clone()));
+        access::set_allowlisted(&e, user, false);
+    }
+
+    fn is_allowlisted(e: Env, user: Address) -> bool {
+        access::is_allowlisted(&e, user)
+    }
+
+    fn add_to_denylist(e: Env, user: Address) {
+        let pool_params = storage::get_pool_params(&e);
+        multisig::require_owner(&e, &pool_params, (Symbol::new(&e, "add_to_denylist"), user.clone()));
+        if user == pool_params.pool_owner {
+            panic!("Pool owner can't be denied");
+        }
+        access::set_denied(&e, user.clone(), true);
+        event::denylist_updated(&e, user, true);
+    }
+
+    fn remove_from_denylist(e: Env, user: Address) {
+        let pool_params = storage::get_pool_params(&e);
+        multisig::require_owner(&e, &pool_params, (Symbol::new(&e, "remove_from_denylist"), user.clone()));
+        access::set_denied(&e, user.clone(), false);
+        event::denylist_updated(&e, user, false);
+    }
+
+    fn is_denied(e: Env, user: Address) -> bool {
+        access::is_denied(&e, user)
+    }
+
+    fn get_attestation_contract(e: Env) -> Option<Address> {
+        access::get_attestation_contract(&e)
+    }
+
+    fn batch_add_subscribers(e: Env, members: Vec<Address>) {
+        let mut state = storage::load_pool(&e);
+        let loaded = state.clone();
+        require_owner_before_start(&e, &state, (Symbol::new(&e, "batch_add_subscribers"), members.clone()));
+        //every member still signs for their own seat, all of them in the same transaction
+        for member in members.iter() {
+            add_subscriber(&e, &mut state, member, None);
+        }
+        storage::save_state(&e, &loaded, &state);
+    }
+
+    fn leave(e: Env, user: Address) {
+        let mut state = storage::load_entries(&e, [user.clone()], []);
+        let loaded = state.clone();
+        user.require_auth();
+        if !state.subcriber_map.contains_key(user.clone()) {
+            panic!("Subscriber is not in the pool");
+        }
+        if state.current_iteration != 0 {
+            panic!("Pool has already started");
+        }
+        if user == state.pool_params.pool_owner {
+            panic!("Pool owner can't leave the pool");
+        }
+        remove_subscriber(&e, &mut state, user);
+        storage::save_state(&e, &loaded, &state);
+    }
+
+    fn remove_member(e: Env, subscriber: Address) {
+        let mut state = storage::load_entries(&e, [subscriber.clone()], []);
+        let loaded = state.clone();
+        require_owner_before_start(&e, &state, (Symbol::new(&e, "remove_member"), subscriber.clone()));
+        if !state.subcriber_map.contains_key(subscriber.clone()) {
+            panic!("Subscriber is not in the pool");
+        }
+        if subscriber == state.pool_params.pool_owner {
+            panic!("Pool owner can't be removed");
+        }
+        let refunded = remove_subscriber(&e, &mut state, subscriber.clone());
+        storage::save_state(&e, &loaded, &state);
+        event::member_removed(&e, subscriber, refunded);
+    }
+
+    fn set_shares(e: Env, subscriber: Address, shares: u32) {
+        let mut state = storage::load_entries(&e, [subscriber.clone()], []);
+        let loaded = state.clone();
+        subscriber.require_auth();
+        require_not_cancelled(&state);
+        let mut subr: Subscriber = match state.subcriber_map.get(subscriber.clone()) {
+            Some(subr) => subr,
+            None => panic!("Subscriber is not in the pool"),
+        };
+        if state.current_iteration != 0 {
+            panic!("Pool has already started");
+        }
+        if shares == 0 || shares > shares::MAX_SHARES {
+            panic!("Shares must be between 1 and 10");
+        }
+        subr.shares = shares;
+        state.subcriber_map.set(subscriber, subr);
+        storage::save_state(&e, &loaded, &state);
+    }
+
+    fn join_waitlist(e: Env, user: Address) {
+        let state = storage::load_pool(&e);
+        user.require_auth();
+        require_not_cancelled(&state);
+        if state.current_iteration != 0 {
+            panic!("Pool has already started");
+        }
+        let members = storage::get_member_addresses(&e);
+        if members.len() < state.pool_params.no_of_subs {
+            panic!("Pool still has free seats");
+        }
+        let mut waitlist = waitlist::get_waitlist(&e);
+        if members.contains(&user) || waitlist.contains(&user) {
+            panic!("Subscriber is already registered");
+        }
+        require_admissible(&e, &state.pool_params, user.clone());
+        collateral::deposit_collateral(&e, &state.pool_params, user.clone());
+        waitlist.push_back(user);
+        waitlist::set_waitlist(&e, &waitlist);
+    }
+
+    fn leave_waitlist(e: Env, user: Address) {
+        let pool_params = storage::get_pool_params(&e);
+        user.require_auth();
+        let mut waitlist = waitlist::get_waitlist(&e);
+        let index = match waitlist.first_index_of(&user) {
+            Some(index) => index,
+            None => panic!("Address is not on the waitlist"),
+        };
+        waitlist.remove(index);
+        waitlist::set_waitlist(&e, &waitlist);
+        collateral::release_collateral(&e, &pool_params, user);
+    }
+
+    fn get_waitlist(e: Env) -> Vec<Address> {
+        waitlist::get_waitlist(&e)
+    }
+
+    fn cancel_pool(e: Env) {
+        let mut state = storage::load_pool(&e);
+        let loaded = state.clone();
+        require_owner_before_start(&e, &state, (Symbol::new(&e, "cancel_pool"),));
+        cancellation::cancel(&e, &mut state);
+        storage::save_state(&e, &loaded, &state);
+    }
+
+    fn abort_unfilled(e: Env) {
+        let mut state = storage::load_pool(&e);
+        let loaded = state.clone();
+        if state.status != PoolStatus::Open {
+            panic!("Pool has already started");
+        }
+        if !is_past_join_deadline(&e, &state.pool_params) {
+            panic!("Join deadline has not passed");
+        }
+        if storage::get_member_addresses(&e).len() >= state.pool_params.no_of_subs {
+            panic!("Pool is full");
+        }
+        cancellation::cancel(&e, &mut state);
+        storage::save_state(&e, &loaded, &state);
+    }
+
+    fn vote_cancel(e: Env, subscriber: Address) {
+        let mut state = storage::load_members(&e, []);
+        let loaded = state.clone();
+        subscriber.require_auth();
+        require_not_cancelled(&state);
+        if !state.subcriber_map.contains_key(subscriber.clone()) {
+            panic!("Subscriber is not in the pool");
+        }
+        if cancellation::vote(&e, &mut state, subscriber) {
+            storage::save_state(&e, &loaded, &state);
+        }
+    }
+
+    fn claim_refund(e: Env, subscriber: Address) {
+        let mut state = storage::load_entries(&e, [subscriber.clone()], []);
+        let loaded = state.clone();
+        guardian::require_not_paused(&e);
+        subscriber.require_auth();
+        freeze::require_not_frozen(&e, &subscriber);
+        if state.status != PoolStatus::Cancelled {
+            panic!("Pool has not been cancelled");
+        }
+        let mut subr: Subscriber = match state.subcriber_map.get(subscriber.clone()) {
+            Some(subr) => subr,
+            None => panic!("Subscriber is not in the pool"),
+        };
+        //a prize already paid out counts against the refund
+        let received = subr.prize_paid;
+        let dues_refund = subr.total_paid.saturating_sub(received);
+        state.paid_out += dues_refund;
+        let held = collateral::take_collateral(&e, subscriber.clone());
+        let refund = i128::from(dues_refund) + held;
+        if refund == 0 {
+            panic!("Nothing to refund");
+        }
+        subr.total_paid = 0;
+        state.subcriber_map.set(subscriber.clone(), subr);
+        let token_client = token::Client::new(&e, &state.pool_params.token);
+        token_client.transfer(&e.current_contract_address(), &subscriber, &refund);
+        breaker::record_outflow(&e, refund);
+        ledger::pay(&e, LedgerAccount::Pot, i128::from(dues_refund));
+        ledger::pay(&e, LedgerAccount::Collateral, held);
+        storage::save_state(&e, &loaded, &state);
+    }
+
+    fn set_pool_winner(e: Env, iteration: u32, prize_amount: u32, subscriber: Address) {
+        //only the winner and the iteration are touched, the other members are credited when next read
+        let mut state = storage::load_entries(&e, [subscriber.clone()], [iteration]);
+        let loaded = state.clone();
+        multisig::require_owner(&e, &state.pool_params, (Symbol::new(&e, "set_pool_winner"), iteration, prize_amount, subscriber.clone()));
+        require_fully_funded(&e, &state, iteration);
+        assign_winner(&e, &mut state, iteration, prize_amount, subscriber);
+
+        //save the state in the storage
+        storage::save_state(&e, &loaded, &state);
+    }
+
+    fn get_pool_winner(e: Env, iteration: u32) -> Address {
+        //return iteration winner
+        storage::get_iteration(&e, iteration).unwrap().winner
+    }
+
+    fn approve_shortfall(e: Env, iteration: u32, reason: String) {
+        let state = storage::load_entries(&e, [], [iteration]);
+        multisig::require_owner(&e, &state.pool_params, (Symbol::new(&e, "approve_shortfall"), iteration, reason.clone()));
+        require_not_cancelled(&state);
+        let pool_iteration = match state.pool_iteration_map.get(iteration) {
+            Some(pool_iteration) => pool_iteration,
+            None => panic!("Iteration has not been started"),
+        };
+        if reason.is_empty() {
+            panic!("Shortfall reason is required");
+        }
+        let expected = expected_collection(&state, iteration);
+        if pool_iteration.amount_collected >= expected {
+            panic!("Iteration is fully funded");
+        }
+        storage::set_instance(&e, &DataKey::ShortfallApproval(iteration), &reason);
+        event::shortfall_approved(&e, iteration, pool_iteration.amount_collected, expected, reason);
+    }
+
+    fn get_shortfall_approval(e: Env, iteration: u32) -> Option<String> {
+        storage::get_instance(&e, &DataKey::ShortfallApproval(iteration))
+    }
+
+    fn get_iterations(e: Env, from: u32, to: u32) -> Vec<PoolIterationParams> {
+        if to >= from && to - from >= MAX_ITERATIONS_PER_QUERY {
+            panic!("Too many iterations requested");
+        }
+        let mut iterations = Vec::new(&e);
+        for iteration in from..=to {
+            if let Some(pool_iteration) = storage::get_iteration(&e, iteration) {
+                iterations.push_back(pool_iteration);
+            }
+        }
+        iterations
+    }
+
+    fn claim_prize(e: Env, subscriber: Address) {
+        let mut state = load_seat(&e, &subscriber, [subscriber.clone()]);
+        let loaded = state.clone();
+        guardian::require_not_paused(&e);
+        require_not_cancelled(&state);
+        let mut subr: Subscriber = match state.subcriber_map.get(subscriber.clone()) {
+            Some(subr) => subr,
+            None => panic!("Subscriber is not in the pool"),
+        };
+        //a prize the winner sold is paid to its buyer
+        let holder = assignment::prize_holder(&state, subscriber.clone(), &subr);
+        holder.require_auth();
+        if subr.winner_at_iter == 0 {
+            panic!("Subscriber has not won yet");
+        }
+        if subr.prize_claimed {
+            panic!("Prize has already been claimed");
+        }
+        dispute::require_undisputed(&e, subr.winner_at_iter);
+        freeze::require_not_frozen(&e, &subscriber);
+        //vested prizes are paid as their tranches unlock
+        let amount = vesting::unlocked_prize(&e, &state, &subr) - subr.prize_paid;
+        if amount == 0 {
+            panic!("Next prize tranche has not unlocked yet");
+        }
+        subr.prize_paid += amount;
+        subr.prize_claimed = subr.prize_paid == subr.prize_money;
+        state.paid_out += amount;
+        let token_client = token::Client::new(&e, &state.pool_params.token);
+        token_client.transfer(&e.current_contract_address(), &holder, &i128::from(amount));
+        breaker::record_outflow(&e, i128::from(amount));
+        ledger::pay(&e, LedgerAccount::Pot, i128::from(amount));
+        let note = if privacy::is_privacy_mode(&e) {
+            privacy::take_winner_note(&e, subscriber.clone())
+        } else {
+            None
+        };
+        event::prize_claimed(&e, subscriber.clone(), subr.winner_at_iter, amount, note);
+        state.subcriber_map.set(subscriber, subr);
+        storage::save_state(&e, &loaded, &state);
+    }
+
+    fn offer_prize(e: Env, winner: Address, buyer: Address, price: u32) {
+        let state = load_seat(&e, &winner, [winner.clone(), buyer.clone()]);
+        winner.require_auth();
+        guardian::require_not_paused(&e);
+        require_not_cancelled(&state);
+        assignment::offer(&e, &state, winner, buyer, price);
+    }
+
+    fn cancel_prize_offer(e: Env, winner: Address) {
+        winner.require_auth();
+        if assignment::get_offer(&e, winner.clone()).is_none() {
+            panic!("No prize offer to cancel");
+        }
+        assignment::remove_offer(&e, winner);
+    }
+
+    fn get_prize_offer(e: Env, winner: Address) -> Option<PrizeOffer> {
+        assignment::get_offer(&e, winner)
+    }
+
+    fn accept_prize_offer(e: Env, buyer: Address, winner: Address) {
+        let mut state = load_seat(&e, &winner, [winner.clone(), buyer.clone()]);
+        let loaded = state.clone();
+        buyer.require_auth();
+        guardian::require_not_paused(&e);
+        require_not_cancelled(&state);
+        assignment::accept(&e, &mut state, winner, buyer);
+        storage::save_state(&e, &loaded, &state);
+    }
+
+    fn set_prize_vesting(e: Env, tranches: u32) {
+        let state = storage::load_pool(&e);
+        require_owner_before_start(&e, &state, (Symbol::new(&e, "set_prize_vesting"), tranches));
+        if tranches == 0 || tranches > vesting::MAX_VESTING_TRANCHES {
+            panic!("Prizes vest in 1 to 12 tranches");
+        }
+        changelog::record(&e, &state, symbol_short!("vesting"), ConfigValue::U32(vesting::get_vesting_tranches(&e)), ConfigValue::U32(tranches));
+        vesting::set_vesting_tranches(&e, tranches);
+    }
+
+    fn get_prize_vesting(e: Env) -> u32 {
+        vesting::get_vesting_tranches(&e)
+    }
+
+    fn set_prize_loan(e: Env, interest_bps: Option<u32>) {
+        let state = storage::load_pool(&e);
+        require_owner_before_start(&e, &state, (Symbol::new(&e, "set_prize_loan"), interest_bps));
+        if interest_bps.is_some_and(|interest_bps| interest_bps > loan::MAX_LOAN_INTEREST_BPS) {
+            panic!("Loan interest can't exceed 20%");
+        }
+        let old = match loan::get_loan_interest(&e) {
+            Some(old) => ConfigValue::U32(old),
+            None => ConfigValue::Unset,
+        };
+        let new = match interest_bps {
+            Some(new) => ConfigValue::U32(new),
+            None => ConfigValue::Unset,
+        };
+        changelog::record(&e, &state, symbol_short!("loan"), old, new);
+        loan::set_loan_interest(&e, interest_bps);
+    }
+
+    fn get_prize_loan_interest(e: Env) -> Option<u32> {
+        loan::get_loan_interest(&e)
+    }
+
+    fn get_prize_loan(e: Env, subscriber: Address) -> Option<PrizeLoan> {
+        let subr: Subscriber = match storage::get_member(&e, subscriber) {
+            Some(subr) => subr,
+            None => panic!("Subscriber is not in the pool"),
+        };
+        if subr.loan_principal == 0 {
+            return None;
+        }
+        let installment = shares::scaled(&subr, storage::get_iteration(&e, subr.winner_at_iter).unwrap().due_amount);
+        Some(loan::loan_of(&e, &subr, installment))
+    }
+
+    fn set_early_discount(e: Env, discount: Option<EarlyDiscount>) {
+        let state = storage::load_pool(&e);
+        require_owner_before_start(&e, &state, (Symbol::new(&e, "set_early_discount"), discount.clone()));
+        if let Some(discount) = discount.clone() {
+            if discount.discount_bps > discount::MAX_EARLY_DISCOUNT_BPS {
+                panic!("Early discount can't exceed 5%");
+            }
+            if discount.window_bps > PERCENTAGE_MULTIPLIER {
+                panic!("Early window can't exceed the iteration window");
+            }
+        }
+        let old = match discount::get_early_discount(&e) {
+            Some(old) => ConfigValue::Discount(old),
+            None => ConfigValue::Unset,
+        };
+        let new = match discount.clone() {
+            Some(new) => ConfigValue::Discount(new),
+            None => ConfigValue::Unset,
+        };
+        changelog::record(&e, &state, symbol_short!("discount"), old, new);
+        discount::set_early_discount(&e, discount);
+    }
+
+    fn get_early_discount(e: Env) -> Option<EarlyDiscount> {
+        discount::get_early_discount(&e)
+    }
+
+    fn get_carryover(e: Env) -> u32 {
+        dividend::get_carryover(&e)
+    }
+
+    fn claim_dividends(e: Env, subscriber: Address) {
+        let mut state = storage::load_entries(&e, [subscriber.clone()], []);
+        let loaded = state.clone();
+        guardian::require_not_paused(&e);
+        subscriber.require_auth();
+        freeze::require_not_frozen(&e, &subscriber);
+        let mut subr: Subscriber = match state.subcriber_map.get(subscriber.clone()) {
+            Some(subr) => subr,
+            None => panic!("Subscriber is not in the pool"),
+        };
+        if subr.dividend_balance == 0 {
+            panic!("No dividends to claim");
+        }
+        let amount = subr.dividend_balance;
+        subr.dividend_balance = 0;
+        state.paid_out += amount;
+        state.subcriber_map.set(subscriber.clone(), subr);
+        let token_client = token::Client::new(&e, &state.pool_params.token);
+        token_client.transfer(&e.current_contract_address(), &subscriber, &i128::from(amount));
+        breaker::record_outflow(&e, i128::from(amount));
+        ledger::pay(&e, LedgerAccount::Dividends, i128::from(amount));
+        storage::save_state(&e, &loaded, &state);
+    }
+
+    fn allocate_dividend(e: Env, iteration: u32, subscriber: Address, amount: u32) {
+        let mut state = storage::load_entries(&e, [subscriber.clone()], [iteration]);
+        let loaded = state.clone();
+        multisig::require_owner(&e, &state.pool_params, (Symbol::new(&e, "allocate_dividend"), iteration, subscriber.clone(), amount));
+        require_not_cancelled(&state);
+        if state.pool_params.dividend_policy != DividendPolicy::OwnerDiscretion {
+            panic!("Dividends are shared by the pool policy");
+        }
+        let mut pool_iteration = match state.pool_iteration_map.get(iteration) {
+            Some(pool_iteration) => pool_iteration,
+            None => panic!("Iteration has not been started"),
+        };
+        if pool_iteration.dividend_distributed + amount > pool_iteration.dividend_amount {
+            panic!("Amount exceeds the dividend left to allocate");
+        }
+        dividend::credit(&mut state, subscriber, amount);
+        ledger::post(&e, LedgerAccount::Pot, LedgerAccount::Dividends, i128::from(amount));
+        pool_iteration.dividend_distributed += amount;
+        state.pool_iteration_map.set(iteration, pool_iteration);
+        storage::save_state(&e, &loaded, &state);
+    }
+
+    fn set_privacy_mode(e: Env, enabled: bool) {
+        let state = storage::load_pool(&e);
+        require_owner_before_start(&e, &state, (Symbol::new(&e, "set_privacy_mode"), enabled));
+        changelog::record(&e, &state, symbol_short!("privacy"), ConfigValue::Bool(privacy::is_privacy_mode(&e)), ConfigValue::Bool(enabled));
+        privacy::set_privacy_mode(&e, enabled);
+    }
+
+    fn set_profile(e: Env, subscriber: Address, display_name: String, contact_hash: Option<BytesN<32>>) {
+        subscriber.require_auth();
+        if storage::get_member(&e, subscriber.clone()).is_none() {
+            panic!("Subscriber is not in the pool");
+        }
+        profile::set_profile(&e, subscriber, display_name, contact_hash);
+    }
+
+    fn get_profile(e: Env, subscriber: Address) -> Option<Profile> {
+        profile::get_profile(&e, subscriber)
+    }
+
+    fn clear_profile(e: Env, subscriber: Address) {
+        let pool_params = storage::get_pool_params(&e);
+        multisig::require_owner(&e, &pool_params, (Symbol::new(&e, "clear_profile"), subscriber.clone()));
+        profile::clear_profile(&e, subscriber);
+    }
+
+    fn emit_reminders(e: Env) -> Vec<Address> {
+        let state = storage::load_members(&e, [storage::get_current_iteration(&e)]);
+        reminder::emit(&e, &state)
+    }
+
+    fn set_winner_note(e: Env, subscriber: Address, note: Bytes) {
+        subscriber.require_auth();
+        if !privacy::is_privacy_mode(&e) {
+            panic!("Pool is not in privacy mode");
+        }
+        let subr: Subscriber = match storage::get_member(&e, subscriber.clone()) {
+            Some(subr) => subr,
+            None => panic!("Subscriber is not in the pool"),
+        };
+        if subr.winner_at_iter != 0 {
+            panic!("Subscriber is already a winner");
+        }
+        privacy::set_winner_note(&e, subscriber, &note);
+    }
+
+    fn get_subscriber_details(e: Env, subscriber_address: Address) -> Subscriber {
+        //get subscriber details by address
+        storage::get_member(&e, subscriber_address).unwrap()
+    }
+
+    fn get_subscribers(e: Env, offset: u32, limit: u32) -> Vec<(Address, Subscriber)> {
+        if limit > MAX_SUBSCRIBERS_PER_QUERY {
+            panic!("Too many subscribers requested");
+        }
+        //only the entries of the members in the page are read
+        let mut subscribers = Vec::new(&e);
+        for address in storage::get_member_addresses(&e).iter().skip(offset as usize).take(limit as usize) {
+            subscribers.push_back((address.clone(), storage::get_member(&e, address).unwrap()));
+        }
+        subscribers
+    }
+
+    fn set_min_subscribers(e: Env, min_subscribers: u32) {
+        let state = storage::load_pool(&e);
+        require_owner_before_start(&e, &state, (Symbol::new(&e, "set_min_subscribers"), min_subscribers));
+        if min_subscribers == 0 || min_subscribers > state.pool_params.no_of_subs {
+            panic!("Minimum must be between 1 and the number of subscribers");
+        }
+        changelog::record(&e, &state, symbol_short!("min_subs"), ConfigValue::U32(get_min_subscribers(&e, &state.pool_params)), ConfigValue::U32(min_subscribers));
+        storage::set_instance(&e, &DataKey::MinSubscribers, &min_subscribers);
+    }
+
+    fn get_min_subscribers(e: Env) -> u32 {
+        get_min_subscribers(&e, &storage::get_pool_params(&e))
+    }
+
+    fn start_pool(e: Env) {
+        let mut state = storage::load_members(&e, []);
+        let loaded = state.clone();
+        require_owner_before_start(&e, &state, (Symbol::new(&e, "start_pool"),));
+        if state.subcriber_map.len() < get_min_subscribers(&e, &state.pool_params) {
+            panic!("Not enough subscribers to start the pool");
+        }
+        if fair_launch::get_fair_launch(&e).is_some() {
+            panic!("Pool is in fair-launch mode");
+        }
+        state.status = PoolStatus::Active;
+        state.started_at = e.ledger().timestamp();
+        //terms still up for a vote can't change anymore
+        proposal::clear_proposal(&e);
+        //the owner holds the winner slot until a winner is picked
+        let owner = state.pool_params.pool_owner.clone();
+        open_iteration(&e, &mut state, 1, owner, e.ledger().timestamp());
+        storage::save_state(&e, &loaded, &state);
+    }
+
+    fn start_new_iteration(e: Env, iteration: u32) {
+        let mut state = storage::load_members(&e, [storage::get_current_iteration(&e)]);
+        let loaded = state.clone();
+        multisig::require_owner(&e, &state.pool_params, (Symbol::new(&e, "start_new_iteration"), iteration));
+        require_not_cancelled(&state);
+        if state.status != PoolStatus::Active {
+            panic!("Pool has not been started");
+        }
+        //iterations run one after the other, past ones are never rewritten
+        if iteration != state.current_iteration + 1 || state.pool_iteration_map.contains_key(iteration) {
+            panic!("Iteration must follow the current iteration");
+        }
+        if iteration > state.pool_params.no_of_subs {
+            panic!("Pool has no iterations left");
+        }
+        //members have until the deadline and the grace period to pay, unless the round already has its winner
+        let previous = state.current_iteration;
+        let has_winner = state.subcriber_map.values().iter().any(|subr| subr.winner_at_iter == previous);
+        if !has_winner && is_on_time(&e, &state.pool_params, &state.pool_iteration_map.get(previous).unwrap()) {
+            panic!("Iteration is still open for payments");
+        }
+        //the round closes the way picking a winner closes it, the next one keeps to the schedule
+        roll_over(&e, &mut state, previous);
+        storage::save_state(&e, &loaded, &state);
+    }
+
+    fn pay_due(e: Env, subscriber: Address, iteration: u32, amount: Option<u32>, payer: Option<Address>) {
+        let mut state = storage::load_entries(&e, [subscriber.clone()], [iteration]);
+        let loaded = state.clone();
+        let payer = payer.unwrap_or(subscriber.clone());
+        if payer != subscriber {
+            let subr: Subscriber = match state.subcriber_map.get(subscriber.clone()) {
+                Some(subr) => subr,
+                None => panic!("Subscriber is not in the pool"),
+            };
+            if subr.payer != Some(payer.clone()) {
+                panic!("Address is not the payer of the subscriber");
+            }
+        }
+        payer.require_auth();
+        apply_payment(&e, &mut state, subscriber, payer, iteration, amount, false);
+
+        //save the state in the storage
+        storage::save_state(&e, &loaded, &state);
+    }
+
+    fn set_payer(e: Env, subscriber: Address, payer: Option<Address>) {
+        let mut state = storage::load_entries(&e, [subscriber.clone()], []);
+        let loaded = state.clone();
+        subscriber.require_auth();
+        let mut subr: Subscriber = match state.subcriber_map.get(subscriber.clone()) {
+            Some(subr) => subr,
+            None => panic!("Subscriber is not in the pool"),
+        };
+        if payer == Some(subscriber.clone()) {
+            panic!("Subscriber can't be their own payer");
+        }
+        subr.payer = payer;
+        state.subcriber_map.set(subscriber, subr);
+
+        storage::save_state(&e, &loaded, &state);
+    }
+
+    fn collect_due(e: Env, subscriber: Address) {
+        let mut state = storage::load_entries(&e, [subscriber.clone()], [storage::get_current_iteration(&e)]);
+        let loaded = state.clone();
+        if state.status != PoolStatus::Active {
+            panic!("Pool is not active");
+        }
+        //keepers collect for members who approved the pool to spend their dues, no signature needed
+        let iteration = state.current_iteration;
+        apply_payment(&e, &mut state, subscriber.clone(), subscriber, iteration, None, true);
+
+        //save the state in the storage
+        storage::save_state(&e, &loaded, &state);
+    }
+
+    fn collect_all_due(e: Env, subscribers: Vec<Address>) -> u32 {
+        let mut state = storage::load_entries(&e, subscribers.clone(), [storage::get_current_iteration(&e)]);
+        let loaded = state.clone();
+        if state.status != PoolStatus::Active {
+            panic!("Pool is not active");
+        }
+        if subscribers.len() > MAX_COLLECTIONS_PER_CALL {
+            panic!("Too many subscribers in one call");
+        }
+        let iteration = state.current_iteration;
+        let token_client = token::Client::new(&e, &state.pool_params.token);
+        let mut collected = 0;
+        for subscriber in subscribers.iter() {
+            //a failed transfer would revert every collection, so members are checked before pulling anything
+            let subr: Subscriber = match state.subcriber_map.get(subscriber.clone()) {
+                Some(subr) => subr,
+                None => {
+                    event::collection_failed(&e, subscriber, iteration, symbol_short!("member"));
+                    continue;
+                }
+            };
+            if subr.last_paid_iter >= iteration {
+                event::collection_failed(&e, subscriber, iteration, symbol_short!("paid"));
+                continue;
+            }
+            let available = subr.credit_balance + sponsorship::get_balance(&e, subscriber.clone());
+            let owed = charge_due(&e, &state, subscriber.clone(), &subr, iteration).saturating_sub(available);
+            if token_client.allowance(&subscriber, &e.current_contract_address()) < i128::from(owed) {
+                event::collection_failed(&e, subscriber, iteration, symbol_short!("allowance"));
+                continue;
+            }
+            if token_client.balance(&subscriber) < i128::from(owed) {
+                event::collection_failed(&e, subscriber, iteration, symbol_short!("balance"));
+                continue;
+            }
+            apply_payment(&e, &mut state, subscriber.clone(), subscriber.clone(), iteration, None, true);
+            event::due_collected(&e, subscriber, iteration, owed);
+            collected += 1;
+        }
+
+        //save the state in the storage
+        storage::save_state(&e, &loaded, &state);
+        collected
+    }
+
+    fn get_credit_balance(e: Env, subscriber: Address) -> u32 {
+        match storage::get_member(&e, subscriber) {
+            Some(subr) => subr.credit_balance,
+            None => panic!("Subscriber is not in the pool"),
+        }
+    }
+
+    fn withdraw_credit(e: Env, subscriber: Address) {
+        let mut state = storage::load_entries(&e, [subscriber.clone()], []);
+        let loaded = state.clone();
+        guardian::require_not_paused(&e);
+        subscriber.require_auth();
+        freeze::require_not_frozen(&e, &subscriber);
+        if !is_pool_completed(&state) && state.status != PoolStatus::Cancelled {
+            panic!("Pool has not completed yet");
+        }
+        let mut subr: Subscriber = match state.subcriber_map.get(subscriber.clone()) {
+            Some(subr) => subr,
+            None => panic!("Subscriber is not in the pool"),
+        };
+        let credit = subr.credit_balance;
+        if credit == 0 {
+            panic!("Subscriber has no credit");
+        }
+        subr.credit_balance = 0;
+        state.subcriber_map.set(subscriber.clone(), subr);
+        let token_client = token::Client::new(&e, &state.pool_params.token);
+        token_client.transfer(&e.current_contract_address(), &subscriber, &i128::from(credit));
+        breaker::record_outflow(&e, i128::from(credit));
+        ledger::pay(&e, LedgerAccount::Members, i128::from(credit));
+
+        //save the state in the storage
+        storage::save_state(&e, &loaded, &state);
+    }
+
+    fn get_pool_schedule(e: Env) -> Vec<ScheduleEntry> {
+        let state = storage::load_pool(&e);
+        let period = frequency_to_secs(&state.pool_params.frequency);
+        let now = e.ledger().timestamp();
+        let latest = state.current_iteration;
+        let mut schedule = Vec::new(&e);
+        //before the pool starts the first iteration is projected to open now
+        let mut open_ts = now;
+        for iteration in 1..=state.pool_params.no_of_subs {
+            //only the iterations already started have an entry to read
+            let pool_iteration = if iteration <= latest { storage::get_iteration(&e, iteration) } else { None };
+            let status = match pool_iteration {
+                Some(pool_iteration) => {
+                    open_ts = pool_iteration.start_time;
+                    if iteration < latest || !is_on_time(&e, &state.pool_params, &pool_iteration) {
+                        IterationStatus::Closed
+                    } else {
+                        IterationStatus::Open
+                    }
+                }
+                None => IterationStatus::Upcoming,
+            };
+            schedule.push_back(ScheduleEntry {
+                iteration,
+                open_ts,
+                deadline_ts: open_ts + period + state.pool_params.grace_period_secs,
+                status,
+            });
+            open_ts += period;
+        }
+        schedule
+    }
+
+    fn get_due_amount(e: Env, subscriber: Address) -> i128 {
+        let iteration = storage::get_current_iteration(&e);
+        let state = storage::load_entries(&e, [subscriber.clone()], [iteration]);
+        let subr: Subscriber = match state.subcriber_map.get(subscriber.clone()) {
+            Some(subr) => subr,
+            None => panic!("Subscriber is not in the pool"),
+        };
+        let arrears = i128::from(subr.prev_due_amount) + i128::from(subr.arrears_interest);
+        if iteration == 0 {
+            return arrears;
+        }
+        if state.status == PoolStatus::Cancelled || subr.last_paid_iter >= iteration {
+            return arrears;
+        }
+        let charge = charge_due(&e, &state, subscriber.clone(), &subr, iteration);
+        let available = subr.credit_balance + sponsorship::get_balance(&e, subscriber);
+        arrears + i128::from(charge.saturating_sub(available))
+    }
+
+    fn set_arrears_interest(e: Env, interest_bps: u32) {
+        let state = storage::load_pool(&e);
+        require_owner_before_start(&e, &state, (Symbol::new(&e, "set_arrears_interest"), interest_bps));
+        if interest_bps > arrears::MAX_ARREARS_INTEREST_BPS {
+            panic!("Arrears interest can't exceed 5%");
+        }
+        changelog::record(&e, &state, symbol_short!("arrears"), ConfigValue::U32(arrears::get_rate(&e)), ConfigValue::U32(interest_bps));
+        arrears::set_rate(&e, interest_bps);
+    }
+
+    fn get_arrears_interest(e: Env) -> u32 {
+        arrears::get_rate(&e)
+    }
+
+    fn get_arrears(e: Env, subscriber: Address) -> Arrears {
+        let subr: Subscriber = match storage::get_member(&e, subscriber) {
+            Some(subr) => subr,
+            None => panic!("Subscriber is not in the pool"),
+        };
+        Arrears { principal: subr.prev_due_amount, interest: subr.arrears_interest }
+    }
+
+    fn pay_arrears(e: Env, subscriber: Address, amount: Option<u32>) {
+        let mut state = storage::load_entries(&e, [subscriber.clone()], []);
+        let loaded = state.clone();
+        subscriber.require_auth();
+        require_not_cancelled(&state);
+        let mut subr: Subscriber = match state.subcriber_map.get(subscriber.clone()) {
+            Some(subr) => subr,
+            None => panic!("Subscriber is not in the pool"),
+        };
+        let owed = subr.prev_due_amount + subr.arrears_interest;
+        let amount = amount.unwrap_or(owed).min(owed);
+        if amount == 0 {
+            panic!("No arrears to pay");
+        }
+        repay_arrears(&e, &mut state, subscriber.clone(), &mut subr, amount);
+        state.subcriber_map.set(subscriber, subr);
+        storage::save_state(&e, &loaded, &state);
+    }
+
+    fn get_pool_stats(e: Env) -> PoolStats {
+        let state = storage::load_state(&e);
+        stats::pool_stats(&e, &state)
+    }
+
+    fn reconcile(e: Env) -> ReconciliationReport {
+        let state = storage::load_state(&e);
+        reconcile::report(&e, &state)
+    }
+
+    fn get_fee_balances(e: Env) -> FeeBalances {
+        let state = storage::load_state(&e);
+        reconcile::fee_balances(&e, &state)
+    }
+
+    fn trial_balance(e: Env) -> TrialBalance {
+        ledger::trial_balance(&e)
+    }
+
+    fn check_invariants(e: Env) -> Vec<Symbol> {
+        let state = storage::load_state(&e);
+        invariant::violations(&e, &state)
+    }
+
+    fn preview_join(e: Env, user: Address) -> JoinPreview {
+        let state = storage::load_pool(&e);
+        let members = storage::get_member_addresses(&e);
+        require_open_seat(&e, &state, &members, user.clone());
+        require_admissible(&e, &state.pool_params, user);
+        preview::join(&e, &state.pool_params, members.len())
+    }
+
+    fn preview_pay(e: Env, user: Address) -> PaymentPreview {
+        let state = storage::load_entries(&e, [user.clone()], [storage::get_current_iteration(&e)]);
+        require_not_cancelled(&state);
+        let subr = match state.subcriber_map.get(user.clone()) {
+            Some(subr) => subr,
+            None => panic!("Subscriber is not in the pool"),
+        };
+        if state.current_iteration == 0 {
+            panic!("Iteration has not been started");
+        }
+        if subr.last_paid_iter >= state.current_iteration {
+            panic!("Subscriber has already paid for this iteration");
+        }
+        preview::payment(&e, &state, user, &subr, state.current_iteration)
+    }
+
+    fn preview_winner_payout(e: Env, iteration: u32) -> PayoutPreview {
+        let state = storage::load_entries(&e, [], [iteration]);
+        preview::payout(&e, &state, iteration)
+    }
+
+    fn set_reserve_rate(e: Env, rate_bps: u32) {
+        let state = storage::load_pool(&e);
+        require_owner_before_start(&e, &state, (Symbol::new(&e, "set_reserve_rate"), rate_bps));
+        if rate_bps > reserve::MAX_RESERVE_BPS {
+            panic!("Reserve rate exceeds the maximum");
+        }
+        changelog::record(&e, &state, symbol_short!("reserve"), ConfigValue::U32(reserve::get_rate(&e)), ConfigValue::U32(rate_bps));
+        reserve::set_rate(&e, rate_bps);
+    }
+
+    fn get_reserve_rate(e: Env) -> u32 {
+        reserve::get_rate(&e)
+    }
+
+    fn get_reserve_balance(e: Env) -> u32 {
+        reserve::get_balance(&e)
+    }
+
+    fn set_referral_reward(e: Env, reward: u32) {
+        let state = storage::load_pool(&e);
+        require_owner_before_start(&e, &state, (Symbol::new(&e, "set_referral_reward"), reward));
+        changelog::record(&e, &state, symbol_short!("referral"), ConfigValue::U32(referral::get_reward(&e)), ConfigValue::U32(reward));
+        referral::set_reward(&e, reward);
+    }
+
+    fn get_referral_reward(e: Env) -> u32 {
+        referral::get_reward(&e)
+    }
+
+    fn get_referral(e: Env, referred: Address) -> Option<Referral> {
+        referral::get_referral(&e, referred)
+    }
+
+    fn claim_referral_reward(e: Env, referred: Address) {
+        let state = storage::load_entries(&e, [referred.clone()], []);
+        guardian::require_not_paused(&e);
+        let mut record = match referral::get_referral(&e, referred.clone()) {
+            Some(record) => record,
+            None => panic!("Subscriber was not referred"),
+        };
+        record.referrer.require_auth();
+        if record.paid || record.reward == 0 {
+            panic!("No referral reward to claim");
+        }
+        if !is_pool_completed(&state) {
+            panic!("Pool has not completed yet");
+        }
+        //the member has to see the pool through without defaulting
+        match state.subcriber_map.get(referred.clone()) {
+            Some(subr) if !subr.defaulted => (),
+            _ => panic!("Referred subscriber did not complete the pool"),
+        }
+        commission::spend(&e, record.reward);
+        record.paid = true;
+        referral::set_referral(&e, referred, &record);
+        let token_client = token::Client::new(&e, &state.pool_params.token);
+        token_client.transfer(&e.current_contract_address(), &record.referrer, &i128::from(record.reward));
+        breaker::record_outflow(&e, i128::from(record.reward));
+        ledger::pay(&e, LedgerAccount::Fees, i128::from(record.reward));
+    }
+
+    fn create_successor(e: Env, factory: Address) -> Address {
+        let state = storage::load_pool(&e);
+        multisig::require_owner(&e, &state.pool_params, (Symbol::new(&e, "create_successor"), factory.clone()));
+        if !is_pool_completed(&state) {
+            panic!("Pool has not completed yet");
+        }
+        successor::create(&e, &state, factory)
+    }
+
+    fn get_successor(e: Env) -> Option<Address> {
+        successor::get_successor(&e)
+    }
+
+    fn get_predecessor(e: Env) -> Option<Address> {
+        successor::get_predecessor(&e)
+    }
+
+    fn opt_into_successor(e: Env, member: Address) {
+        let state = storage::load_entries(&e, [member.clone()], []);
+        member.require_auth();
+        guardian::require_not_paused(&e);
+        match state.subcriber_map.get(member.clone()) {
+            Some(subr) if !subr.defaulted => (),
+            _ => panic!("Only members in good standing can opt in"),
+        }
+        let released = successor::opt_in(&e, &state, member);
+        breaker::record_outflow(&e, released);
+    }
+
+    fn link_predecessor(e: Env, predecessor: Address, reputation_contract: Option<Address>) {
+        let state = storage::load_pool(&e);
+        require_owner_before_start(&e, &state, (Symbol::new(&e, "link_predecessor"), predecessor.clone(), reputation_contract.clone()));
+        predecessor.require_auth();
+        if successor::get_predecessor(&e).is_some() {
+            panic!("Pool already has a predecessor");
+        }
+        //members keep building on the ledger the predecessor used
+        reputation::set_reputation_contract(&e, reputation_contract);
+        successor::set_predecessor(&e, &predecessor);
+    }
+
+    fn carry_over_reputation(e: Env, member: Address, reputation: u32) {
+        let predecessor = match successor::get_predecessor(&e) {
+            Some(predecessor) => predecessor,
+            None => panic!("Pool has no predecessor"),
+        };
+        predecessor.require_auth();
+        if storage::get_current_iteration(&e) != 0 {
+            panic!("Pool has already started");
+        }
+        reputation::increase(&e, member, reputation);
+    }
+
+    fn estimate_resources(e: Env) -> ResourceEstimate {
+        let state = storage::load_state(&e);
+        let owner = state.pool_params.pool_owner.clone();
+        let no_of_subs = state.pool_params.no_of_subs;
+        let sub_amount = state.pool_params.sub_amount;
+        //the size of an amount doesn't depend on its value, a pot a u32 can't hold is sized as the largest one
+        let pot = sub_amount.saturating_mul(no_of_subs);
+
+        //dry-run closing the current iteration, the first one before the pool starts, for a member still to win.
+        //Only the entries set_pool_winner loads are read, and nothing is saved
+        let iteration = state.current_iteration.max(1);
+        let winner = state
+            .subcriber_map
+            .iter()
+            .find(|(_, subr)| subr.winner_at_iter == 0 && !subr.defaulted)
+            .map(|(address, _)| address);
+        let loaded = storage::load_entries(&e, winner.clone(), [iteration]);
+        let mut closed = loaded.clone();
+        if let Some(winner) = winner.clone() {
+            let mut subr = closed.subcriber_map.get(winner.clone()).unwrap();
+            subr.winner_at_iter = iteration;
+            subr.prize_money = pot;
+            closed.subcriber_map.set(winner, subr);
+        }
+        let winner = winner.unwrap_or(owner.clone());
+        closed.pool_iteration_map.set(iteration, closed_iteration(&e, iteration, winner, pot, sub_amount));
+
+        //every member paid the whole cycle and won, and every iteration was closed
+        let mut projected_members = Vec::new(&e);
+        for mut subr in state.subcriber_map.values() {
+            subr.winner_at_iter = no_of_subs;
+            subr.prize_money = pot;
+            subr.last_paid_iter = no_of_subs;
+            projected_members.push_back(subr);
+        }
+        let mut projected_iterations = Vec::new(&e);
+        for iteration in 1..=no_of_subs {
+            projected_iterations.push_back(closed_iteration(&e, iteration, owner.clone(), pot, sub_amount));
+        }
+
+        ResourceEstimate {
+            subscribers: state.subcriber_map.len(),
+            iterations: state.pool_iteration_map.len(),
+            pool_entry_bytes: storage::get_pool_state(&e).to_xdr(&e).len(),
+            member_entry_bytes: storage::largest_entry(&e, state.subcriber_map.values()),
+            iteration_entry_bytes: storage::largest_entry(&e, state.pool_iteration_map.values()),
+            projected_member_bytes: storage::largest_entry(&e, projected_members),
+            projected_iteration_bytes: storage::largest_entry(&e, projected_iterations),
+            close_read_entries: storage::read_entries(&loaded),
+            close_write_entries: storage::written_entries(&loaded, &closed)
+        }
+    }
+
+    fn set_max_missed_iterations(e: Env, max_missed_iterations: u32) {
+        let mut state = storage::load_pool(&e);
+        let loaded = state.clone();
+        require_owner_before_start(&e, &state, (Symbol::new(&e, "set_max_missed_iterations"), max_missed_iterations));
+        if max_missed_iterations == 0 {
+            panic!("At least one missed iteration must be tolerated");
+        }
+        changelog::record(&e, &state, symbol_short!("max_miss"), ConfigValue::U32(state.pool_params.max_missed_iterations), ConfigValue::U32(max_missed_iterations));
+        state.pool_params.max_missed_iterations = max_missed_iterations;
+        storage::save_state(&e, &loaded, &state);
+    }
+
+    fn set_min_reputation(e: Env, min_reputation: u32) {
+        let mut state = storage::load_pool(&e);
+        let loaded = state.clone();
+        require_owner_before_start(&e, &state, (Symbol::new(&e, "set_min_reputation"), min_reputation));
+        changelog::record(&e, &state, symbol_short!("min_rep"), ConfigValue::U32(state.pool_params.min_reputation), ConfigValue::U32(min_reputation));
+        state.pool_params.min_reputation = min_reputation;
+        storage::save_state(&e, &loaded, &state);
+    }
+
+    fn mark_defaulted(e: Env, subscriber: Address) {
+        let mut state = storage::load_entries(&e, [subscriber.clone()], []);
+        let loaded = state.clone();
+        multisig::require_owner(&e, &state.pool_params, (Symbol::new(&e, "mark_defaulted"), subscriber.clone()));
+        let mut subr: Subscriber = match state.subcriber_map.get(subscriber.clone()) {
+            Some(subr) => subr,
+            None => panic!("Subscriber is not in the pool"),
+        };
+        if subr.defaulted {
+            panic!("Subscriber has already defaulted");
+        }
+        default_subscriber(&e, &mut state, subscriber.clone(), &mut subr);
+        state.subcriber_map.set(subscriber, subr);
+        storage::save_state(&e, &loaded, &state);
+    }
+
+    fn get_payment_history(e: Env, subscriber: Address, start: u32, limit: u32) -> Vec<PaymentRecord> {
+        history::get_history(&e, subscriber, start, limit)
+    }
+
+    fn get_payer_stats(e: Env, subscriber: Address) -> Vec<PayerStats> {
+        payer::get_payer_stats(&e, subscriber)
+    }
+
+    fn cover_due(e: Env, guarantor: Address, subscriber: Address) {
+        let mut state = storage::load_entries(&e, [subscriber.clone()], [storage::get_current_iteration(&e)]);
+        let loaded = state.clone();
+        guarantor.require_auth();
+        let subr: Subscriber = match state.subcriber_map.get(subscriber.clone()) {
+            Some(subr) => subr,
+            None => panic!("Subscriber is not in the pool"),
+        };
+        if subr.guarantor != Some(guarantor.clone()) {
+            panic!("Address is not the guarantor of the subscriber");
+        }
+        let iteration = state.current_iteration;
+        if iteration == 0 {
+            panic!("Iteration has not been started");
+        }
+        // the guarantor only steps in once the subscriber failed to pay in time
+        let pool_iteration = state.pool_iteration_map.get(iteration).unwrap();
+        if is_on_time(&e, &state.pool_params, &pool_iteration) {
+            panic!("Due is not overdue yet");
+        }
+        apply_payment(&e, &mut state, subscriber, guarantor, iteration, None, false);
+
+        //save the state in the storage
+        storage::save_state(&e, &loaded, &state);
+    }
+
+    fn set_insurance_premium(e: Env, premium: u32) {
+        let state = storage::load_pool(&e);
+        require_owner_before_start(&e, &state, (Symbol::new(&e, "set_insurance_premium"), premium));
+        changelog::record(&e, &state, symbol_short!("premium"), ConfigValue::U32(insurance::get_premium(&e)), ConfigValue::U32(premium));
+        insurance::set_premium(&e, premium);
+    }
+
+    fn buy_seat_insurance(e: Env, subscriber: Address) {
+        let state = storage::load_entries(&e, [subscriber.clone()], []);
+        subscriber.require_auth();
+        require_not_cancelled(&state);
+        if insurance::get_premium(&e) == 0 {
+            panic!("Pool doesn't offer seat insurance");
+        }
+        let subr: Subscriber = match state.subcriber_map.get(subscriber.clone()) {
+            Some(subr) => subr,
+            None => panic!("Subscriber is not in the pool"),
+        };
+        if subr.defaulted {
+            panic!("Subscriber has defaulted");
+        }
+        if insurance::get_seat_insurance(&e, subscriber.clone()).is_some() {
+            panic!("Seat is already insured");
+        }
+        insurance::set_seat_insurance(&e, subscriber, &SeatInsurance { premiums_paid: 0, claims: 0, covered_iteration: 0, repaid: 0 });
+    }
+
+    fn get_seat_insurance(e: Env, subscriber: Address) -> Option<SeatInsurance> {
+        insurance::get_seat_insurance(&e, subscriber)
+    }
+
+    fn get_badge(e: Env, holder: Address) -> Option<u32> {
+        badge::badge_of(&e, holder)
+    }
+
+    fn badge_owner(e: Env, token_id: u32) -> Address {
+        match badge::owner_of(&e, token_id) {
+            Some(holder) => holder,
+            None => panic!("Badge does not exist"),
+        }
+    }
+
+    fn sponsor(e: Env, sponsor: Address, subscriber: Address, amount: u32) {
+        let state = storage::load_entries(&e, [subscriber.clone()], []);
+        sponsor.require_auth();
+        require_not_cancelled(&state);
+        if !state.subcriber_map.contains_key(subscriber.clone()) {
+            panic!("Subscriber is not in the pool");
+        }
+        if amount == 0 {
+            panic!("Amount must be positive");
+        }
+        sponsorship::deposit(&e, &state.pool_params, sponsor, subscriber, amount);
+    }
+
+    fn get_sponsorship(e: Env, subscriber: Address) -> Option<Sponsorship> {
+        sponsorship::get_sponsorship(&e, subscriber)
+    }
+
+    fn refund_sponsorship(e: Env, subscriber: Address) {
+        let state = storage::load_pool(&e);
+        guardian::require_not_paused(&e);
+        if !is_pool_completed(&state) && state.status != PoolStatus::Cancelled {
+            panic!("Pool has not completed yet");
+        }
+        //the tokens only ever go back to the sponsor, so anyone can trigger it
+        let refund = sponsorship::refund(&e, &state.pool_params, subscriber);
+        if refund == 0 {
+            panic!("Nothing left to refund");
+        }
+        breaker::record_outflow(&e, i128::from(refund));
+    }
+
+    fn get_collateral(e: Env, subscriber: Address) -> i128 {
+        collateral::get_collateral(&e, subscriber)
+    }
+
+    fn withdraw_collateral(e: Env, subscriber: Address) {
+        let state = storage::load_pool(&e);
+        subscriber.require_auth();
+        if !is_pool_completed(&state) {
+            panic!("Pool has not completed yet");
+        }
+        if collateral::get_collateral(&e, subscriber.clone()) == 0 {
+            panic!("Subscriber has no collateral");
+        }
+        collateral::release_collateral(&e, &state.pool_params, subscriber);
+    }
+
+    fn seize_collateral(e: Env, subscriber: Address) -> SlashSettlement {
+        let mut state = storage::load_members(&e, [storage::get_current_iteration(&e)]);
+        let loaded = state.clone();
+        multisig::require_owner(&e, &state.pool_params, (Symbol::new(&e, "seize_collateral"), subscriber.clone()));
+        let subr: Subscriber = match state.subcriber_map.get(subscriber.clone()) {
+            Some(subr) => subr,
+            None => panic!("Subscriber is not in the pool"),
+        };
+        if !subr.defaulted {
+            panic!("Subscriber has not defaulted");
+        }
+        let seized = collateral::take_collateral(&e, subscriber.clone());
+        if seized == 0 {
+            panic!("Subscriber has no collateral");
+        }
+        let settlement = slashing::settle(&e, &mut state, subscriber, seized);
+        storage::save_state(&e, &loaded, &state);
+        settlement
+    }
+
+    fn set_winner_strategy(e: Env, strategy: WinnerStrategy) {
+        let state = storage::load_pool(&e);
+        //the owner can switch strategies at any time so a broken one can be replaced
+        multisig::require_owner(&e, &state.pool_params, (Symbol::new(&e, "set_winner_strategy"), strategy.clone()));
+        let old_strategy = strategy::get_winner_strategy(&e);
+        changelog::record(&e, &state, symbol_short!("strategy"), ConfigValue::Strategy(old_strategy), ConfigValue::Strategy(strategy.clone()));
+        strategy::set_winner_strategy(&e, &strategy);
+    }
+
+    fn get_winner_strategy(e: Env) -> WinnerStrategy {
+        strategy::get_winner_strategy(&e)
+    }
+
+    fn draw_winner(e: Env, iteration: u32) -> Address {
+        let mut state = storage::load_members(&e, [iteration]);
+        let loaded = state.clone();
+        multisig::require_owner(&e, &state.pool_params, (Symbol::new(&e, "draw_winner"), iteration));
+        let mut pool_iteration = match state.pool_iteration_map.get(iteration) {
+            Some(pool_iteration) => pool_iteration,
+            None => panic!("Iteration has not been started"),
+        };
+        require_fully_funded(&e, &state, iteration);
+        let candidates = winner_candidates(&e, &state);
+        let winner = strategy::select_winner(&e, &state, iteration, &candidates);
+        reserve::fill(&e, &state, &mut pool_iteration);
+        assign_winner(&e, &mut state, iteration, prize_pot(&e, &pool_iteration), winner.clone());
+
+        //save the state in the storage
+        storage::save_state(&e, &loaded, &state);
+        winner
+    }
+
+    fn force_draw(e: Env, caller: Address, iteration: u32) -> Address {
+        let mut state = storage::load_members(&e, [iteration]);
+        let loaded = state.clone();
+        caller.require_auth();
+        if !state.subcriber_map.contains_key(caller.clone()) {
+            panic!("Subscriber is not in the pool");
+        }
+        let mut pool_iteration = match state.pool_iteration_map.get(iteration) {
+            Some(pool_iteration) => pool_iteration,
+            None => panic!("Iteration has not been started"),
+        };
+        if state.subcriber_map.values().iter().any(|subr| subr.winner_at_iter == iteration) {
+            panic!("Iteration already has a winner");
+        }
+        let deadline = pool_iteration.start_time
+            + frequency_to_secs(&state.pool_params.frequency)
+            + state.pool_params.grace_period_secs;
+        if e.ledger().timestamp() <= deadline + FORCE_DRAW_TIMEOUT_SECS {
+            panic!("Owner can still pick the winner");
+        }
+        //the dues still missing this long after the deadline are not coming, the round closes with what it has
+        let candidates = winner_candidates(&e, &state);
+        let winner = strategy::random_draw(&e, &state, &candidates);
+        reserve::fill(&e, &state, &mut pool_iteration);
+        assign_winner(&e, &mut state, iteration, prize_pot(&e, &pool_iteration), winner.clone());
+
+        storage::save_state(&e, &loaded, &state);
+        winner
+    }
+
+    fn set_commission(e: Env, commission_bps: u32) {
+        let mut state = storage::load_pool(&e);
+        require_owner_before_start(&e, &state, (Symbol::new(&e, "set_commission"), commission_bps));
+        timelock::require_unlocked(&e);
+        apply_param_change(&e, &mut state, ParamChange::Commission(commission_bps));
+    }
+
+    fn get_commission(e: Env) -> u32 {
+        commission::get_commission_bps(&e)
+    }
+
+    fn claim_commission(e: Env) {
+        let pool_params = storage::get_pool_params(&e);
+        guardian::require_not_paused(&e);
+        let owner = pool_params.pool_owner.clone();
+        owner.require_auth();
+        let amount = commission::take_commission_balance(&e);
+        if amount == 0 {
+            panic!("No commission to claim");
+        }
+        let token_client = token::Client::new(&e, &pool_params.token);
+        token_client.transfer(&e.current_contract_address(), &owner, &i128::from(amount));
+        breaker::record_outflow(&e, i128::from(amount));
+        ledger::pay(&e, LedgerAccount::Fees, i128::from(amount));
+    }
+
+    fn get_protocol_fee(e: Env) -> Option<ProtocolFee> {
+        treasury::get_protocol_fee(&e)
+    }
+
+    fn get_treasury_balance(e: Env) -> u32 {
+        treasury::get_treasury_balance(&e)
+    }
+
+    fn withdraw_treasury(e: Env) {
+        let pool_params = storage::get_pool_params(&e);
+        guardian::require_not_paused(&e);
+        let fee = match treasury::get_protocol_fee(&e) {
+            Some(fee) => fee,
+            None => panic!("Pool doesn't pay a protocol fee"),
+        };
+        fee.treasurer.require_auth();
+        let amount = treasury::take_treasury_balance(&e);
+        if amount == 0 {
+            panic!("Nothing to withdraw");
+        }
+        let token_client = token::Client::new(&e, &pool_params.token);
+        token_client.transfer(&e.current_contract_address(), &fee.treasury, &i128::from(amount));
+        breaker::record_outflow(&e, i128::from(amount));
+        ledger::pay(&e, LedgerAccount::Fees, i128::from(amount));
+    }
+
+    fn set_protection_limits(e: Env, late_fee_bps: u32, max_penalty_bps: u32) {
+        let mut state = storage::load_pool(&e);
+        require_owner_before_start(&e, &state, (Symbol::new(&e, "set_protection_limits"), late_fee_bps, max_penalty_bps));
+        timelock::require_unlocked(&e);
+        apply_param_change(&e, &mut state, ParamChange::ProtectionLimits(PenaltyConfig { late_fee_bps, max_penalty_bps }));
+    }
+
+    fn get_protection_limits(e: Env) -> ProtectionLimits {
+        let pool_params = storage::get_pool_params(&e);
+        let config = penalty::get_penalty_config(&e);
+        ProtectionLimits {
+            late_fee_bps: config.late_fee_bps,
+            max_penalty_bps: config.max_penalty_bps,
+            max_penalty_amount: penalty::max_penalty(&pool_params, &config)
+        }
+    }
+
+    fn open_fair_launch(e: Env, window_end: u64) {
+        let state = storage::load_pool(&e);
+        require_owner_before_start(&e, &state, (Symbol::new(&e, "open_fair_launch"), window_end));
+        if fair_launch::get_fair_launch(&e).is_some() {
+            panic!("Fair launch is already open");
+        }
+        if window_end <= e.ledger().timestamp() {
+            panic!("Window must end in the future");
+        }
+        fair_launch::set_fair_launch(&e, &FairLaunch { window_end, registrations: Vec::new(&e) });
+    }
+
+    fn register_for_seat(e: Env, user: Address) {
+        let state = storage::load_entries(&e, [user.clone()], []);
+        user.require_auth();
+        require_not_cancelled(&state);
+        let mut launch = match fair_launch::get_fair_launch(&e) {
+            Some(launch) => launch,
+            None => panic!("No fair launch is open"),
+        };
+        if e.ledger().timestamp() > launch.window_end {
+            panic!("Registration window is closed");
+        }
+        if state.subcriber_map.contains_key(user.clone()) || launch.registrations.contains(&user) {
+            panic!("Subscriber is already registered");
+        }
+        require_admissible(&e, &state.pool_params, user.clone());
+        collateral::deposit_collateral(&e, &state.pool_params, user.clone());
+        launch.registrations.push_back(user);
+        fair_launch::set_fair_launch(&e, &launch);
+    }
+
+    fn close_fair_launch(e: Env) -> Vec<Address> {
+        let mut state = storage::load_members(&e, []);
+        let loaded = state.clone();
+        let seated = fair_launch::close(&e, &mut state);
+        storage::save_state(&e, &loaded, &state);
+        seated
+    }
+
+    fn get_fair_launch(e: Env) -> Option<FairLaunch> {
+        fair_launch::get_fair_launch(&e)
+    }
+
+    fn set_reputation_contract(e: Env, contract: Option<Address>) {
+        let state = storage::load_pool(&e);
+        require_owner_before_start(&e, &state, (Symbol::new(&e, "set_reputation_contract"), contract.clone()));
+        let old_contract = reputation::get_reputation_contract(&e);
+        changelog::record(&e, &state, symbol_short!("rep_ledgr"), old_contract.map_or(ConfigValue::Unset, ConfigValue::Address), contract.clone().map_or(ConfigValue::Unset, ConfigValue::Address));
+        reputation::set_reputation_contract(&e, contract);
+    }
+
+    fn get_reputation_contract(e: Env) -> Option<Address> {
+        reputation::get_reputation_contract(&e)
+    }
+
+    fn set_reputation_decay(e: Env, decay: Option<ReputationDecay>) {
+        let state = storage::load_pool(&e);
+        require_owner_before_start(&e, &state, (Symbol::new(&e, "set_reputation_decay"), decay.clone()));
+        if let Some(decay) = decay.clone() {
+            if decay.period_secs == 0 {
+                panic!("Decay period must be positive");
+            }
+        }
+        let old_decay = reputation::get_reputation_decay(&e);
+        changelog::record(&e, &state, symbol_short!("rep_decay"), old_decay.map_or(ConfigValue::Unset, ConfigValue::Decay), decay.clone().map_or(ConfigValue::Unset, ConfigValue::Decay));
+        reputation::set_reputation_decay(&e, decay);
+    }
+
+    fn get_reputation_decay(e: Env) -> Option<ReputationDecay> {
+        reputation::get_reputation_decay(&e)
+    }
+
+    fn get_effective_reputation(e: Env, subscriber: Address) -> u32 {
+        reputation::get_effective_reputation(&e, subscriber)
+    }
+
+    fn set_accounting_currency(e: Env, currency: Option<AccountingCurrency>) {
+        let state = storage::load_pool(&e);
+        require_owner_before_start(&e, &state, (Symbol::new(&e, "set_accounting_currency"), currency.clone()));
+        if let Some(currency) = currency.clone() {
+            if currency.reference_amount == 0 {
+                panic!("Reference amount must be positive");
+            }
+            if currency.max_price_age_secs == 0 {
+                panic!("Price age limit must be positive");
+            }
+        }
+        let old_currency = oracle::get_accounting_currency(&e);
+        changelog::record(&e, &state, symbol_short!("currency"), old_currency.map_or(ConfigValue::Unset, ConfigValue::Currency), currency.clone().map_or(ConfigValue::Unset, ConfigValue::Currency));
+        oracle::set_accounting_currency(&e, currency);
+    }
+
+    fn get_accounting_currency(e: Env) -> Option<AccountingCurrency> {
+        oracle::get_accounting_currency(&e)
+    }
+
+    fn set_swap_config(e: Env, config: Option<SwapConfig>) {
+        let state = storage::load_pool(&e);
+        require_owner_before_start(&e, &state, (Symbol::new(&e, "set_swap_config"), config.clone()));
+        if let Some(config) = config.clone() {
+            if config.max_slippage_bps > swap::MAX_SLIPPAGE_BPS {
+                panic!("Slippage can't exceed 5%");
+            }
+            if config.tokens.contains(&state.pool_params.token) {
+                panic!("Pool token is always accepted");
+            }
+        }
+        let old_config = swap::get_swap_config(&e);
+        changelog::record(&e, &state, symbol_short!("swap"), old_config.map_or(ConfigValue::Unset, ConfigValue::Swap), config.clone().map_or(ConfigValue::Unset, ConfigValue::Swap));
+        swap::set_swap_config(&e, config);
+    }
+
+    fn get_swap_config(e: Env) -> Option<SwapConfig> {
+        swap::get_swap_config(&e)
+    }
+
+    fn pay_due_with_token(e: Env, subscriber: Address, iteration: u32, token: Address) -> i128 {
+        let mut state = storage::load_entries(&e, [subscriber.clone()], [iteration]);
+        let loaded = state.clone();
+        subscriber.require_auth();
+        let subr: Subscriber = match state.subcriber_map.get(subscriber.clone()) {
+            Some(subr) => subr,
+            None => panic!("Subscriber is not in the pool"),
+        };
+        if !state.pool_iteration_map.contains_key(iteration) {
+            panic!("Iteration has not been started");
+        }
+        if subr.last_paid_iter >= iteration {
+            panic!("Subscriber has already paid for this iteration");
+        }
+        //only what the credit and sponsorship don't cover is bought, then paid like any due
+        let available = subr.credit_balance + sponsorship::get_balance(&e, subscriber.clone());
+        let owed = charge_due(&e, &state, subscriber.clone(), &subr, iteration).saturating_sub(available);
+        let spent = if owed == 0 {
+            0
+        } else if settlement::is_deferred(&e) {
+            //the other token is held and the due is paid out of the credit it buys, converted at payout
+            let taken = settlement::hold(&e, subscriber.clone(), token, owed, &state.pool_params);
+            let mut subr = subr;
+            subr.credit_balance += owed;
+            state.subcriber_map.set(subscriber.clone(), subr);
+            ledger::post(&e, LedgerAccount::Unsettled, LedgerAccount::Members, i128::from(owed));
+            taken
+        } else {
+            swap::convert(&e, &state.pool_params, subscriber.clone(), token, owed)
+        };
+        apply_payment(&e, &mut state, subscriber.clone(), subscriber, iteration, None, false);
+
+        //save the state in the storage
+        storage::save_state(&e, &loaded, &state);
+        spent
+    }
+
+    fn set_deferred_settlement(e: Env, enabled: bool) {
+        let state = storage::load_pool(&e);
+        require_owner_before_start(&e, &state, (Symbol::new(&e, "set_deferred_settlement"), enabled));
+        if enabled && swap::get_swap_config(&e).is_none() {
+            panic!("Pool doesn't accept other tokens");
+        }
+        changelog::record(&e, &state, symbol_short!("deferred"), ConfigValue::Bool(settlement::is_deferred(&e)), ConfigValue::Bool(enabled));
+        settlement::set_deferred(&e, enabled);
+    }
+
+    fn is_deferred_settlement(e: Env) -> bool {
+        settlement::is_deferred(&e)
+    }
+
+    fn get_asset_holdings(e: Env) -> Vec<AssetHolding> {
+        settlement::holdings(&e)
+    }
+
+    fn settle_contributions(e: Env) -> u32 {
+        let pool_params = storage::get_pool_params(&e);
+        guardian::require_not_paused(&e);
+        settlement::settle(&e, &pool_params)
+    }
+
+    fn set_yield_adapter(e: Env, adapter: Option<Address>) {
+        let state = storage::load_pool(&e);
+        require_owner_before_start(&e, &state, (Symbol::new(&e, "set_yield_adapter"), adapter.clone()));
+        let old_adapter = yield_adapter::get_yield_adapter(&e);
+        changelog::record(&e, &state, symbol_short!("yield"), old_adapter.map_or(ConfigValue::Unset, ConfigValue::Address), adapter.clone().map_or(ConfigValue::Unset, ConfigValue::Address));
+        yield_adapter::set_yield_adapter(&e, adapter);
+    }
+
+    fn get_yield_adapter(e: Env) -> Option<Address> {
+        yield_adapter::get_yield_adapter(&e)
+    }
+
+    fn deposit_idle_funds(e: Env) -> u32 {
+        let state = storage::load_members(&e, [storage::get_current_iteration(&e)]);
+        multisig::require_owner(&e, &state.pool_params, (Symbol::new(&e, "deposit_idle_funds"),));
+        require_not_cancelled(&state);
+        guardian::require_not_paused(&e);
+        let iteration = state.current_iteration;
+        let pool_iteration = match state.pool_iteration_map.get(iteration) {
+            Some(pool_iteration) => pool_iteration,
+            None => panic!("Pool has not started"),
+        };
+        if state.subcriber_map.values().iter().any(|subr| subr.winner_at_iter == iteration) {
+            panic!("Iteration already has a winner");
+        }
+        //only the pot is lent out, fees included since they are taken at the payout
+        let deposited = yield_adapter::get_idle_deposit(&e).map_or(0, |deposit| deposit.amount);
+        let amount = (pool_iteration.amount_collected + pool_iteration.carried_in - pool_iteration.discounts)
+            .saturating_sub(deposited);
+        if amount == 0 {
+            panic!("No idle funds to deposit");
+        }
+        yield_adapter::deposit(&e, &state.pool_params, iteration, amount);
+        amount
+    }
+
+    fn get_idle_deposit(e: Env) -> Option<IdleDeposit> {
+        yield_adapter::get_idle_deposit(&e)
+    }
+
+    fn propose_emergency_withdraw(e: Env, to: Address, amount: i128) -> EmergencyWithdrawal {
+        let pool_params = storage::get_pool_params(&e);
+        multisig::require_owner(&e, &pool_params, (Symbol::new(&e, "propose_emergency_withdraw"), to.clone(), amount));
+        emergency::propose(&e, to, amount)
+    }
+
+    fn execute_emergency_withdraw(e: Env) {
+        let pool_params = storage::get_pool_params(&e);
+        multisig::require_owner(&e, &pool_params, (Symbol::new(&e, "execute_emergency_withdraw"),));
+        emergency::execute(&e, &pool_params);
+    }
+
+    fn cancel_emergency_withdraw(e: Env) {
+        let pool_params = storage::get_pool_params(&e);
+        multisig::require_owner(&e, &pool_params, (Symbol::new(&e, "cancel_emergency_withdraw"),));
+        emergency::cancel(&e);
+    }
+
+    fn get_emergency_withdrawal(e: Env) -> Option<EmergencyWithdrawal> {
+        emergency::get_pending(&e)
+    }
+
+    fn set_timelock_delay(e: Env, delay_secs: u64) {
+        let state = storage::load_pool(&e);
+        require_owner_before_start(&e, &state, (Symbol::new(&e, "set_timelock_delay"), delay_secs));
+        if delay_secs > timelock::MAX_TIMELOCK_DELAY_SECS {
+            panic!("Timelock delay can't exceed 30 days");
+        }
+        changelog::record(&e, &state, symbol_short!("timelock"), ConfigValue::U64(timelock::get_delay(&e)), ConfigValue::U64(delay_secs));
+        timelock::set_delay(&e, delay_secs);
+    }
+
+    fn get_timelock_delay(e: Env) -> u64 {
+        timelock::get_delay(&e)
+    }
+
+    fn propose_param_change(e: Env, change: ParamChange) -> u32 {
+        let state = storage::load_pool(&e);
+        multisig::require_owner(&e, &state.pool_params, (Symbol::new(&e, "propose_param_change"), change.clone()));
+        require_not_cancelled(&state);
+        //checked now so members aren't given notice of a change that can't happen, and again when it is carried out
+        validate_param_change(&e, &state, &change);
+        timelock::propose(&e, change)
+    }
+
+    fn execute_param_change(e: Env, id: u32) {
+        let mut state = storage::load_pool(&e);
+        let loaded = state.clone();
+        multisig::require_owner(&e, &state.pool_params, (Symbol::new(&e, "execute_param_change"), id));
+        require_not_cancelled(&state);
+        let change = timelock::take_executable(&e, id);
+        apply_param_change(&e, &mut state, change);
+
+        storage::save_state(&e, &loaded, &state);
+    }
+
+    fn cancel_param_change(e: Env, id: u32) {
+        let pool_params = storage::get_pool_params(&e);
+        multisig::require_owner(&e, &pool_params, (Symbol::new(&e, "cancel_param_change"), id));
+        timelock::cancel(&e, id);
+    }
+
+    fn get_pending_change(e: Env, id: u32) -> Option<PendingChange> {
+        timelock::get_pending(&e, id)
+    }
+
+    fn set_guardians(e: Env, guardians: Option<GuardianSet>) {
+        let state = storage::load_pool(&e);
+        require_owner_before_start(&e, &state, (Symbol::new(&e, "set_guardians"), guardians.clone()));
+        if guardians.is_none() && breaker::get_outflow_limit(&e).is_some() {
+            panic!("Circuit breaker needs guardians to unpause");
+        }
+        if let Some(set) = guardians.clone() {
+            if set.threshold == 0 || set.threshold > set.guardians.len() {
+                panic!("Threshold must be between 1 and the number of guardians");
+            }
+            //guardians are a check on the owner
+            if set.guardians.contains(&state.pool_params.pool_owner) {
+                panic!("Pool owner can't be a guardian");
+            }
+            if let Some(council) = multisig::get_council(&e) {
+                if council.owners.iter().any(|owner| set.guardians.contains(&owner)) {
+                    panic!("Guardian can't be an owner");
+                }
+            }
+        }
+        let old_guardians = guardian::get_guardians(&e);
+        changelog::record(&e, &state, symbol_short!("guardians"), old_guardians.map_or(ConfigValue::Unset, ConfigValue::Guardians), guardians.clone().map_or(ConfigValue::Unset, ConfigValue::Guardians));
+        guardian::set_guardians(&e, guardians);
+    }
+
+    fn get_guardians(e: Env) -> Option<GuardianSet> {
+        guardian::get_guardians(&e)
+    }
+
+    fn guardian_approve(e: Env, guardian: Address, action: GuardianAction) -> bool {
+        guardian.require_auth();
+        guardian::approve(&e, guardian, action)
+    }
+
+    fn get_guardian_approvals(e: Env, action: GuardianAction) -> Vec<Address> {
+        guardian::get_approvals(&e, &action)
+    }
+
+    fn correct_iteration(e: Env, iteration: u32, winner: Address, prize_amount: u32) -> Correction {
+        let mut state = storage::load_entries(&e, [winner.clone()], [iteration, storage::get_current_iteration(&e)]);
+        //the winners of the iteration corrected and of the one after it
+        for pool_iteration in state.pool_iteration_map.values().iter() {
+            storage::load_member(&e, &mut state, pool_iteration.winner);
+        }
+        let loaded = state.clone();
+        multisig::require_owner(&e, &state.pool_params, (Symbol::new(&e, "correct_iteration"), iteration, winner.clone(), prize_amount));
+        require_not_cancelled(&state);
+        if state.status == PoolStatus::Completed {
+            panic!("Pool is already settled");
+        }
+        let correction = correction::correct(&e, &mut state, iteration, winner, prize_amount);
+        storage::save_state(&e, &loaded, &state);
+        correction
+    }
+
+    fn settle_pool(e: Env) -> SettlementSummary {
+        let mut state = storage::load_state(&e);
+        let loaded = state.clone();
+        guardian::require_not_paused(&e);
+        require_not_cancelled(&state);
+        if state.status == PoolStatus::Completed {
+            panic!("Pool is already settled");
+        }
+        if !is_pool_completed(&state) {
+            panic!("Pool has not completed yet");
+        }
+        let summary = completion::settle(&e, &mut state);
+        storage::save_state(&e, &loaded, &state);
+        summary
+    }
+
+    fn archive_pool(e: Env) -> PoolArchive {
+        let mut state = storage::load_state(&e);
+        let loaded = state.clone();
+        if state.status != PoolStatus::Completed {
+            panic!("Pool has not been settled");
+        }
+        let summary = archive::archive(&e, &mut state);
+        storage::save_state(&e, &loaded, &state);
+        summary
+    }
+
+    fn get_archive(e: Env) -> Option<PoolArchive> {
+        archive::get_archive(&e)
+    }
+
+    fn freeze_member(e: Env, subscriber: Address) {
+        let pool_params = storage::get_pool_params(&e);
+        multisig::require_owner(&e, &pool_params, (Symbol::new(&e, "freeze_member"), subscriber.clone()));
+        if storage::get_member(&e, subscriber.clone()).is_none() {
+            panic!("Subscriber is not in the pool");
+        }
+        freeze::freeze(&e, subscriber);
+    }
+
+    fn unfreeze_member(e: Env, subscriber: Address) {
+        let pool_params = storage::get_pool_params(&e);
+        multisig::require_owner(&e, &pool_params, (Symbol::new(&e, "unfreeze_member"), subscriber.clone()));
+        freeze::unfreeze(&e, subscriber);
+    }
+
+    fn get_frozen_members(e: Env) -> Vec<Address> {
+        freeze::get_frozen(&e)
+    }
+
+    fn set_owner_council(e: Env, council: Option<OwnerCouncil>) {
+        let state = storage::load_pool(&e);
+        multisig::require_owner(&e, &state.pool_params, (Symbol::new(&e, "set_owner_council"), council.clone()));
+        require_not_cancelled(&state);
+        if let Some(council) = council.clone() {
+            if council.threshold == 0 || council.threshold > council.owners.len() {
+                panic!("Threshold must be between 1 and the number of owners");
+            }
+            //guardians are a check on the owners
+            if let Some(set) = guardian::get_guardians(&e) {
+                if council.owners.iter().any(|owner| set.guardians.contains(&owner)) {
+                    panic!("Guardian can't be an owner");
+                }
+            }
+        }
+        let old_council = multisig::get_council(&e);
+        changelog::record(&e, &state, symbol_short!("council"), old_council.map_or(ConfigValue::Unset, ConfigValue::Council), council.clone().map_or(ConfigValue::Unset, ConfigValue::Council));
+        multisig::set_council(&e, council);
+    }
+
+    fn get_owner_council(e: Env) -> Option<OwnerCouncil> {
+        multisig::get_council(&e)
+    }
+
+    fn approve_operation(e: Env, owner: Address, operation: Vec<Val>) -> u32 {
+        owner.require_auth();
+        multisig::approve(&e, owner, operation.into_val(&e))
+    }
+
+    fn get_operation_approvals(e: Env, operation: Vec<Val>) -> Vec<Address> {
+        multisig::get_approvals(&e, &multisig::operation_id(&e, operation.into_val(&e)))
+    }
+
+    fn is_paused(e: Env) -> bool {
+        guardian::is_paused(&e)
+    }
+
+    fn set_outflow_limit(e: Env, limit: Option<OutflowLimit>) {
+        let state = storage::load_pool(&e);
+        require_owner_before_start(&e, &state, (Symbol::new(&e, "set_outflow_limit"), limit.clone()));
+        if let Some(limit) = limit.clone() {
+            if limit.window_secs == 0 || limit.max_outflow <= 0 {
+                panic!("Window and ceiling must be positive");
+            }
+            //only the guardians can unpause a tripped breaker
+            if guardian::get_guardians(&e).is_none() {
+                panic!("Pool has no guardians");
+            }
+        }
+        let old_limit = breaker::get_outflow_limit(&e);
+        changelog::record(&e, &state, symbol_short!("outflow"), old_limit.map_or(ConfigValue::Unset, ConfigValue::Outflow), limit.clone().map_or(ConfigValue::Unset, ConfigValue::Outflow));
+        breaker::set_outflow_limit(&e, limit);
+    }
+
+    fn get_outflow_limit(e: Env) -> Option<OutflowLimit> {
+        breaker::get_outflow_limit(&e)
+    }
+
+    fn get_outflow_window(e: Env) -> OutflowWindow {
+        breaker::get_window(&e)
+    }
+
+    fn raise_dispute(e: Env, subscriber: Address, iteration: u32, reason_code: u32) -> u32 {
+        let state = storage::load_entries(&e, [subscriber.clone()], []);
+        subscriber.require_auth();
+        require_not_cancelled(&state);
+        if !state.subcriber_map.contains_key(subscriber.clone()) {
+            panic!("Subscriber is not in the pool");
+        }
+        if iteration == 0 || iteration > state.current_iteration {
+            panic!("Iteration has not been started");
+        }
+        dispute::raise(&e, subscriber, iteration, reason_code)
+    }
+
+    fn resolve_dispute(e: Env, resolver: Address, dispute_id: u32, outcome: DisputeOutcome) {
+        let state = storage::load_pool(&e);
+        resolver.require_auth();
+        dispute::require_resolver(&e, &state, &resolver);
+        dispute::resolve(&e, dispute_id, outcome);
+    }
+
+    fn get_dispute(e: Env, dispute_id: u32) -> Dispute {
+        match dispute::get_dispute(&e, dispute_id) {
+            Some(dispute) => dispute,
+            None => panic!("Dispute doesn't exist"),
+        }
+    }
+
+    fn get_disputes(e: Env, start: u32, limit: u32) -> Vec<Dispute> {
+        dispute::get_disputes(&e, start, limit)
+    }
+
+    fn propose_expulsion(e: Env, proposer: Address, subscriber: Address) -> bool {
+        let mut state = storage::load_members(&e, [storage::get_current_iteration(&e)]);
+        let loaded = state.clone();
+        proposer.require_auth();
+        require_expel_vote(&state, &proposer, &subscriber);
+        let expelled = expulsion::propose(&e, &mut state, proposer, subscriber);
+
+        storage::save_state(&e, &loaded, &state);
+        expelled
+    }
+
+    fn vote_expel(e: Env, voter: Address, subscriber: Address) -> bool {
+        let mut state = storage::load_members(&e, [storage::get_current_iteration(&e)]);
+        let loaded = state.clone();
+        voter.require_auth();
+        require_expel_vote(&state, &voter, &subscriber);
+        if expulsion::get_votes(&e, subscriber.clone()).is_empty() {
+            panic!("Expulsion has not been proposed");
+        }
+        let expelled = expulsion::vote(&e, &mut state, voter, subscriber);
+
+        storage::save_state(&e, &loaded, &state);
+        expelled
+    }
+
+    fn get_expel_votes(e: Env, subscriber: Address) -> Vec<Address> {
+        expulsion::get_votes(&e, subscriber)
+    }
+
+    fn propose_terms(e: Env, terms: PoolTerms) -> u32 {
+        let mut state = storage::load_pool(&e);
+        let loaded = state.clone();
+        require_owner_before_start(&e, &state, (Symbol::new(&e, "propose_terms"), terms.clone()));
+        validate_terms(&e, &terms);
+        let (id, approved) = proposal::propose(&e, &state, terms);
+        if let Some(terms) = approved {
+            apply_terms(&e, &mut state, terms);
+            storage::save_state(&e, &loaded, &state);
+        }
+        id
+    }
+
+    fn vote_on_proposal(e: Env, voter: Address, proposal_id: u32, approve: bool) {
+        let mut state = storage::load_pool(&e);
+        let loaded = state.clone();
+        voter.require_auth();
+        require_not_cancelled(&state);
+        if state.current_iteration != 0 {
+            panic!("Pool has already started");
+        }
+        if let Some(terms) = proposal::vote(&e, &state, voter, proposal_id, approve) {
+            apply_terms(&e, &mut state, terms);
+            storage::save_state(&e, &loaded, &state);
+        }
+    }
+
+    fn cancel_terms_proposal(e: Env) {
+        let pool_params = storage::get_pool_params(&e);
+        multisig::require_owner(&e, &pool_params, (Symbol::new(&e, "cancel_terms_proposal"),));
+        let proposal = match proposal::get_proposal(&e) {
+            Some(proposal) => proposal,
+            None => panic!("Proposal is not open"),
+        };
+        proposal::clear_proposal(&e);
+        event::terms_decided(&e, proposal.id, false);
+    }
+
+    fn get_terms_proposal(e: Env) -> Option<TermsProposal> {
+        proposal::get_proposal(&e)
+    }
+
+    fn export_snapshot(e: Env, section: SnapshotSection, cursor: u32) -> SnapshotPage {
+        let state = storage::load_state(&e);
+        snapshot::export(&e, &state, section, cursor)
+    }
+
+    fn get_version(e: Env) -> (u32, Symbol) {
+        (SCHEMA_VERSION, Symbol::new(&e, BUILD_ID))
+    }
+
+    fn get_config_history(e: Env, start: u32, limit: u32) -> Vec<ConfigChange> {
+        changelog::get_history(&e, start, limit)
+    }
+
+    fn rotate_address(e: Env, old: Address, new: Address) {
+        let mut state = load_seat(&e, &old, storage::get_member_addresses(&e));
+        //the owner holds the winner slot of the iteration still open
+        let current = state.current_iteration;
+        storage::load_iteration(&e, &mut state, current);
+        let loaded = state.clone();
+        //both keys sign, so a seat can't be pulled away from or pushed onto someone
+        old.require_auth();
+        new.require_auth();
+        //a frozen seat stays where the investigation can find it
+        freeze::require_not_frozen(&e, &old);
+        let subr: Subscriber = match state.subcriber_map.get(old.clone()) {
+            Some(subr) => subr,
+            None => panic!("Subscriber is not in the pool"),
+        };
+        if state.subcriber_map.contains_key(new.clone()) {
+            panic!("Subscriber is already in the pool");
+        }
+        if subr.guarantor == Some(new.clone()) {
+            panic!("Subscriber can't be their own guarantor");
+        }
+        require_admissible(&e, &state.pool_params, new.clone());
+        move_seat(&e, &mut state, old.clone(), new.clone(), subr);
+        //members co-signed or referred by the old address keep their guarantor and referrer
+        for (address, mut other) in state.subcriber_map.iter() {
+            if let Some(mut record) = referral::get_referral(&e, address.clone()) {
+                if record.referrer == old {
+                    record.referrer = new.clone();
+                    referral::set_referral(&e, address.clone(), &record);
+                }
+            }
+            if other.guarantor == Some(old.clone()) {
+                other.guarantor = Some(new.clone());
+                state.subcriber_map.set(address, other);
+            }
+        }
+        if state.pool_params.pool_owner == old {
+            state.pool_params.pool_owner = new.clone();
+        }
+        collateral::move_collateral(&e, old.clone(), new.clone());
+        reputation::move_reputation(&e, old.clone(), new.clone());
+        event::rotated(&e, old, new);
+
+        storage::save_state(&e, &loaded, &state);
+    }
+
+    fn transfer_slot(e: Env, from: Address, to: Address) {
+        let mut state = load_seat(&e, &from, [from.clone(), to.clone()]);
+        let loaded = state.clone();
+        from.require_auth();
+        to.require_auth();
+        multisig::require_owner(&e, &state.pool_params, (Symbol::new(&e, "transfer_slot"), from.clone(), to.clone()));
+        require_not_cancelled(&state);
+        freeze::require_not_frozen(&e, &from);
+        let subr: Subscriber = match state.subcriber_map.get(from.clone()) {
+            Some(subr) => subr,
+            None => panic!("Subscriber is not in the pool"),
+        };
+        if state.subcriber_map.contains_key(to.clone()) {
+            panic!("Subscriber is already in the pool");
+        }
+        if from == state.pool_params.pool_owner {
+            panic!("Pool owner can't transfer their slot");
+        }
+        if subr.defaulted {
+            panic!("Subscriber has defaulted");
+        }
+        if access::is_denied(&e, from.clone()) {
+            panic!("Address is on the denylist");
+        }
+        require_admissible(&e, &state.pool_params, to.clone());
+        hand_over_seat(&e, &mut state, from.clone(), to.clone(), subr);
+        event::slot_transferred(&e, from, to);
+
+        storage::save_state(&e, &loaded, &state);
+    }
+
+    fn substitute_member(e: Env, leaving: Address, incoming: Address) {
+        let mut state = load_seat(&e, &leaving, [leaving.clone(), incoming.clone()]);
+        let loaded = state.clone();
+        incoming.require_auth();
+        multisig::require_owner(&e, &state.pool_params, (Symbol::new(&e, "substitute_member"), leaving.clone(), incoming.clone()));
+        require_not_cancelled(&state);
+        freeze::require_not_frozen(&e, &leaving);
+        let mut subr: Subscriber = match state.subcriber_map.get(leaving.clone()) {
+            Some(subr) => subr,
+            None => panic!("Subscriber is not in the pool"),
+        };
+        if state.subcriber_map.contains_key(incoming.clone()) {
+            panic!("Subscriber is already in the pool");
+        }
+        if leaving == state.pool_params.pool_owner {
+            panic!("Pool owner can't be substituted");
+        }
+        if subr.defaulted {
+            panic!("Subscriber has defaulted");
+        }
+        require_admissible(&e, &state.pool_params, incoming.clone());
+        //the dues the leaving member missed are paid by the one taking over, so the pots stay whole
+        let owed = subr.prev_due_amount + subr.arrears_interest;
+        if owed > 0 {
+            repay_arrears(&e, &mut state, incoming.clone(), &mut subr, owed);
+        }
+        hand_over_seat(&e, &mut state, leaving.clone(), incoming.clone(), subr);
+        event::member_substituted(&e, leaving, incoming, owed);
+
+        storage::save_state(&e, &loaded, &state);
+    }
+}
+
+// `payer` pays `amount` of the arrears of the member, the interest first
+fn repay_arrears(e: &Env, state: &mut State, payer: Address, subr: &mut Subscriber, amount: u32) {
+    let token_client = token::Client::new(e, &state.pool_params.token);
+    token_client.transfer(&payer, &e.current_contract_address(), &i128::from(amount));
+    let (interest, principal) = arrears::repay(e, subr, amount);
+    state.penalties_collected += interest;
+    //the principal joins the next pot
+    ledger::receive(e, LedgerAccount::Fees, i128::from(interest));
+    ledger::receive(e, LedgerAccount::Pot, i128::from(principal));
+}
+
+// the pool with the entries of `members` and of the iteration the seat of `winner` won, for the flows on a prize
+// or moving a seat
+fn load_seat(e: &Env, winner: &Address, members: impl IntoIterator<Item = Address>) -> State {
+    let mut state = storage::load_entries(e, members, []);
+    if let Some(subr) = state.subcriber_map.get(winner.clone()) {
+        storage::load_iteration(e, &mut state, subr.winner_at_iter);
+    }
+    state
+}
+
+// hand the seat over to a new member, who puts up their own collateral
+fn hand_over_seat(e: &Env, state: &mut State, from: Address, to: Address, mut subr: Subscriber) {
+    //the guarantor vouched for the previous holder only, and the payer and sponsor paid for them
+    subr.guarantor = None;
+    subr.payer = None;
+    sponsorship::refund(e, &state.pool_params, from.clone());
+    profile::remove_profile(e, from.clone());
+    move_seat(e, state, from.clone(), to.clone(), subr);
+    collateral::release_collateral(e, &state.pool_params, from);
+    collateral::deposit_collateral(e, &state.pool_params, to);
+}
+
+// hand the seat of `old`, with its dues history and winnings, to `new`
+fn move_seat(e: &Env, state: &mut State, old: Address, new: Address, subr: Subscriber) {
+    state.subcriber_map.remove(old.clone());
+    state.subcriber_map.set(new.clone(), subr);
+    for (iteration, mut pool_iteration) in state.pool_iteration_map.iter() {
+        if pool_iteration.winner == old {
+            pool_iteration.winner = new.clone();
+            state.pool_iteration_map.set(iteration, pool_iteration);
+        }
+    }
+    payer::move_payer_stats(e, old.clone(), new.clone());
+    history::move_history(e, old.clone(), new.clone());
+    insurance::move_seat_insurance(e, old.clone(), new.clone());
+    sponsorship::move_sponsorship(e, old.clone(), new.clone());
+    profile::move_profile(e, old.clone(), new.clone());
+    referral::move_referral(e, old.clone(), new.clone());
+    cancellation::move_vote(e, old.clone(), new.clone());
+    expulsion::move_votes(e, old.clone(), new.clone());
+    badge::transfer(e, old, new);
+}
+
+// give the seat of `user` up before the pool starts for the first one waiting, returns the collateral released
+fn remove_subscriber(e: &Env, state: &mut State, user: Address) -> i128 {
+    state.subcriber_map.remove(user.clone());
+    let released = collateral::release_collateral(e, &state.pool_params, user.clone());
+    cancellation::drop_vote(e, user.clone());
+    expulsion::drop_votes(e, user.clone());
+    badge::burn(e, user);
+    waitlist::promote(e, state);
+    released
+}
+
+// give `user` a seat in the pool, with `guarantor` co-signing it
+fn add_subscriber(e: &Env, state: &mut State, user: Address, guarantor: Option<Address>) {
+    //the members seated earlier in the same call aren't in the index until the pool is saved
+    let mut members = storage::get_member_addresses(e);
+    for address in state.subcriber_map.keys().iter() {
+        if !members.contains(&address) {
+            members.push_back(address);
+        }
+    }
+    require_open_seat(e, state, &members, user.clone());
+    user.require_auth();
+    require_admissible(e, &state.pool_params, user.clone());
+    let mut subscriber = new_subscriber();
+    // the guarantor co-signs the membership
+    if let Some(guarantor) = guarantor {
+        if guarantor == user {
+            panic!("Subscriber can't be their own guarantor");
+        }
+        guarantor.require_auth();
+        subscriber.guarantor = Some(guarantor);
+    }
+    //the subscriber gets its own entry when the pool is saved, the other members aren't read
+    state.subcriber_map.set(user.clone(), subscriber);
+    collateral::deposit_collateral(e, &state.pool_params, user.clone());
+    badge::mint(e, user);
+}
+
+// a seat is free for `user` to join
+fn require_open_seat(e: &Env, state: &State, members: &Vec<Address>, user: Address) {
+    // check if the subscriber is already in the pool. Only one address allowed in apool
+    if members.contains(&user) {
+        panic!("Subscriber is already in the pool");
+    }
+    require_not_cancelled(state);
+    // the member list is locked once the pool has started
+    if state.status != PoolStatus::Open {
+        panic!("Pool has already started");
+    }
+    if is_past_join_deadline(e, &state.pool_params) {
+        panic!("Join deadline has passed");
+    }
+    // seats are drawn among the registrations while a fair launch is open
+    if fair_launch::get_fair_launch(e).is_some() {
+        panic!("Pool is in fair-launch mode");
+    }
+    if members.len() >= state.pool_params.no_of_subs {
+        panic_with_error!(e, Error::PoolFull);
+    }
+}
+
+// add `iteration` to the pool, `placeholder` stands as its winner until one is picked
+fn open_iteration(e: &Env, state: &mut State, iteration: u32, placeholder: Address, start_time: u64) {
+    //create a new instance of PoolIterationParams
+    let pool_iteration = PoolIterationParams {
+        current_iteration: iteration,
+        amount_collected: 0,
+        winner: placeholder,
+        prize_money: 0,
+        dividend_amount: 0,
+        commission: 0,
+        protocol_fee: 0,
+        //the dividend carried from the previous iteration joins this pot
+        carried_in: dividend::take_carryover(e),
+        dividend_distributed: 0,
+        discounts: 0,
+        yield_earned: 0,
+        reserve_cut: 0,
+        reserve_backfill: 0,
+        start_time,
+        //the value of the due is fixed, its token amount follows the price
+        due_amount: oracle::iteration_due(e, &state.pool_params),
+        prize_assignments: Vec::new(e)
+    };
+    //save the pool iteration in the pool_iteration_map
+    state.pool_iteration_map.set(iteration, pool_iteration);
+    state.current_iteration = iteration;
+    //members who prepaid or are sponsored have the new due settled when their credit and sponsorship cover all of it
+    for (address, subr) in state.subcriber_map.iter() {
+        let available = subr.credit_balance + sponsorship::get_balance(e, address.clone());
+        if available > 0
+            && !subr.defaulted
+            && available >= charge_due(e, state, address.clone(), &subr, iteration)
+        {
+            apply_payment(e, state, address.clone(), address, iteration, None, false);
+        }
+    }
+}
+
+// members needed to start the pool, every seat unless the owner lowered it
+fn get_min_subscribers(e: &Env, pool_params: &PoolParams) -> u32 {
+    storage::get_instance(e, &DataKey::MinSubscribers).unwrap_or(pool_params.no_of_subs)
+}
+
+// what completing the due of `iteration` charges the subscriber now, fees and premiums included, before using
+// their credit. Mirrors what apply_payment charges
+fn charge_due(e: &Env, state: &State, subscriber: Address, subr: &Subscriber, iteration: u32) -> u32 {
+    let preview = preview::payment(e, state, subscriber, subr, iteration);
+    preview.due + preview.late_fee + preview.premium + preview.loan_interest - preview.discount
+}
+
+// record `amount` of the due of `subscriber` for `iteration` as paid by `payer`, None pays all of it.
+// With `from_allowance` the tokens are pulled from the payer with the allowance they gave the pool
+fn apply_payment(e: &Env, state: &mut State, subscriber: Address, payer: Address, iteration: u32, amount: Option<u32>, from_allowance: bool) {
+    let mut subr: Subscriber = match state.subcriber_map.get(subscriber.clone()) {
+        Some(subr) => subr,
+        None => panic!("Subscriber is not in the pool"),
+    };
+    let mut pool_iteration: PoolIterationParams = match state.pool_iteration_map.get(iteration) {
+        Some(pool_iteration) => pool_iteration,
+        None => panic!("Iteration has not been started"),
+    };
+    require_not_cancelled(state);
+    // a subscriber pays once per iteration
+    if subr.last_paid_iter >= iteration {
+        panic!("Subscriber has already paid for this iteration");
+    }
+    // dues of closed iterations are owed as arrears
+    if iteration < state.current_iteration {
+        panic!("Missed dues are paid as arrears");
+    }
+    let due = shares::scaled(&subr, pool_iteration.due_amount);
+    let late = !is_on_time(e, &state.pool_params, &pool_iteration);
+    //a due can be paid in parts, the iteration only counts as paid once all of it is in
+    let partial = if subr.partial_iter == iteration { subr.partial_amount } else { 0 };
+    let amount = amount.unwrap_or(due - partial);
+    if amount == 0 {
+        panic!("Amount must be positive");
+    }
+    let applied = amount.min(due - partial);
+    let mut late_fee = 0;
+    let mut premium = 0;
+    let mut interest = 0;
+    let mut discount = 0;
+    if partial + applied < due {
+        subr.partial_iter = iteration;
+        subr.partial_amount = partial + applied;
+        subr.partial_payments += 1;
+    } else {
+        subr.partial_iter = 0;
+        subr.partial_amount = 0;
+        //payments inside the grace window are still treated as on-time, fees are charged with the payment completing the due
+        if !late {
+            reputation::increase(e, subscriber.clone(), reputation::ON_TIME_PAYMENT_REWARD);
+        } else {
+            subr.late_payments += 1;
+            reputation::decrease(e, subscriber.clone(), reputation::LATE_PAYMENT_PENALTY);
+            late_fee = penalty::charge(e, &state.pool_params, &mut subr, penalty::late_fee(e, due));
+            state.penalties_collected += late_fee;
+        }
+        //dues paid after winning repay the prize loan, the interest is kept with the penalties
+        interest = loan::repay(e, &mut subr, due);
+        state.penalties_collected += interest;
+        //dues completed early get a discount, the pot still counts the whole due and the dividend makes up for it
+        discount = discount::discount_on(e, &state.pool_params, &pool_iteration, due).min(applied);
+        pool_iteration.discounts += discount;
+        subr.last_paid_iter = iteration;
+        premium = insurance::collect_premium(e, state, subscriber.clone());
+    }
+    //the sponsorship goes first then the credit left from earlier payments, anything sent over the due is added to it
+    let charge = applied + late_fee + premium + interest - discount;
+    let sponsored = sponsorship::draw(e, subscriber.clone(), charge);
+    let from_credit = subr.credit_balance.min(charge - sponsored);
+    let excess = amount - applied;
+    subr.credit_balance = subr.credit_balance - from_credit + excess;
+    subr.total_paid += applied - discount;
+    state.subcriber_map.set(subscriber.clone(), subr);
+    let transferred = charge - sponsored - from_credit + excess;
+    if transferred > 0 {
+        let token_client = token::Client::new(e, &state.pool_params.token);
+        let pool = e.current_contract_address();
+        if from_allowance {
+            token_client.transfer_from(&pool, &payer, &pool, &i128::from(transferred));
+        } else {
+            token_client.transfer(&payer, &pool, &i128::from(transferred));
+        }
+    }
+    //what comes in is held for the member, then the charge moves out of their funds
+    ledger::receive(e, LedgerAccount::Members, i128::from(transferred));
+    ledger::post(e, LedgerAccount::Members, LedgerAccount::Pot, i128::from(applied - discount));
+    ledger::post(e, LedgerAccount::Members, LedgerAccount::Fees, i128::from(late_fee + interest));
+    ledger::post(e, LedgerAccount::Members, LedgerAccount::Insurance, i128::from(premium));
+    let record = PaymentRecord {
+        iteration,
+        payer: payer.clone(),
+        amount: transferred,
+        discount,
+        timestamp: e.ledger().timestamp(),
+        late
+    };
+    history::record(e, subscriber.clone(), &record);
+    event::paid(e, subscriber.clone(), &record);
+    payer::record_payment(e, subscriber, payer, iteration, applied);
+
+    pool_iteration.amount_collected += applied;
+    state.pool_iteration_map.set(iteration, pool_iteration);
+}
+
+// mark `subscriber` as the winner of `iteration` with the given prize
+fn assign_winner(e: &Env, state: &mut State, iteration: u32, prize_amount: u32, subscriber: Address) {
+    require_not_cancelled(state);
+    // get the subscriber details for given address
+    let mut subr:Subscriber = state.subcriber_map.get(subscriber.clone()).unwrap();
+    // check if the subscriber is already marked as a winner
+    if subr.winner_at_iter != 0 {
+        panic!("Subscriber is already a winner");
+    }
+    // defaulted subscribers can't win the pool
+    if subr.defaulted {
+        panic!("Subscriber has defaulted");
+    }
+    freeze::require_not_frozen(e, &subscriber);
+    //the counts of the pool still hold the winner among the members yet to win until it is saved
+    let winner_shares = subr.shares;
+    //set iterarion for this subscriber
+    subr.winner_at_iter = iteration;
+    //set prize money for this subscriber
+    subr.prize_money = prize_amount;
+    // get the pool iteration details
+    let mut pool_iteration:PoolIterationParams = state.pool_iteration_map.get(iteration).unwrap();
+    //dues held in other tokens are converted before anything is paid out of the pot
+    settlement::settle(e, &state.pool_params);
+    //the pot lent out comes back before the payout, what it earned goes to the dividend
+    pool_iteration.yield_earned = yield_adapter::withdraw(e, iteration);
+    //the reserve takes its cut and makes up for the dues of the defaulters before the dividend shrinks
+    reserve::fill(e, state, &mut pool_iteration);
+    //the dues still ahead of the winner become a loan taken with the prize
+    if loan::get_loan_interest(e).is_some() {
+        subr.loan_principal = loan::principal_for(state, iteration, shares::scaled(&subr, pool_iteration.due_amount));
+        subr.loan_outstanding = subr.loan_principal;
+    }
+    //save the subscriber in the subcriber_map
+    state.subcriber_map.set(subscriber.clone(), subr);
+
+    // set the winner for the given iteration
+    pool_iteration.winner = subscriber.clone();
+    // set the prize money for the given iteration
+    pool_iteration.prize_money = prize_amount;
+    //the owner commission and the protocol fee come off the pot first
+    let commission = commission::commission_on(e, pool_iteration.amount_collected);
+    let protocol_fee = treasury::fee_on(e, pool_iteration.amount_collected);
+    if prize_amount > prize_pot(e, &pool_iteration) {
+        panic!("Prize exceeds the pot after fees");
+    }
+    commission::charge(e, commission);
+    treasury::accrue(e, iteration, protocol_fee);
+    reserve::settle(e, &pool_iteration);
+    ledger::post(e, LedgerAccount::Pot, LedgerAccount::Fees, i128::from(commission + protocol_fee));
+    ledger::post(e, LedgerAccount::Pot, LedgerAccount::Insurance, i128::from(pool_iteration.reserve_cut));
+    ledger::post(e, LedgerAccount::Insurance, LedgerAccount::Pot, i128::from(pool_iteration.reserve_backfill));
+    pool_iteration.commission = commission;
+    pool_iteration.protocol_fee = protocol_fee;
+    //set iteration to the pool iteration map
+    pool_iteration.dividend_amount = prize_pot(e, &pool_iteration) - prize_amount;
+    pool_iteration.dividend_distributed = dividend::distribute(state, pool_iteration.dividend_amount, winner_shares);
+    ledger::post(e, LedgerAccount::Pot, LedgerAccount::Dividends, i128::from(pool_iteration.dividend_distributed));
+    //whatever the members weren't credited joins the next pot, unless the owner is still to allocate it
+    if state.pool_params.dividend_policy != DividendPolicy::OwnerDiscretion {
+        dividend::carry(e, pool_iteration.dividend_amount - pool_iteration.dividend_distributed);
+    }
+    state.pool_iteration_map.set(iteration, pool_iteration);
+    event::won(e, subscriber, iteration, prize_amount);
+    //the reserve nobody needed goes back to the members once the last of them won
+    if state.eligible_shares == winner_shares {
+        let released = reserve::release(e, state);
+        ledger::post(e, LedgerAccount::Insurance, LedgerAccount::Dividends, i128::from(released));
+    }
+    //the next iteration is opened by start_new_iteration, which goes over every member for the missed payments
+}
+
+// `iteration` fully paid and closed with the whole pot as the prize of `winner`, to size its entry
+fn closed_iteration(e: &Env, iteration: u32, winner: Address, pot: u32, due_amount: u32) -> PoolIterationParams {
+    PoolIterationParams {
+        current_iteration: iteration,
+        amount_collected: pot,
+        winner,
+        prize_money: pot,
+        dividend_amount: 0,
+        commission: 0,
+        protocol_fee: 0,
+        carried_in: 0,
+        dividend_distributed: 0,
+        discounts: 0,
+        yield_earned: 0,
+        reserve_cut: 0,
+        reserve_backfill: 0,
+        start_time: e.ledger().timestamp(),
+        due_amount,
+        prize_assignments: Vec::new(e)
+    }
+}
+
+// pot of the iteration left for the prize and dividend once the fees are taken, the carried dividend is fee-free
+fn prize_pot(e: &Env, pool_iteration: &PoolIterationParams) -> u32 {
+    let collected = pool_iteration.amount_collected;
+    collected - commission::commission_on(e, collected) - treasury::fee_on(e, collected) + pool_iteration.carried_in
+        - pool_iteration.discounts + pool_iteration.yield_earned - pool_iteration.reserve_cut + pool_iteration.reserve_backfill
+}
+
+// once the winner of the current iteration is known the next one opens at its scheduled time, until every seat has had its turn
+fn roll_over(e: &Env, state: &mut State, iteration: u32) {
+    if iteration != state.current_iteration || iteration >= state.pool_params.no_of_subs {
+        return;
+    }
+    record_missed_payments(e, state, iteration);
+    let start_time = state.pool_iteration_map.get(iteration).unwrap().start_time
+        + frequency_to_secs(&state.pool_params.frequency);
+    let owner = state.pool_params.pool_owner.clone();
+    open_iteration(e, state, iteration + 1, owner, start_time);
+}
+
+// prizes come out of a fully funded round unless the owner documented the shortfall
+fn require_fully_funded(e: &Env, state: &State, iteration: u32) {
+    let collected = match state.pool_iteration_map.get(iteration) {
+        Some(pool_iteration) => pool_iteration.amount_collected,
+        None => panic!("Iteration has not been started"),
+    };
+    if collected < expected_collection(state, iteration)
+        && !storage::has_instance(e, &DataKey::ShortfallApproval(iteration))
+    {
+        panic!("Iteration has not collected every due");
+    }
+}
+
+// members still eligible to win
+fn winner_candidates(e: &Env, state: &State) -> Vec<Address> {
+    let mut candidates = Vec::new(e);
+    for (address, subr) in state.subcriber_map.iter() {
+        if subr.winner_at_iter == 0 && !subr.defaulted && !freeze::is_frozen(e, &address) {
+            candidates.push_back(address);
+        }
+    }
+    if candidates.is_empty() {
+        panic!("No subscriber left to win");
+    }
+    candidates
+}
+
+// dues the iteration should collect: one per share, except from defaulted members who didn't pay it
+fn expected_collection(state: &State, iteration: u32) -> u32 {
+    let due = state.pool_iteration_map.get(iteration).unwrap().due_amount;
+    due * shares::owing_shares(state, iteration)
+}
+
+// the seats already taken have to fit in the pool
+fn validate_terms(e: &Env, terms: &PoolTerms) {
+    if terms.sub_amount == 0 {
+        panic!("Amount must be positive");
+    }
+    if terms.no_of_subs < storage::get_member_addresses(e).len() {
+        panic!("Pool size can't be below the members already joined");
+    }
+}
+
+fn apply_terms(e: &Env, state: &mut State, terms: PoolTerms) {
+    validate_terms(e, &terms);
+    let old_terms = PoolTerms {
+        sub_amount: state.pool_params.sub_amount,
+        frequency: state.pool_params.frequency.clone(),
+        no_of_subs: state.pool_params.no_of_subs,
+    };
+    changelog::record(e, state, symbol_short!("terms"), ConfigValue::Terms(old_terms), ConfigValue::Terms(terms.clone()));
+    state.pool_params.sub_amount = terms.sub_amount;
+    state.pool_params.frequency = terms.frequency;
+    state.pool_params.no_of_subs = terms.no_of_subs;
+}
+
+// only paid-up members vote, on expelling a member who defaulted while the pool runs
+fn require_expel_vote(state: &State, voter: &Address, subscriber: &Address) {
+    require_not_cancelled(state);
+    if state.current_iteration == 0 {
+        panic!("Pool has not started");
+    }
+    let voter_subr = match state.subcriber_map.get(voter.clone()) {
+        Some(subr) => subr,
+        None => panic!("Subscriber is not in the pool"),
+    };
+    if !expulsion::is_paid_up(state, &voter_subr) {
+        panic!("Only paid-up members can vote");
+    }
+    let subr = match state.subcriber_map.get(subscriber.clone()) {
+        Some(subr) => subr,
+        None => panic!("Subscriber is not in the pool"),
+    };
+    if !subr.defaulted {
+        panic!("Subscriber has not defaulted");
+    }
+    if *subscriber == state.pool_params.pool_owner {
+        panic!("Pool owner can't be expelled");
+    }
+}
+
+// only the pool owner, or its council, can change the pool configuration, and only before the first iteration
+fn require_owner_before_start(e: &Env, state: &State, operation: impl IntoVal<Env, Val>) {
+    multisig::require_owner(e, &state.pool_params, operation);
+    require_not_cancelled(state);
+    if state.current_iteration != 0 {
+        panic!("Pool has already started");
+    }
+}
+
+// sensitive parameter changes are checked the same way whether they are made directly or through the timelock
+fn validate_param_change(e: &Env, state: &State, change: &ParamChange) {
+    match change {
+        ParamChange::Commission(commission_bps) => {
+            if *commission_bps > commission::MAX_COMMISSION_BPS {
+                panic!("Commission can't exceed 5%");
+            }
+        }
+        ParamChange::ProtectionLimits(config) => {
+            if config.late_fee_bps > PERCENTAGE_MULTIPLIER || config.max_penalty_bps > PERCENTAGE_MULTIPLIER {
+                panic!("Rates can't exceed 100%");
+            }
+        }
+        ParamChange::Token(token) => {
+            //the balances the pool keeps are all in the one token
+            if state.current_iteration != 0 {
+                panic!("Pool has already started");
+            }
+            let balance = token::Client::new(e, &state.pool_params.token).balance(&e.current_contract_address());
+            if balance != 0 {
+                panic!("Pool still holds the current token");
+            }
+            require_token(e, token);
+        }
+    }
+}
+
+fn apply_param_change(e: &Env, state: &mut State, change: ParamChange) {
+    validate_param_change(e, state, &change);
+    match change {
+        ParamChange::Commission(commission_bps) => {
+            changelog::record(e, state, symbol_short!("comm"), ConfigValue::U32(commission::get_commission_bps(e)), ConfigValue::U32(commission_bps));
+            commission::set_commission_bps(e, commission_bps);
+        }
+        ParamChange::ProtectionLimits(config) => {
+            changelog::record(e, state, symbol_short!("penalty"), ConfigValue::Penalty(penalty::get_penalty_config(e)), ConfigValue::Penalty(config.clone()));
+            penalty::set_penalty_config(e, &config);
+        }
+        ParamChange::Token(token) => {
+            changelog::record(e, state, symbol_short!("token"), ConfigValue::Address(state.pool_params.token.clone()), ConfigValue::Address(token.clone()));
+            state.pool_params.token = token;
+        }
+    }
+}
+
+// probe the token the pool is paid in, so a pool can't be set up against a contract that isn't one
+fn require_token(e: &Env, token: &Address) {
+    let client = token::Client::new(e, token);
+    let pool = e.current_contract_address();
+    //a transfer of nothing to itself leaves every balance as it is
+    let implemented = matches!(client.try_decimals(), Ok(Ok(_)))
+        && matches!(client.try_balance(&pool), Ok(Ok(_)))
+        && matches!(client.try_transfer(&pool, &pool, &0), Ok(Ok(())));
+    if !implemented {
+        panic!("Token doesn't implement the token interface");
+    }
+}
+
+fn require_not_cancelled(state: &State) {
+    if state.status == PoolStatus::Cancelled {
+        panic!("Pool has been cancelled");
+    }
+}
+
+// checks every address taking a seat has to pass, whichever way it comes in: not denylisted, allowlisted and verified
+// when the pool asks for it, and with the reputation the pool asks for, read from the shared ledger when there is one
+fn require_admissible(e: &Env, pool_params: &PoolParams, user: Address) {
+    if access::is_denied(e, user.clone()) {
+        panic!("Address is on the denylist");
+    }
+    if !access::is_allowed(e, user.clone()) {
+        panic!("Address is not on the allowlist");
+    }
+    if !access::is_verified(e, user.clone()) {
+        panic!("Address has not been verified");
+    }
+    if reputation::get_effective_reputation(e, user) < pool_params.min_reputation {
+        panic!("Reputation is below the pool minimum");
+    }
+}
+
+// count a missed iteration for every subscriber that didn't pay it, defaulting repeat offenders
+fn record_missed_payments(e: &Env, state: &mut State, iteration: u32) {
+    let share_due = state.pool_iteration_map.get(iteration).unwrap().due_amount;
+    for (address, mut subr) in state.subcriber_map.iter() {
+        if subr.last_paid_iter >= iteration {
+            //the arrears carried through the iteration still accrue interest
+            if subr.prev_due_amount > 0 {
+                arrears::accrue(e, &state.pool_params, &mut subr, 0);
+                state.subcriber_map.set(address, subr);
+            }
+            continue;
+        }
+        let due = shares::scaled(&subr, share_due);
+        //an insured seat gets one missed due per cycle paid by the fund, without penalties
+        if !subr.defaulted && insurance::cover_missed_due(e, state, address.clone(), iteration, due) {
+            subr.last_paid_iter = iteration;
+            state.subcriber_map.set(address, subr);
+            continue;
+        }
+        //what wasn't paid of the due is owed from now on as arrears
+        let partial = if subr.partial_iter == iteration { subr.partial_amount } else { 0 };
+        arrears::accrue(e, &state.pool_params, &mut subr, due - partial);
+        subr.missed_iterations += 1;
+        if subr.defaulted {
+            //the pool keeps running short of what this subscriber didn't pay of the due
+            state.shortfall += due - partial;
+        } else if subr.missed_iterations >= state.pool_params.max_missed_iterations {
+            default_subscriber(e, state, address.clone(), &mut subr);
+        }
+        state.subcriber_map.set(address, subr);
+    }
+}
+
+// freeze the subscriber's eligibility to win and record the dues missed so far against the pool, each at the
+// amount it was due in its own iteration
+fn default_subscriber(e: &Env, state: &mut State, address: Address, subr: &mut Subscriber) {
+    let shortfall = subr.prev_due_amount;
+    subr.defaulted = true;
+    state.shortfall += shortfall;
+    reputation::decrease(e, address.clone(), reputation::DEFAULT_PENALTY);
+    event::defaulted(e, address, subr.missed_iterations, shortfall);
+}
+
+fn is_past_join_deadline(e: &Env, pool_params: &PoolParams) -> bool {
+    match pool_params.join_deadline {
+        Some(join_deadline) => e.ledger().timestamp() > join_deadline,
+        None => false,
+    }
+}
+
+// every subscriber still eligible to win has won an iteration
+fn is_pool_completed(state: &State) -> bool {
+    state.current_iteration != 0 && state.eligible_shares == 0
+}
+
+// a payment is on-time until the end of the iteration window plus the grace period
+fn is_on_time(e: &Env, pool_params: &PoolParams, pool_iteration: &PoolIterationParams) -> bool {
+    let deadline = pool_iteration.start_time
+        + frequency_to_secs(&pool_params.frequency)
+        + pool_params.grace_period_secs;
+    e.ledger().timestamp() <= deadline
+}
+
+
+#[cfg(any(test, feature = "demo"))]
+mod demo;
+mod access;
+mod archive;
+mod arrears;
+mod assignment;
+mod badge;
+mod breaker;
+mod cancellation;
+mod changelog;
+mod collateral;
+mod commission;
+mod completion;
+mod correction;
+mod discount;
+mod dispute;
+mod dividend;
+mod emergency;
+mod event;
+mod expulsion;
+mod fair_launch;
+mod freeze;
+mod guardian;
+mod history;
+mod insurance;
+mod invariant;
+mod ledger;
+mod loan;
+mod multisig;
+mod oracle;
+mod payer;
+mod preview;
+mod privacy;
+mod profile;
+mod proposal;
+mod reconcile;
+mod penalty;
+mod referral;
+mod reminder;
+mod settlement;
+mod reputation;
+mod reserve;
+mod snapshot;
+mod slashing;
+mod shares;
+mod sponsorship;
+mod stats;
+mod storage;
+mod successor;
+mod swap;
+pub mod testutils;
+mod timelock;
+mod strategy;
+mod treasury;
+mod vesting;
+mod waitlist;
+mod yield_adapter;
 
+pub use crate::access::{AttestationClient, AttestationInterface};
+pub use crate::arrears::MAX_ARREARS_INTEREST_BPS;
+pub use crate::commission::MAX_COMMISSION_BPS;
+pub use crate::discount::MAX_EARLY_DISCOUNT_BPS;
+pub use crate::emergency::EMERGENCY_DELAY_SECS;
+pub use crate::loan::MAX_LOAN_INTEREST_BPS;
+pub use crate::oracle::{Asset, PriceData, PriceOracleClient, PriceOracleInterface};
+pub use crate::profile::MAX_DISPLAY_NAME_LEN;
+pub use crate::reminder::MAX_REMINDERS_PER_CALL;
+pub use crate::reputation::{ReputationLedgerClient, ReputationLedgerInterface};
+pub use crate::reserve::MAX_RESERVE_BPS;
+pub use crate::shares::MAX_SHARES;
+pub use crate::snapshot::{SNAPSHOT_PAGE_SIZE, SNAPSHOT_VERSION};
+pub use crate::strategy::{WinnerStrategyClient, WinnerStrategyInterface};
+pub use crate::successor::{PoolFactoryClient, PoolFactoryInterface};
+pub use crate::swap::{SwapRouterClient, SwapRouterInterface, MAX_SLIPPAGE_BPS};
+pub use crate::timelock::MAX_TIMELOCK_DELAY_SECS;
+pub use crate::treasury::MAX_PROTOCOL_FEE_BPS;
+pub use crate::vesting::MAX_VESTING_TRANCHES;
+pub use crate::yield_adapter::{YieldAdapterClient, YieldAdapterInterface};
 mod test;
@@ -0,0 +1,55 @@
+use soroban_sdk::Env;
+
+use crate::{storage, DataKey, PrizeLoan, State, Subscriber, PERCENTAGE_MULTIPLIER};
+
+// cap on the interest charged on prize loans
+pub const MAX_LOAN_INTEREST_BPS: u32 = 2_000; // 20%
+
+// interest charged on every repayment of a prize loan, None when prizes aren't treated as loans
+pub fn get_loan_interest(e: &Env) -> Option<u32> {
+    storage::get_instance(e, &DataKey::LoanInterestBps)
+}
+
+pub fn set_loan_interest(e: &Env, interest_bps: Option<u32>) {
+    match interest_bps {
+        Some(interest_bps) => storage::set_instance(e, &DataKey::LoanInterestBps, &interest_bps),
+        None => storage::remove_instance(e, &DataKey::LoanInterestBps),
+    }
+}
+
+// dues the winner of `iteration` still owes the pool, lent to them along with the prize
+pub fn principal_for(state: &State, iteration: u32, due: u32) -> u32 {
+    due * (state.pool_params.no_of_subs - iteration)
+}
+
+// interest owed on top of a due of `due`, 0 when the subscriber has no loan outstanding
+pub fn interest_on(e: &Env, subr: &Subscriber, due: u32) -> u32 {
+    let interest_bps = get_loan_interest(e).unwrap_or(0);
+    let repaid = due.min(subr.loan_outstanding);
+    (u64::from(repaid) * u64::from(interest_bps) / u64::from(PERCENTAGE_MULTIPLIER)) as u32
+}
+
+// apply a due of `due` to the loan of the subscriber, returns the interest charged with it
+pub fn repay(e: &Env, subr: &mut Subscriber, due: u32) -> u32 {
+    let interest = interest_on(e, subr, due);
+    subr.loan_outstanding -= due.min(subr.loan_outstanding);
+    subr.loan_interest_paid += interest;
+    interest
+}
+
+// the loan of the subscriber as it stands, `installment` is the due repaying it every iteration
+pub fn loan_of(e: &Env, subr: &Subscriber, installment: u32) -> PrizeLoan {
+    let remaining_installments = if installment == 0 {
+        0
+    } else {
+        subr.loan_outstanding.div_ceil(installment)
+    };
+    PrizeLoan {
+        principal: subr.loan_principal,
+        outstanding: subr.loan_outstanding,
+        interest_bps: get_loan_interest(e).unwrap_or(0),
+        interest_paid: subr.loan_interest_paid,
+        installment,
+        remaining_installments,
+    }
+}
@@ -0,0 +1,91 @@
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{Address, BytesN, Env, IntoVal, Val, Vec};
+
+use crate::{event, storage, GovernanceKey, OwnerCouncil, PoolParams};
+
+pub fn get_council(e: &Env) -> Option<OwnerCouncil> {
+    storage::get_instance(e, &GovernanceKey::OwnerCouncil)
+}
+
+// a new council starts without the approvals given under the previous one
+pub fn set_council(e: &Env, council: Option<OwnerCouncil>) {
+    clear_approvals(e);
+    match council {
+        Some(council) => storage::set_instance(e, &GovernanceKey::OwnerCouncil, &council),
+        None => storage::remove_instance(e, &GovernanceKey::OwnerCouncil),
+    }
+}
+
+// an operation is the name of the entry point followed by its arguments, approvals are kept under its hash
+pub fn operation_id(e: &Env, operation: Val) -> BytesN<32> {
+    e.crypto().sha256(&operation.to_xdr(e)).to_bytes()
+}
+
+pub fn get_approvals(e: &Env, id: &BytesN<32>) -> Vec<Address> {
+    storage::get_instance(e, &GovernanceKey::OwnerApprovals(id.clone())).unwrap_or(Vec::new(e))
+}
+
+// count the approval of `owner` for a pending operation. Returns the approvals it has
+pub fn approve(e: &Env, owner: Address, operation: Val) -> u32 {
+    let council = match get_council(e) {
+        Some(council) => council,
+        None => panic!("Pool has no owner council"),
+    };
+    if !council.owners.contains(&owner) {
+        panic!("Address is not an owner");
+    }
+    let id = operation_id(e, operation);
+    let mut approvals = get_approvals(e, &id);
+    if approvals.contains(&owner) {
+        panic!("Owner has already approved");
+    }
+    if approvals.is_empty() {
+        let mut pending = get_pending(e);
+        pending.push_back(id.clone());
+        storage::set_instance(e, &GovernanceKey::PendingOperations, &pending);
+    }
+    approvals.push_back(owner.clone());
+    storage::set_instance(e, &GovernanceKey::OwnerApprovals(id.clone()), &approvals);
+    event::operation_approved(e, owner, id);
+    approvals.len()
+}
+
+// admin actions are authorized by the pool owner, or with an owner council by its approvals of the exact operation,
+// which are used up by carrying it out
+pub fn require_owner(e: &Env, pool_params: &PoolParams, operation: impl IntoVal<Env, Val>) {
+    let council = match get_council(e) {
+        Some(council) => council,
+        None => {
+            pool_params.pool_owner.require_auth();
+            return;
+        }
+    };
+    let id = operation_id(e, operation.into_val(e));
+    //only the owners still on the council count
+    let approvals = get_approvals(e, &id)
+        .iter()
+        .filter(|owner| council.owners.contains(owner))
+        .count() as u32;
+    if approvals < council.threshold {
+        panic!("Operation is missing owner approvals");
+    }
+    storage::remove_instance(e, &GovernanceKey::OwnerApprovals(id.clone()));
+    let mut pending = get_pending(e);
+    if let Some(index) = pending.first_index_of(&id) {
+        pending.remove(index);
+        storage::set_instance(e, &GovernanceKey::PendingOperations, &pending);
+    }
+    event::operation_executed(e, id);
+}
+
+// operations with approvals that haven't been carried out yet
+fn get_pending(e: &Env) -> Vec<BytesN<32>> {
+    storage::get_instance(e, &GovernanceKey::PendingOperations).unwrap_or(Vec::new(e))
+}
+
+fn clear_approvals(e: &Env) {
+    for id in get_pending(e).iter() {
+        storage::remove_instance(e, &GovernanceKey::OwnerApprovals(id));
+    }
+    storage::remove_instance(e, &GovernanceKey::PendingOperations);
+}
@@ -0,0 +1,68 @@
+use soroban_sdk::{contractclient, contracttype, Address, Env, Symbol};
+
+use crate::{storage, AccountingCurrency, DataKey, PoolParams};
+
+// asset quoted by the oracle, as in the Reflector oracle interface
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum Asset {
+    Stellar(Address),
+    Other(Symbol),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PriceData {
+    // price of one token unit in the oracle base currency, scaled by 10^decimals
+    pub price: i128,
+    pub timestamp: u64,
+}
+
+// Interface of the price feed used to convert reference amounts into the settlement token
+#[contractclient(name = "PriceOracleClient")]
+pub trait PriceOracleInterface {
+    fn decimals(e: Env) -> u32;
+    fn lastprice(e: Env, asset: Asset) -> Option<PriceData>;
+}
+
+pub fn get_accounting_currency(e: &Env) -> Option<AccountingCurrency> {
+    storage::get_instance(e, &DataKey::AccountingCurrency)
+}
+
+pub fn set_accounting_currency(e: &Env, currency: Option<AccountingCurrency>) {
+    match currency {
+        Some(currency) => storage::set_instance(e, &DataKey::AccountingCurrency, &currency),
+        None => storage::remove_instance(e, &DataKey::AccountingCurrency),
+    }
+}
+
+// due of an iteration in the settlement token, converted at the current price
+// when the pool accounts in a reference currency
+pub fn iteration_due(e: &Env, pool_params: &PoolParams) -> u32 {
+    let currency = match get_accounting_currency(e) {
+        Some(currency) => currency,
+        None => return pool_params.sub_amount,
+    };
+    let client = PriceOracleClient::new(e, &currency.oracle);
+    let price_data = match client.lastprice(&Asset::Stellar(pool_params.token.clone())) {
+        Some(price_data) => price_data,
+        None => panic!("Oracle has no price for the pool token"),
+    };
+    //a feed that stopped updating would fix the dues at an outdated price
+    if e.ledger().timestamp().saturating_sub(price_data.timestamp) > currency.max_price_age_secs {
+        panic!("Oracle price is stale");
+    }
+    let price = price_data.price;
+    if price <= 0 {
+        panic!("Oracle price must be positive");
+    }
+    let scale = 10_i128.pow(client.decimals());
+    let due = i128::from(currency.reference_amount) * scale / price;
+    if due == 0 {
+        panic!("Due rounds down to zero");
+    }
+    match u32::try_from(due) {
+        Ok(due) => due,
+        Err(_) => panic!("Due is too large"),
+    }
+}
@@ -0,0 +1,67 @@
+use soroban_sdk::{symbol_short, Address, Env, Symbol, Vec};
+
+use crate::{storage, DataKey, PayerStats, BALANCE_BUMP_AMOUNT, BALANCE_BUMP_THREASHOLD};
+
+const RECORDED_DUES: Symbol = symbol_short!("RECORDED");
+
+// dues applied to the iterations so far, by the members or their payers and by the insurance fund
+pub fn get_recorded_dues(e: &Env) -> u32 {
+    storage::get_instance(e, &RECORDED_DUES).unwrap_or(0)
+}
+
+pub fn record_due(e: &Env, amount: u32) {
+    storage::set_instance(e, &RECORDED_DUES, &(get_recorded_dues(e) + amount));
+}
+
+pub fn get_payer_stats(e: &Env, subscriber: Address) -> Vec<PayerStats> {
+    e.storage()
+        .persistent()
+        .get(&DataKey::PayerStats(subscriber))
+        .unwrap_or(Vec::new(e))
+}
+
+// record that `payer` contributed `amount` to the due of `subscriber`
+pub fn record_payment(e: &Env, subscriber: Address, payer: Address, iteration: u32, amount: u32) {
+    record_due(e, amount);
+    let mut stats = get_payer_stats(e, subscriber.clone());
+    let position = stats.iter().position(|entry| entry.payer == payer);
+    match position {
+        Some(index) => {
+            let mut entry = stats.get(index as u32).unwrap();
+            entry.payments += 1;
+            entry.total_amount += amount;
+            entry.last_iteration = iteration;
+            stats.set(index as u32, entry);
+        }
+        None => stats.push_back(PayerStats {
+            payer,
+            payments: 1,
+            total_amount: amount,
+            last_iteration: iteration,
+        }),
+    }
+
+    let key = DataKey::PayerStats(subscriber);
+    e.storage().persistent().set(&key, &stats);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_BUMP_THREASHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+// keep the contributions recorded for `from` under the address `to`
+pub fn move_payer_stats(e: &Env, from: Address, to: Address) {
+    let stats = get_payer_stats(e, from.clone());
+    if stats.is_empty() {
+        return;
+    }
+    e.storage().persistent().remove(&DataKey::PayerStats(from));
+    let key = DataKey::PayerStats(to);
+    e.storage().persistent().set(&key, &stats);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_BUMP_THREASHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+pub fn remove_payer_stats(e: &Env, subscriber: Address) {
+    e.storage().persistent().remove(&DataKey::PayerStats(subscriber));
+}
@@ -0,0 +1,44 @@
+use soroban_sdk::Env;
+
+use crate::{storage, DataKey, PenaltyConfig, PoolParams, Subscriber, PERCENTAGE_MULTIPLIER};
+
+// cap on penalties plus interest when the owner doesn't configure one
+pub const DEFAULT_MAX_PENALTY_BPS: u32 = 1_000; // 10%
+
+pub fn get_penalty_config(e: &Env) -> PenaltyConfig {
+    storage::get_instance(e, &DataKey::PenaltyConfig).unwrap_or(PenaltyConfig {
+            late_fee_bps: 0,
+            max_penalty_bps: DEFAULT_MAX_PENALTY_BPS,
+        })
+}
+
+pub fn set_penalty_config(e: &Env, config: &PenaltyConfig) {
+    storage::set_instance(e, &DataKey::PenaltyConfig, config);
+}
+
+// most a member can be charged in penalties plus interest over the cycle
+pub fn max_penalty(pool_params: &PoolParams, config: &PenaltyConfig) -> u32 {
+    let total_dues = u64::from(pool_params.sub_amount) * u64::from(pool_params.no_of_subs);
+    (total_dues * u64::from(config.max_penalty_bps) / u64::from(PERCENTAGE_MULTIPLIER)) as u32
+}
+
+// fee due on a late payment of `due`, before applying the member protection cap
+pub fn late_fee(e: &Env, due: u32) -> u32 {
+    let config = get_penalty_config(e);
+    (u64::from(due) * u64::from(config.late_fee_bps)
+        / u64::from(PERCENTAGE_MULTIPLIER)) as u32
+}
+
+// charge a penalty or interest to the subscriber, never going over the protection cap.
+// Returns the amount actually charged.
+pub fn charge(e: &Env, pool_params: &PoolParams, subr: &mut Subscriber, amount: u32) -> u32 {
+    let charged = capped(e, pool_params, subr, amount);
+    subr.penalties_paid += charged;
+    charged
+}
+
+// part of `amount` that can still be charged to the subscriber under the protection cap
+pub fn capped(e: &Env, pool_params: &PoolParams, subr: &Subscriber, amount: u32) -> u32 {
+    let cap = max_penalty(pool_params, &get_penalty_config(e)) * subr.shares;
+    amount.min(cap.saturating_sub(subr.penalties_paid))
+}
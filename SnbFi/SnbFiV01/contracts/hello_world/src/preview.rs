@@ -0,0 +1,72 @@
+use soroban_sdk::{Address, Env};
+
+use crate::{
+    commission, discount, insurance, is_on_time, loan, oracle, penalty, prize_pot, reserve, shares, sponsorship, treasury,
+    JoinPreview, PaymentPreview, PayoutPreview, PoolParams, State, Subscriber,
+};
+
+// what paying the rest of the due of `iteration` charges `subscriber`, and how it is covered
+pub fn payment(e: &Env, state: &State, subscriber: Address, subr: &Subscriber, iteration: u32) -> PaymentPreview {
+    let pool_iteration = state.pool_iteration_map.get(iteration).unwrap();
+    let partial = if subr.partial_iter == iteration { subr.partial_amount } else { 0 };
+    let full_due = shares::scaled(subr, pool_iteration.due_amount);
+    let due = full_due - partial;
+    let late = !is_on_time(e, &state.pool_params, &pool_iteration);
+    let late_fee = if late {
+        penalty::capped(e, &state.pool_params, subr, penalty::late_fee(e, full_due))
+    } else {
+        0
+    };
+    let premium = match insurance::get_seat_insurance(e, subscriber.clone()) {
+        Some(_) => insurance::get_premium(e),
+        None => 0,
+    };
+    let loan_interest = loan::interest_on(e, subr, full_due);
+    let discount = discount::discount_on(e, &state.pool_params, &pool_iteration, full_due).min(due);
+    //the sponsorship goes first then the credit of the subscriber
+    let charge = due + late_fee + premium + loan_interest - discount;
+    let sponsored = sponsorship::get_balance(e, subscriber).min(charge);
+    let from_credit = subr.credit_balance.min(charge - sponsored);
+    PaymentPreview {
+        iteration,
+        due,
+        late,
+        late_fee,
+        premium,
+        loan_interest,
+        discount,
+        sponsored,
+        from_credit,
+        transfer: charge - sponsored - from_credit,
+    }
+}
+
+// what joining the pool takes from a new member
+pub fn join(e: &Env, pool_params: &PoolParams, members: u32) -> JoinPreview {
+    JoinPreview {
+        collateral: pool_params.collateral_amount,
+        due: oracle::iteration_due(e, pool_params),
+        seats_left: pool_params.no_of_subs - members,
+    }
+}
+
+// how the pot of `iteration` splits if its winner were picked now
+pub fn payout(e: &Env, state: &State, iteration: u32) -> PayoutPreview {
+    let mut pool_iteration = match state.pool_iteration_map.get(iteration) {
+        Some(pool_iteration) => pool_iteration,
+        None => panic!("Iteration has not been started"),
+    };
+    reserve::fill(e, state, &mut pool_iteration);
+    let collected = pool_iteration.amount_collected;
+    PayoutPreview {
+        iteration,
+        collected,
+        commission: commission::commission_on(e, collected),
+        protocol_fee: treasury::fee_on(e, collected),
+        carried_in: pool_iteration.carried_in,
+        discounts: pool_iteration.discounts,
+        reserve_cut: pool_iteration.reserve_cut,
+        reserve_backfill: pool_iteration.reserve_backfill,
+        pot: prize_pot(e, &pool_iteration),
+    }
+}
@@ -0,0 +1,28 @@
+use soroban_sdk::{Address, Bytes, Env};
+
+use crate::{storage, DataKey, BALANCE_BUMP_AMOUNT, BALANCE_BUMP_THREASHOLD};
+
+pub fn is_privacy_mode(e: &Env) -> bool {
+    storage::get_instance(e, &DataKey::PrivacyMode).unwrap_or(false)
+}
+
+pub fn set_privacy_mode(e: &Env, enabled: bool) {
+    storage::set_instance(e, &DataKey::PrivacyMode, &enabled);
+}
+
+// keep the note encrypted by the subscriber until their prize is claimed
+pub fn set_winner_note(e: &Env, subscriber: Address, note: &Bytes) {
+    let key = DataKey::WinnerNote(subscriber);
+    e.storage().persistent().set(&key, note);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_BUMP_THREASHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+// the note is handed out once, in the claim event of its owner
+pub fn take_winner_note(e: &Env, subscriber: Address) -> Option<Bytes> {
+    let key = DataKey::WinnerNote(subscriber);
+    let note = e.storage().persistent().get(&key);
+    e.storage().persistent().remove(&key);
+    note
+}
@@ -0,0 +1,53 @@
+use soroban_sdk::{symbol_short, Address, Bytes, BytesN, Env, String, Symbol};
+
+use crate::{event, Profile, BALANCE_BUMP_AMOUNT, BALANCE_BUMP_THREASHOLD};
+
+// longest display name accepted, in bytes
+pub const MAX_DISPLAY_NAME_LEN: u32 = 32;
+
+const PROFILE: Symbol = symbol_short!("PROFILE");
+
+pub fn get_profile(e: &Env, subscriber: Address) -> Option<Profile> {
+    e.storage().persistent().get(&(PROFILE, subscriber))
+}
+
+// the contact is only kept as a hash, members compare it with the one they were given off-chain
+pub fn set_profile(e: &Env, subscriber: Address, display_name: String, contact_hash: Option<BytesN<32>>) {
+    if display_name.is_empty() || display_name.len() > MAX_DISPLAY_NAME_LEN {
+        panic!("Display name must be between 1 and 32 bytes");
+    }
+    let key = (PROFILE, subscriber.clone());
+    let contact_hash = contact_hash.map_or(Bytes::new(e), Bytes::from);
+    let profile = Profile { display_name, contact_hash };
+    e.storage().persistent().set(&key, &profile);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_BUMP_THREASHOLD, BALANCE_BUMP_AMOUNT);
+    event::profile_set(e, subscriber, profile.display_name);
+}
+
+// drop the profile of the subscriber, e.g. when the owner moderates an abusive name
+pub fn clear_profile(e: &Env, subscriber: Address) {
+    if get_profile(e, subscriber.clone()).is_none() {
+        panic!("Subscriber has no profile");
+    }
+    e.storage().persistent().remove(&(PROFILE, subscriber.clone()));
+    event::profile_cleared(e, subscriber);
+}
+
+// the profile follows the seat to the new address of the subscriber
+pub fn move_profile(e: &Env, from: Address, to: Address) {
+    if let Some(profile) = get_profile(e, from.clone()) {
+        e.storage().persistent().remove(&(PROFILE, from));
+        let key = (PROFILE, to);
+        e.storage().persistent().set(&key, &profile);
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, BALANCE_BUMP_THREASHOLD, BALANCE_BUMP_AMOUNT);
+    }
+}
+
+// drop the profile of a subscriber handing their seat to someone else, it names them and not the new holder
+pub fn remove_profile(e: &Env, subscriber: Address) {
+    e.storage().persistent().remove(&(PROFILE, subscriber));
+}
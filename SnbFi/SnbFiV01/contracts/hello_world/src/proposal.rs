@@ -0,0 +1,86 @@
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::{event, storage, GovernanceKey, PoolTerms, State, TermsProposal};
+
+pub fn get_proposal(e: &Env) -> Option<TermsProposal> {
+    storage::get_instance(e, &GovernanceKey::TermsProposal)
+}
+
+fn set_proposal(e: &Env, proposal: &TermsProposal) {
+    storage::set_instance(e, &GovernanceKey::TermsProposal, proposal);
+}
+
+pub fn clear_proposal(e: &Env) {
+    storage::remove_instance(e, &GovernanceKey::TermsProposal);
+}
+
+// members who already committed to the terms, the owner proposing the change has no vote
+fn electorate(e: &Env, state: &State) -> Vec<Address> {
+    let mut voters = storage::get_member_addresses(e);
+    if let Some(index) = voters.first_index_of(&state.pool_params.pool_owner) {
+        voters.remove(index);
+    }
+    voters
+}
+
+// put new terms to the members. Returns the id of the proposal, and the terms to apply right away when
+// no member other than the owner has joined yet
+pub fn propose(e: &Env, state: &State, terms: PoolTerms) -> (u32, Option<PoolTerms>) {
+    if get_proposal(e).is_some() {
+        panic!("A proposal is already open");
+    }
+    let id: u32 = storage::get_instance(e, &GovernanceKey::TermsProposalCount).unwrap_or(0);
+    storage::set_instance(e, &GovernanceKey::TermsProposalCount, &(id + 1));
+    let proposal = TermsProposal {
+        id,
+        terms,
+        approvals: Vec::new(e),
+        rejections: Vec::new(e),
+        proposed_at: e.ledger().timestamp(),
+    };
+    event::terms_proposed(e, id, proposal.terms.clone());
+    if electorate(e, state).is_empty() {
+        event::terms_decided(e, id, true);
+        return (id, Some(proposal.terms));
+    }
+    set_proposal(e, &proposal);
+    (id, None)
+}
+
+// count the vote of `voter` on the open proposal.
+// Returns the terms to apply once a majority of the members approved them
+pub fn vote(e: &Env, state: &State, voter: Address, id: u32, approve: bool) -> Option<PoolTerms> {
+    let mut proposal = match get_proposal(e) {
+        Some(proposal) if proposal.id == id => proposal,
+        _ => panic!("Proposal is not open"),
+    };
+    let voters = electorate(e, state);
+    if !voters.contains(&voter) {
+        panic!("Subscriber can't vote on the proposal");
+    }
+    if proposal.approvals.contains(&voter) || proposal.rejections.contains(&voter) {
+        panic!("Subscriber has already voted");
+    }
+    if approve {
+        proposal.approvals.push_back(voter.clone());
+    } else {
+        proposal.rejections.push_back(voter.clone());
+    }
+    event::terms_vote(e, id, voter, approve);
+    //members who left since they voted no longer count
+    let count = |votes: &Vec<Address>| votes.iter().filter(|voter| voters.contains(voter)).count() as u32;
+    let members = voters.len();
+    if count(&proposal.approvals) * 2 > members {
+        clear_proposal(e);
+        event::terms_decided(e, id, true);
+        return Some(proposal.terms);
+    }
+    //a majority can't be reached anymore
+    if count(&proposal.rejections) * 2 >= members {
+        clear_proposal(e);
+        event::terms_decided(e, id, false);
+        return None;
+    }
+    set_proposal(e, &proposal);
+    None
+}
@@ -0,0 +1,104 @@
+use soroban_sdk::{symbol_short, token, Env, Symbol, Vec};
+
+use crate::{
+    arrears, collateral, commission, emergency, fair_launch, reserve, settlement, sponsorship, treasury, waitlist, yield_adapter, BucketBalance, FeeBalances,
+    ReconciliationReport, State,
+};
+
+// tokens the pool accounts for, bucket by bucket, against what the contract actually holds
+pub fn report(e: &Env, state: &State) -> ReconciliationReport {
+    //early payment discounts were never transferred, they come out of the dividend
+    let collected: u32 = state
+        .pool_iteration_map
+        .values()
+        .iter()
+        .map(|pool_iteration| {
+            pool_iteration.amount_collected - pool_iteration.discounts + pool_iteration.yield_earned
+                - pool_iteration.reserve_cut + pool_iteration.reserve_backfill
+        })
+        .sum();
+    //dues covered by the insurance fund are counted in the collected dues, fees leave the pot once charged
+    let fees = fee_balances(e, state);
+    //the reserve released at completion is owed to the members as dividends, missed dues paid back join the next pot
+    let pot = i128::from(collected) + i128::from(reserve::get_released(e)) + i128::from(arrears::get_recovered(e))
+        - i128::from(state.paid_out)
+        - i128::from(fees.commission_charged)
+        - i128::from(fees.protocol_fees_charged)
+        //the part lent out through the yield adapter isn't held by the contract until it comes back
+        - yield_adapter::get_idle_deposit(e).map_or(0, |deposit| i128::from(deposit.amount))
+        //emergency withdrawals aren't earmarked, they come out of the pot
+        - emergency::get_withdrawn(e);
+
+    //tokens sent over the dues and sponsorships are held for the members until used
+    let credits: i128 = state
+        .subcriber_map
+        .values()
+        .iter()
+        .map(|subr| i128::from(subr.credit_balance))
+        .sum();
+    let sponsored: i128 = state
+        .subcriber_map
+        .keys()
+        .iter()
+        .map(|subscriber| i128::from(sponsorship::get_balance(e, subscriber)))
+        .sum();
+
+    let mut buckets = Vec::new(e);
+    add_bucket(&mut buckets, symbol_short!("pot"), pot);
+    add_bucket(&mut buckets, symbol_short!("penalties"), i128::from(state.penalties_collected));
+    add_bucket(&mut buckets, symbol_short!("insurance"), i128::from(state.insurance_fund));
+    add_bucket(&mut buckets, symbol_short!("reserve"), i128::from(reserve::get_balance(e)));
+    add_bucket(&mut buckets, symbol_short!("collatrl"), held_collateral(e, state));
+    add_bucket(&mut buckets, symbol_short!("seized"), state.seized_collateral);
+    add_bucket(&mut buckets, symbol_short!("credit"), credits);
+    add_bucket(&mut buckets, symbol_short!("sponsored"), sponsored);
+    add_bucket(&mut buckets, symbol_short!("comm"), i128::from(fees.commission_unclaimed));
+    add_bucket(&mut buckets, symbol_short!("treasury"), i128::from(fees.protocol_fees_unwithdrawn));
+    //dues paid in other tokens are accounted for in the pool token but only held once converted at payout
+    add_bucket(&mut buckets, symbol_short!("unsettled"), -i128::from(settlement::total_pending(e)));
+
+    let expected_balance: i128 = buckets.iter().map(|bucket| bucket.amount).sum();
+    let actual_balance =
+        token::Client::new(e, &state.pool_params.token).balance(&e.current_contract_address());
+    ReconciliationReport {
+        token: state.pool_params.token.clone(),
+        buckets,
+        expected_balance,
+        actual_balance,
+        discrepancy: actual_balance - expected_balance,
+    }
+}
+
+// collateral is held for members as well as for applicants waiting for a seat
+pub fn held_collateral(e: &Env, state: &State) -> i128 {
+    let mut holders = state.subcriber_map.keys();
+    holders.append(&waitlist::get_waitlist(e));
+    if let Some(launch) = fair_launch::get_fair_launch(e) {
+        holders.append(&launch.registrations);
+    }
+    holders
+        .iter()
+        .map(|holder| collateral::get_collateral(e, holder))
+        .sum()
+}
+
+// fees charged on every pot so far, kept apart from the dues collected for the prizes
+pub fn fee_balances(e: &Env, state: &State) -> FeeBalances {
+    let mut commission_charged = 0;
+    let mut protocol_fees_charged = 0;
+    for pool_iteration in state.pool_iteration_map.values().iter() {
+        commission_charged += pool_iteration.commission;
+        protocol_fees_charged += pool_iteration.protocol_fee;
+    }
+    FeeBalances {
+        commission_charged,
+        commission_unclaimed: commission::get_commission_balance(e),
+        protocol_fees_charged,
+        protocol_fees_unwithdrawn: treasury::get_treasury_balance(e),
+        penalties_collected: state.penalties_collected,
+    }
+}
+
+fn add_bucket(buckets: &mut Vec<BucketBalance>, name: Symbol, amount: i128) {
+    buckets.push_back(BucketBalance { name, amount });
+}
@@ -0,0 +1,43 @@
+use soroban_sdk::{symbol_short, Address, Env, Symbol};
+
+use crate::{storage, Referral, BALANCE_BUMP_AMOUNT, BALANCE_BUMP_THREASHOLD};
+
+const REWARD: Symbol = symbol_short!("REFREWARD");
+const REFERRAL: Symbol = symbol_short!("REFERRAL");
+
+// reward paid to the referrer of a member who completes the pool without defaulting, 0 when there is none
+pub fn get_reward(e: &Env) -> u32 {
+    storage::get_instance(e, &REWARD).unwrap_or(0)
+}
+
+pub fn set_reward(e: &Env, reward: u32) {
+    storage::set_instance(e, &REWARD, &reward);
+}
+
+pub fn get_referral(e: &Env, referred: Address) -> Option<Referral> {
+    e.storage().persistent().get(&(REFERRAL, referred))
+}
+
+pub fn set_referral(e: &Env, referred: Address, referral: &Referral) {
+    let key = (REFERRAL, referred);
+    e.storage().persistent().set(&key, referral);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_BUMP_THREASHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+// record that `referrer` brought `referred` in, at the reward offered when they joined
+pub fn record(e: &Env, referred: Address, referrer: Address) {
+    if referrer == referred {
+        panic!("Subscriber can't refer themselves");
+    }
+    set_referral(e, referred, &Referral { referrer, reward: get_reward(e), paid: false });
+}
+
+// the referral of the seat follows it to its new address
+pub fn move_referral(e: &Env, from: Address, to: Address) {
+    if let Some(referral) = get_referral(e, from.clone()) {
+        e.storage().persistent().remove(&(REFERRAL, from));
+        set_referral(e, to, &referral);
+    }
+}
@@ -0,0 +1,49 @@
+use soroban_sdk::{symbol_short, Address, Env, Symbol, Vec};
+
+use crate::{event, is_on_time, storage, ReminderRound, State, DAY_IN_SECS};
+
+// most unpaid members listed by one reminder, so a call stays within the instruction budget
+pub const MAX_REMINDERS_PER_CALL: u32 = 25;
+
+const ROUND: Symbol = symbol_short!("REMINDER");
+
+pub fn get_round(e: &Env) -> Option<ReminderRound> {
+    storage::get_instance(e, &ROUND)
+}
+
+// list the next page of members who haven't paid the current iteration yet, once a day while its dues are on time.
+// The pages of a day carry on from each other until every unpaid member was listed
+pub fn emit(e: &Env, state: &State) -> Vec<Address> {
+    let iteration = state.current_iteration;
+    let pool_iteration = match state.pool_iteration_map.get(iteration) {
+        Some(pool_iteration) => pool_iteration,
+        None => panic!("Pool has not started"),
+    };
+    if !is_on_time(e, &state.pool_params, &pool_iteration) {
+        panic!("Dues of the iteration are already late");
+    }
+    let day = e.ledger().timestamp() / DAY_IN_SECS;
+    let start = match get_round(e) {
+        Some(round) if round.iteration == iteration && round.day == day => {
+            if round.done {
+                panic!("Reminders were already sent today");
+            }
+            round.next
+        }
+        _ => 0,
+    };
+    let mut unpaid = Vec::new(e);
+    for (address, subr) in state.subcriber_map.iter() {
+        if subr.last_paid_iter < iteration && !subr.defaulted {
+            unpaid.push_back(address);
+        }
+    }
+    if unpaid.is_empty() {
+        panic!("Every member has paid");
+    }
+    let end = unpaid.len().min(start + MAX_REMINDERS_PER_CALL);
+    let page = unpaid.slice(start..end);
+    storage::set_instance(e, &ROUND, &ReminderRound { iteration, day, next: end, done: end == unpaid.len() });
+    event::dues_reminder(e, iteration, page.clone());
+    page
+}
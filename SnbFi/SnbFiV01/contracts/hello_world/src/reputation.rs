@@ -0,0 +1,161 @@
+// the Reputation trait exposes camelCase entry points, which the generated client mirrors
+#![allow(non_snake_case)]
+
+use soroban_sdk::{contractclient, contractimpl, Address, Env, Symbol};
+
+use crate::{
+    multisig, storage, DataKey, HelloContract, HelloContractClient, Reputation, ReputationDecay, BALANCE_BUMP_AMOUNT,
+    BALANCE_BUMP_THREASHOLD,
+};
+
+// reputation earned for every due paid on time
+pub(crate) const ON_TIME_PAYMENT_REWARD: u32 = 1;
+// reputation lost for every due paid after the grace period
+pub(crate) const LATE_PAYMENT_PENALTY: u32 = 1;
+// reputation lost when the subscriber defaults
+pub(crate) const DEFAULT_PENALTY: u32 = 5;
+
+// shared ledger contract, lets several pools build up one reputation per subscriber
+#[contractclient(name = "ReputationLedgerClient")]
+pub trait ReputationLedgerInterface {
+    fn add_reputation(e: Env, pool: Address, subscriber: Address, amount: u32);
+    fn remove_reputation(e: Env, pool: Address, subscriber: Address, amount: u32);
+    fn get_reputation(e: Env, subscriber: Address) -> u32;
+}
+
+pub fn get_reputation_contract(e: &Env) -> Option<Address> {
+    storage::get_instance(e, &DataKey::ReputationContract)
+}
+
+pub fn set_reputation_contract(e: &Env, contract: Option<Address>) {
+    match contract {
+        Some(contract) => storage::set_instance(e, &DataKey::ReputationContract, &contract),
+        None => storage::remove_instance(e, &DataKey::ReputationContract),
+    }
+}
+
+// without a ledger contract the pool keeps reputation in its own storage
+pub fn get_reputation(e: &Env, subscriber: Address) -> u32 {
+    if let Some(contract) = get_reputation_contract(e) {
+        return ReputationLedgerClient::new(e, &contract).get_reputation(&subscriber);
+    }
+    e.storage()
+        .persistent()
+        .get(&DataKey::Reputation(subscriber))
+        .unwrap_or(0)
+}
+
+fn set_reputation(e: &Env, subscriber: Address, reputation: u32) {
+    let key = DataKey::Reputation(subscriber);
+    e.storage().persistent().set(&key, &reputation);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_BUMP_THREASHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+pub fn get_reputation_decay(e: &Env) -> Option<ReputationDecay> {
+    storage::get_instance(e, &DataKey::ReputationDecay)
+}
+
+pub fn set_reputation_decay(e: &Env, decay: Option<ReputationDecay>) {
+    match decay {
+        Some(decay) => storage::set_instance(e, &DataKey::ReputationDecay, &decay),
+        None => storage::remove_instance(e, &DataKey::ReputationDecay),
+    }
+}
+
+fn set_updated_at(e: &Env, subscriber: Address) {
+    let key = DataKey::ReputationUpdatedAt(subscriber);
+    e.storage().persistent().set(&key, &e.ledger().timestamp());
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_BUMP_THREASHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+// reputation lost to inactivity since the subscriber's reputation last changed
+fn pending_decay(e: &Env, subscriber: Address) -> u32 {
+    let decay = match get_reputation_decay(e) {
+        Some(decay) => decay,
+        None => return 0,
+    };
+    let updated_at: u64 = match e.storage().persistent().get(&DataKey::ReputationUpdatedAt(subscriber)) {
+        Some(updated_at) => updated_at,
+        None => return 0,
+    };
+    let periods = (e.ledger().timestamp() - updated_at) / decay.period_secs;
+    u32::try_from(periods)
+        .unwrap_or(u32::MAX)
+        .saturating_mul(decay.points_per_period)
+}
+
+// reputation once the decay accrued since the last update is taken off
+pub fn get_effective_reputation(e: &Env, subscriber: Address) -> u32 {
+    get_reputation(e, subscriber.clone()).saturating_sub(pending_decay(e, subscriber))
+}
+
+// write the accrued decay into the score and restart the clock, before changing the score
+fn settle_decay(e: &Env, subscriber: Address) {
+    let decayed = pending_decay(e, subscriber.clone());
+    if decayed > 0 {
+        remove(e, subscriber.clone(), decayed);
+    }
+    set_updated_at(e, subscriber);
+}
+
+pub fn increase(e: &Env, subscriber: Address, amount: u32) {
+    settle_decay(e, subscriber.clone());
+    add(e, subscriber, amount);
+}
+
+pub fn decrease(e: &Env, subscriber: Address, amount: u32) {
+    settle_decay(e, subscriber.clone());
+    remove(e, subscriber, amount);
+}
+
+fn add(e: &Env, subscriber: Address, amount: u32) {
+    if let Some(contract) = get_reputation_contract(e) {
+        ReputationLedgerClient::new(e, &contract).add_reputation(
+            &e.current_contract_address(),
+            &subscriber,
+            &amount,
+        );
+        return;
+    }
+    let reputation = get_reputation(e, subscriber.clone());
+    set_reputation(e, subscriber, reputation.saturating_add(amount));
+}
+
+fn remove(e: &Env, subscriber: Address, amount: u32) {
+    if let Some(contract) = get_reputation_contract(e) {
+        ReputationLedgerClient::new(e, &contract).remove_reputation(
+            &e.current_contract_address(),
+            &subscriber,
+            &amount,
+        );
+        return;
+    }
+    let reputation = get_reputation(e, subscriber.clone());
+    set_reputation(e, subscriber, reputation.saturating_sub(amount));
+}
+
+// carry the whole reputation of `from` over to `to`
+pub fn move_reputation(e: &Env, from: Address, to: Address) {
+    let reputation = get_effective_reputation(e, from.clone());
+    if reputation > 0 {
+        decrease(e, from, reputation);
+        increase(e, to, reputation);
+    }
+}
+
+#[contractimpl]
+impl Reputation for HelloContract {
+    //Award reputation to a subscriber, only the pool owner can do it
+    fn addReputation(e: Env, subscriber: Address, reputation: u32) {
+        multisig::require_owner(&e, &storage::get_pool_params(&e), (Symbol::new(&e, "addReputation"), subscriber.clone(), reputation));
+        increase(&e, subscriber, reputation);
+    }
+
+    fn getReputation(e: Env, subscriber: Address) -> u32 {
+        get_reputation(&e, subscriber)
+    }
+}
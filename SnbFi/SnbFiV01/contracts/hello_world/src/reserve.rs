@@ -0,0 +1,63 @@
+use soroban_sdk::{symbol_short, Env, Symbol};
+
+use crate::{event, shares, storage, PoolIterationParams, State, PERCENTAGE_MULTIPLIER};
+
+//most of every pot the reserve can take, 20%
+pub const MAX_RESERVE_BPS: u32 = 2_000;
+
+const RATE: Symbol = symbol_short!("RSRVRATE");
+const BALANCE: Symbol = symbol_short!("RESERVE");
+const RELEASED: Symbol = symbol_short!("RSRVREL");
+
+// part of every pot set aside for the reserve, in basis points of the dues collected
+pub fn get_rate(e: &Env) -> u32 {
+    storage::get_instance(e, &RATE).unwrap_or(0)
+}
+
+pub fn set_rate(e: &Env, rate_bps: u32) {
+    storage::set_instance(e, &RATE, &rate_bps);
+}
+
+pub fn get_balance(e: &Env) -> u32 {
+    storage::get_instance(e, &BALANCE).unwrap_or(0)
+}
+
+fn set_balance(e: &Env, balance: u32) {
+    storage::set_instance(e, &BALANCE, &balance);
+}
+
+// reserve handed to the members once the pool completed
+pub fn get_released(e: &Env) -> u32 {
+    storage::get_instance(e, &RELEASED).unwrap_or(0)
+}
+
+// set the cut of the pot going to the reserve, and the dues missing from it the reserve makes up for
+pub fn fill(e: &Env, state: &State, pool_iteration: &mut PoolIterationParams) {
+    let collected = pool_iteration.amount_collected;
+    pool_iteration.reserve_cut =
+        (u64::from(collected) * u64::from(get_rate(e)) / u64::from(PERCENTAGE_MULTIPLIER)) as u32;
+    let expected = pool_iteration.due_amount * shares::total_shares(state);
+    pool_iteration.reserve_backfill = get_balance(e).min(expected.saturating_sub(collected));
+}
+
+// move the cut of the pot into the reserve and the backfill out of it
+pub fn settle(e: &Env, pool_iteration: &PoolIterationParams) {
+    set_balance(e, get_balance(e) + pool_iteration.reserve_cut - pool_iteration.reserve_backfill);
+}
+
+// share what is left of the reserve equally between the members who didn't default, as dividends credited
+// when they are next read. The remainder of the division stays in the reserve. Returns the amount released
+pub fn release(e: &Env, state: &mut State) -> u32 {
+    let members = state.active_shares;
+    let balance = get_balance(e);
+    if members == 0 || balance < members {
+        return 0;
+    }
+    let share = balance / members;
+    state.dividend_index.released += share;
+    let released = share * members;
+    set_balance(e, balance - released);
+    storage::set_instance(e, &RELEASED, &(get_released(e) + released));
+    event::reserve_released(e, released, share);
+    released
+}
@@ -0,0 +1,101 @@
+use soroban_sdk::{symbol_short, token, vec, Address, Env, Symbol, Vec};
+
+use crate::swap::{get_swap_config, SwapRouterClient};
+use crate::{ledger, storage, AssetHolding, LedgerAccount, PoolParams, PERCENTAGE_MULTIPLIER};
+
+const DEFERRED: Symbol = symbol_short!("DEFERSETL");
+const HELD: Symbol = symbol_short!("HELD");
+const PENDING: Symbol = symbol_short!("PENDING");
+
+// dues paid in the other tokens are kept as they are and only converted into the pool token at payout
+pub fn is_deferred(e: &Env) -> bool {
+    storage::get_instance(e, &DEFERRED).unwrap_or(false)
+}
+
+pub fn set_deferred(e: &Env, deferred: bool) {
+    storage::set_instance(e, &DEFERRED, &deferred);
+}
+
+// tokens of `token` the pool holds
+fn get_held(e: &Env, token: Address) -> i128 {
+    storage::get_instance(e, &(HELD, token)).unwrap_or(0)
+}
+
+// pool tokens the held `token` still has to be converted into
+fn get_pending(e: &Env, token: Address) -> u32 {
+    storage::get_instance(e, &(PENDING, token)).unwrap_or(0)
+}
+
+fn set_holding(e: &Env, token: Address, held: i128, pending: u32) {
+    storage::set_instance(e, &(HELD, token.clone()), &held);
+    storage::set_instance(e, &(PENDING, token), &pending);
+}
+
+pub fn holdings(e: &Env) -> Vec<AssetHolding> {
+    let mut holdings = Vec::new(e);
+    if let Some(config) = get_swap_config(e) {
+        for token in config.tokens.iter() {
+            holdings.push_back(AssetHolding {
+                held: get_held(e, token.clone()),
+                pending: get_pending(e, token.clone()),
+                token,
+            });
+        }
+    }
+    holdings
+}
+
+// pool tokens owed to the buckets by the contributions not converted yet
+pub fn total_pending(e: &Env) -> u32 {
+    holdings(e).iter().map(|holding| holding.pending).sum()
+}
+
+// take the value of `amount` pool tokens from `payer` in `token`, at the router quote plus the slippage the pool
+// accepts so the conversion at payout is covered. Returns the amount of `token` taken
+pub fn hold(e: &Env, payer: Address, token: Address, amount: u32, pool_params: &PoolParams) -> i128 {
+    let config = match get_swap_config(e) {
+        Some(config) => config,
+        None => panic!("Pool doesn't accept other tokens"),
+    };
+    if !config.tokens.contains(&token) {
+        panic!("Token is not accepted by the pool");
+    }
+    let path = vec![e, token.clone(), pool_params.token.clone()];
+    let quoted_in = SwapRouterClient::new(e, &config.router)
+        .router_get_amounts_in(&i128::from(amount), &path)
+        .get(0)
+        .unwrap();
+    let taken = quoted_in + quoted_in * i128::from(config.max_slippage_bps) / i128::from(PERCENTAGE_MULTIPLIER);
+    token::Client::new(e, &token).transfer(&payer, &e.current_contract_address(), &taken);
+    set_holding(e, token.clone(), get_held(e, token.clone()) + taken, get_pending(e, token) + amount);
+    taken
+}
+
+// buy the pool tokens the held contributions are worth with them. What is left of a token once its value
+// is in stays held for the next conversions. Returns the pool tokens bought
+pub fn settle(e: &Env, pool_params: &PoolParams) -> u32 {
+    let config = match get_swap_config(e) {
+        Some(config) if is_deferred(e) => config,
+        _ => return 0,
+    };
+    let router = SwapRouterClient::new(e, &config.router);
+    let mut settled = 0;
+    for token in config.tokens.iter() {
+        let pending = get_pending(e, token.clone());
+        if pending == 0 {
+            continue;
+        }
+        let held = get_held(e, token.clone());
+        let amounts = router.swap_tokens_for_exact_tokens(
+            &i128::from(pending),
+            &held,
+            &vec![e, token.clone(), pool_params.token.clone()],
+            &e.current_contract_address(),
+            &e.ledger().timestamp(),
+        );
+        set_holding(e, token, held - amounts.get(0).unwrap(), 0);
+        settled += pending;
+        ledger::post(e, LedgerAccount::Cash, LedgerAccount::Unsettled, i128::from(pending));
+    }
+    settled
+}
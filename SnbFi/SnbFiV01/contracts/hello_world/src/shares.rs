@@ -0,0 +1,57 @@
+use crate::{PoolState, State, Subscriber};
+
+// most shares a single member can commit to
+pub const MAX_SHARES: u32 = 10;
+
+// what the subscriber owes or is owed when one share comes to `per_share`
+pub fn scaled(subr: &Subscriber, per_share: u32) -> u32 {
+    per_share * subr.shares
+}
+
+// shares held by every member, defaulted or not
+pub fn total_shares(state: &State) -> u32 {
+    state.active_shares + state.defaulted_shares.values().iter().sum::<u32>()
+}
+
+// shares that owe the due of `iteration`: the members who haven't defaulted and the defaulted ones who paid it
+pub fn owing_shares(state: &State, iteration: u32) -> u32 {
+    let paid: u32 = state
+        .defaulted_shares
+        .iter()
+        .filter(|(last_paid_iter, _)| *last_paid_iter >= iteration)
+        .map(|(_, shares)| shares)
+        .sum();
+    state.active_shares + paid
+}
+
+// add a member entry to the counts of the pool
+pub fn count(pool: &mut PoolState, subr: &Subscriber) {
+    if subr.defaulted {
+        let shares = pool.defaulted_shares.get(subr.last_paid_iter).unwrap_or(0);
+        pool.defaulted_shares.set(subr.last_paid_iter, shares + subr.shares);
+        return;
+    }
+    pool.active_shares += subr.shares;
+    pool.contributions += u64::from(subr.total_paid);
+    if subr.winner_at_iter == 0 {
+        pool.eligible_shares += subr.shares;
+    }
+}
+
+// take a member entry out of the counts of the pool, before it changes or leaves
+pub fn uncount(pool: &mut PoolState, subr: &Subscriber) {
+    if subr.defaulted {
+        let shares = pool.defaulted_shares.get(subr.last_paid_iter).unwrap_or(0) - subr.shares;
+        if shares == 0 {
+            pool.defaulted_shares.remove(subr.last_paid_iter);
+        } else {
+            pool.defaulted_shares.set(subr.last_paid_iter, shares);
+        }
+        return;
+    }
+    pool.active_shares -= subr.shares;
+    pool.contributions -= u64::from(subr.total_paid);
+    if subr.winner_at_iter == 0 {
+        pool.eligible_shares -= subr.shares;
+    }
+}
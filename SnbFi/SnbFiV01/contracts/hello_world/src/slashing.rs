@@ -0,0 +1,88 @@
+use soroban_sdk::{Address, Env};
+
+use crate::{event, insurance, ledger, payer, shares, storage, LedgerAccount, SlashSettlement, State};
+
+// share out the tokens seized from a defaulted subscriber: their due in the current pot first, then what the
+// insurance fund paid for them, then equal shares of credit for the members in good standing.
+// The remainder of the division is kept with the seized collateral
+pub fn settle(e: &Env, state: &mut State, subscriber: Address, seized: i128) -> SlashSettlement {
+    let mut left = seized;
+    let to_pot = cover_current_due(e, state, subscriber.clone(), left);
+    left -= i128::from(to_pot);
+    let to_insurance = repay_insurance(e, state, subscriber.clone(), left);
+    left -= i128::from(to_insurance);
+
+    let members: u32 = state
+        .subcriber_map
+        .iter()
+        .filter(|(address, subr)| *address != subscriber && !subr.defaulted)
+        .count() as u32;
+    let mut to_members = 0;
+    if members > 0 {
+        let share = (left / i128::from(members)).min(i128::from(u32::MAX / members)) as u32;
+        for (address, mut subr) in state.subcriber_map.iter() {
+            if address != subscriber && !subr.defaulted {
+                subr.credit_balance += share;
+                state.subcriber_map.set(address, subr);
+            }
+        }
+        to_members = share * members;
+        left -= i128::from(to_members);
+    }
+    state.seized_collateral += left;
+    //what is kept stays with the collateral
+    ledger::post(e, LedgerAccount::Collateral, LedgerAccount::Pot, i128::from(to_pot));
+    ledger::post(e, LedgerAccount::Collateral, LedgerAccount::Insurance, i128::from(to_insurance));
+    ledger::post(e, LedgerAccount::Collateral, LedgerAccount::Members, i128::from(to_members));
+
+    let settlement = SlashSettlement { seized, to_pot, to_insurance, to_members, kept: left };
+    event::slashed(e, subscriber, settlement.clone());
+    settlement
+}
+
+// pay what is left of the due of the subscriber in the current iteration, while its winner isn't picked
+fn cover_current_due(e: &Env, state: &mut State, subscriber: Address, available: i128) -> u32 {
+    let iteration = state.current_iteration;
+    let mut pool_iteration = match state.pool_iteration_map.get(iteration) {
+        Some(pool_iteration) => pool_iteration,
+        None => return 0,
+    };
+    let mut subr = state.subcriber_map.get(subscriber.clone()).unwrap();
+    if subr.last_paid_iter >= iteration || state.subcriber_map.values().iter().any(|subr| subr.winner_at_iter == iteration) {
+        return 0;
+    }
+    let partial = if subr.partial_iter == iteration { subr.partial_amount } else { 0 };
+    let due = shares::scaled(&subr, pool_iteration.due_amount) - partial;
+    let covered = available.min(i128::from(due)) as u32;
+    if covered == 0 {
+        return 0;
+    }
+    if covered == due {
+        subr.last_paid_iter = iteration;
+        subr.partial_iter = 0;
+        subr.partial_amount = 0;
+    } else {
+        subr.partial_iter = iteration;
+        subr.partial_amount = partial + covered;
+    }
+    state.subcriber_map.set(subscriber, subr);
+    pool_iteration.amount_collected += covered;
+    state.pool_iteration_map.set(iteration, pool_iteration);
+    payer::record_due(e, covered);
+    covered
+}
+
+// pay back the missed due the insurance fund covered for the subscriber
+fn repay_insurance(e: &Env, state: &mut State, subscriber: Address, available: i128) -> u32 {
+    let mut seat_insurance = match insurance::get_seat_insurance(e, subscriber.clone()) {
+        Some(seat_insurance) if seat_insurance.claims > 0 => seat_insurance,
+        _ => return 0,
+    };
+    let subr = state.subcriber_map.get(subscriber.clone()).unwrap();
+    let claimed = shares::scaled(&subr, storage::get_iteration(e, seat_insurance.covered_iteration).unwrap().due_amount);
+    let repaid = available.min(i128::from(claimed - seat_insurance.repaid)) as u32;
+    seat_insurance.repaid += repaid;
+    insurance::set_seat_insurance(e, subscriber, &seat_insurance);
+    state.insurance_fund += repaid;
+    repaid
+}
@@ -0,0 +1,60 @@
+use soroban_sdk::{Env, Vec};
+
+use crate::{history, SnapshotEntry, SnapshotPage, SnapshotSection, State};
+
+// entries per page, low enough for a page to stay within the read limits of a view call
+pub const SNAPSHOT_PAGE_SIZE: u32 = 20;
+// bumped whenever the layout of the entries changes
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+// the page of `section` starting at the `cursor`-th entry
+pub fn export(e: &Env, state: &State, section: SnapshotSection, cursor: u32) -> SnapshotPage {
+    let (entries, total) = match section {
+        SnapshotSection::Subscribers => subscribers(e, state, cursor),
+        SnapshotSection::Iterations => iterations(e, state, cursor),
+        SnapshotSection::Payments => payments(e, state, cursor),
+    };
+    let next = cursor.saturating_add(entries.len());
+    SnapshotPage {
+        version: SNAPSHOT_VERSION,
+        entries,
+        next_cursor: if next < total { next } else { 0 },
+        total,
+    }
+}
+
+fn subscribers(e: &Env, state: &State, cursor: u32) -> (Vec<SnapshotEntry>, u32) {
+    let mut entries = Vec::new(e);
+    for (address, subscriber) in state.subcriber_map.iter().skip(cursor as usize).take(SNAPSHOT_PAGE_SIZE as usize) {
+        entries.push_back(SnapshotEntry::Subscriber(address, subscriber));
+    }
+    (entries, state.subcriber_map.len())
+}
+
+fn iterations(e: &Env, state: &State, cursor: u32) -> (Vec<SnapshotEntry>, u32) {
+    let mut entries = Vec::new(e);
+    for (iteration, params) in state.pool_iteration_map.iter().skip(cursor as usize).take(SNAPSHOT_PAGE_SIZE as usize) {
+        entries.push_back(SnapshotEntry::Iteration(iteration, params));
+    }
+    (entries, state.pool_iteration_map.len())
+}
+
+// payments of all the subscribers one after the other, in the order of the subscribers
+fn payments(e: &Env, state: &State, cursor: u32) -> (Vec<SnapshotEntry>, u32) {
+    let mut entries = Vec::new(e);
+    let mut total: u32 = 0;
+    for address in state.subcriber_map.keys() {
+        let count = history::get_payment_count(e, address.clone());
+        let first = total;
+        total += count;
+        if entries.len() >= SNAPSHOT_PAGE_SIZE || total <= cursor {
+            continue;
+        }
+        let start = cursor.saturating_sub(first);
+        let limit = SNAPSHOT_PAGE_SIZE - entries.len();
+        for (offset, record) in history::get_history(e, address.clone(), start, limit).iter().enumerate() {
+            entries.push_back(SnapshotEntry::Payment(address.clone(), start + offset as u32, record));
+        }
+    }
+    (entries, total)
+}
@@ -0,0 +1,88 @@
+use soroban_sdk::{token, Address, Env};
+
+use crate::{ledger, DataKey, LedgerAccount, PoolParams, Sponsorship, BALANCE_BUMP_AMOUNT, BALANCE_BUMP_THREASHOLD};
+
+pub fn get_sponsorship(e: &Env, subscriber: Address) -> Option<Sponsorship> {
+    e.storage()
+        .persistent()
+        .get(&DataKey::Sponsorship(subscriber))
+}
+
+fn set_sponsorship(e: &Env, subscriber: Address, sponsorship: &Sponsorship) {
+    let key = DataKey::Sponsorship(subscriber);
+    e.storage().persistent().set(&key, sponsorship);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_BUMP_THREASHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+// tokens the sponsor of the subscriber has left for their dues
+pub fn get_balance(e: &Env, subscriber: Address) -> u32 {
+    match get_sponsorship(e, subscriber) {
+        Some(sponsorship) => sponsorship.balance,
+        None => 0,
+    }
+}
+
+// move `amount` from the sponsor into the contract, earmarked for the dues of the subscriber
+pub fn deposit(e: &Env, pool_params: &PoolParams, sponsor: Address, subscriber: Address, amount: u32) {
+    let mut sponsorship = match get_sponsorship(e, subscriber.clone()) {
+        Some(sponsorship) => sponsorship,
+        None => Sponsorship {
+            sponsor: sponsor.clone(),
+            balance: 0,
+            drawn: 0,
+        },
+    };
+    //a subscriber has one sponsor at a time, another one can step in once the balance is used up
+    if sponsorship.sponsor != sponsor && sponsorship.balance > 0 {
+        panic!("Subscriber already has a sponsor");
+    }
+    sponsorship.sponsor = sponsor.clone();
+    sponsorship.balance += amount;
+    let token_client = token::Client::new(e, &pool_params.token);
+    token_client.transfer(&sponsor, &e.current_contract_address(), &i128::from(amount));
+    ledger::receive(e, LedgerAccount::Members, i128::from(amount));
+    set_sponsorship(e, subscriber, &sponsorship);
+}
+
+// take up to `amount` out of the sponsorship of the subscriber, returns what was drawn
+pub fn draw(e: &Env, subscriber: Address, amount: u32) -> u32 {
+    let mut sponsorship = match get_sponsorship(e, subscriber.clone()) {
+        Some(sponsorship) => sponsorship,
+        None => return 0,
+    };
+    let drawn = sponsorship.balance.min(amount);
+    if drawn == 0 {
+        return 0;
+    }
+    sponsorship.balance -= drawn;
+    sponsorship.drawn += drawn;
+    set_sponsorship(e, subscriber, &sponsorship);
+    drawn
+}
+
+// send what is left of the sponsorship of the subscriber back to the sponsor, returns the amount
+pub fn refund(e: &Env, pool_params: &PoolParams, subscriber: Address) -> u32 {
+    let mut sponsorship = match get_sponsorship(e, subscriber.clone()) {
+        Some(sponsorship) => sponsorship,
+        None => return 0,
+    };
+    let amount = sponsorship.balance;
+    if amount > 0 {
+        sponsorship.balance = 0;
+        set_sponsorship(e, subscriber, &sponsorship);
+        let token_client = token::Client::new(e, &pool_params.token);
+        token_client.transfer(&e.current_contract_address(), &sponsorship.sponsor, &i128::from(amount));
+        ledger::pay(e, LedgerAccount::Members, i128::from(amount));
+    }
+    amount
+}
+
+// keep the sponsorship of `from` under the address `to`
+pub fn move_sponsorship(e: &Env, from: Address, to: Address) {
+    if let Some(sponsorship) = get_sponsorship(e, from.clone()) {
+        e.storage().persistent().remove(&DataKey::Sponsorship(from));
+        set_sponsorship(e, to, &sponsorship);
+    }
+}
@@ -0,0 +1,44 @@
+use soroban_sdk::Env;
+
+use crate::{history, PoolStats, State, PERCENTAGE_MULTIPLIER};
+
+// totals over every iteration so far, computed from the state without writing anything
+pub fn pool_stats(e: &Env, state: &State) -> PoolStats {
+    let mut total_collected = 0;
+    let mut total_prizes = 0;
+    let mut total_dividends = 0;
+    for pool_iteration in state.pool_iteration_map.values().iter() {
+        total_collected += pool_iteration.amount_collected;
+        total_prizes += pool_iteration.prize_money;
+        total_dividends += pool_iteration.dividend_amount;
+    }
+
+    //every due paid counts, missed iterations count against the rate
+    let mut payments = 0;
+    let mut on_time = 0;
+    let mut missed = 0;
+    for (address, subr) in state.subcriber_map.iter() {
+        let count = history::get_payment_count(e, address) - subr.partial_payments;
+        payments += count;
+        on_time += count.saturating_sub(subr.late_payments);
+        missed += subr.missed_iterations;
+    }
+    let on_time_rate_bps = if payments + missed == 0 {
+        PERCENTAGE_MULTIPLIER
+    } else {
+        (u64::from(on_time) * u64::from(PERCENTAGE_MULTIPLIER) / u64::from(payments + missed)) as u32
+    };
+
+    PoolStats {
+        subscribers: state.subcriber_map.len(),
+        total_collected,
+        total_prizes,
+        total_dividends,
+        penalties_collected: state.penalties_collected,
+        on_time_rate_bps,
+        remaining_iterations: state
+            .pool_params
+            .no_of_subs
+            .saturating_sub(state.pool_iteration_map.len()),
+    }
+}
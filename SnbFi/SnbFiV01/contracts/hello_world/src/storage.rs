@@ -0,0 +1,327 @@
+use soroban_sdk::{symbol_short, xdr::ToXdr, Address, Env, IntoVal, Map, Symbol, TryFromVal, Val, Vec};
+
+use crate::{
+    dividend, shares, DividendIndex, PoolIterationParams, PoolParams, PoolState, PoolStatus, State, Subscriber,
+    BALANCE_BUMP_AMOUNT, BALANCE_BUMP_THREASHOLD,
+};
+
+// the pool without its members and iterations, which have entries of their own
+pub(crate) const STATE: Symbol = symbol_short!("STATE");
+// addresses of the members, kept in the instance entry next to the pool
+pub(crate) const MEMBERS: Symbol = symbol_short!("MEMBERS");
+pub(crate) const MEMBER: Symbol = symbol_short!("MEMBER");
+pub(crate) const ITERATION: Symbol = symbol_short!("ITERATION");
+
+// the settings and running totals of the pool live in the instance entry, which lives as long as the contract
+// is used: every read and write extends it
+pub fn get_instance<K, V>(e: &Env, key: &K) -> Option<V>
+where
+    K: IntoVal<Env, Val>,
+    V: TryFromVal<Env, Val>,
+{
+    extend_instance(e);
+    e.storage().instance().get(key)
+}
+
+pub fn has_instance<K>(e: &Env, key: &K) -> bool
+where
+    K: IntoVal<Env, Val>,
+{
+    extend_instance(e);
+    e.storage().instance().has(key)
+}
+
+pub fn set_instance<K, V>(e: &Env, key: &K, value: &V)
+where
+    K: IntoVal<Env, Val>,
+    V: IntoVal<Env, Val>,
+{
+    e.storage().instance().set(key, value);
+    extend_instance(e);
+}
+
+pub fn remove_instance<K>(e: &Env, key: &K)
+where
+    K: IntoVal<Env, Val>,
+{
+    e.storage().instance().remove(key);
+    extend_instance(e);
+}
+
+fn extend_instance(e: &Env) {
+    e.storage()
+        .instance()
+        .extend_ttl(BALANCE_BUMP_THREASHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+pub fn get_pool_state(e: &Env) -> PoolState {
+    get_instance(e, &STATE).unwrap()
+}
+
+// the pool without its members and iterations, for the flows that only work on its parameters and status.
+// Saving it back rewrites the instance entry alone
+pub fn load_pool(e: &Env) -> State {
+    let pool = get_pool_state(e);
+    State {
+        pool_params: pool.pool_params,
+        current_iteration: pool.current_iteration,
+        subcriber_map: Map::new(e),
+        pool_iteration_map: Map::new(e),
+        shortfall: pool.shortfall,
+        seized_collateral: pool.seized_collateral,
+        penalties_collected: pool.penalties_collected,
+        status: pool.status,
+        insurance_fund: pool.insurance_fund,
+        paid_out: pool.paid_out,
+        started_at: pool.started_at,
+        active_shares: pool.active_shares,
+        eligible_shares: pool.eligible_shares,
+        contributions: pool.contributions,
+        defaulted_shares: pool.defaulted_shares,
+        dividend_index: pool.dividend_index,
+    }
+}
+
+pub fn get_pool_params(e: &Env) -> PoolParams {
+    get_pool_state(e).pool_params
+}
+
+pub fn get_current_iteration(e: &Env) -> u32 {
+    get_pool_state(e).current_iteration
+}
+
+pub fn get_member_addresses(e: &Env) -> Vec<Address> {
+    get_instance(e, &MEMBERS).unwrap_or(Vec::new(e))
+}
+
+// None when the address isn't a member. The dividends shared out since the entry was last read are credited
+pub fn get_member(e: &Env, member: Address) -> Option<Subscriber> {
+    read_member(e, member, &get_pool_state(e).dividend_index)
+}
+
+fn read_member(e: &Env, member: Address, index: &DividendIndex) -> Option<Subscriber> {
+    let mut subr: Subscriber = get_entry(e, (MEMBER, member))?;
+    dividend::settle(index, &mut subr);
+    Some(subr)
+}
+
+// None when the iteration hasn't started
+pub fn get_iteration(e: &Env, iteration: u32) -> Option<PoolIterationParams> {
+    get_entry(e, (ITERATION, iteration))
+}
+
+// keys of the member and iteration entries the pool can't work without. A client restores the archived ones,
+// along with the contract instance and code when those expired, before retrying
+pub fn restore_hints(e: &Env) -> Vec<Val> {
+    let mut keys = Vec::new(e);
+    if get_pool_state(e).status == PoolStatus::Archived {
+        return keys;
+    }
+    for address in get_member_addresses(e).iter() {
+        keys.push_back((MEMBER, address).into_val(e));
+    }
+    for iteration in 1..=get_current_iteration(e) {
+        keys.push_back((ITERATION, iteration).into_val(e));
+    }
+    keys
+}
+
+// the whole pool with every member and iteration, for the flows working over all of them
+pub fn load_state(e: &Env) -> State {
+    let mut state = load_pool(e);
+    //an archived pool only has its summary left
+    if state.status == PoolStatus::Archived {
+        return state;
+    }
+    for address in get_member_addresses(e).iter() {
+        let subr = read_member(e, address.clone(), &state.dividend_index).unwrap();
+        state.subcriber_map.set(address, subr);
+    }
+    for iteration in 1..=state.current_iteration {
+        state.pool_iteration_map.set(iteration, get_iteration(e, iteration).unwrap());
+    }
+    state
+}
+
+// the pool with every member but only `iterations` out of the iterations, for the flows counting or crediting
+// all the members. The other iterations are left out of the footprint
+pub fn load_members(e: &Env, iterations: impl IntoIterator<Item = u32>) -> State {
+    load_entries(e, get_member_addresses(e), iterations)
+}
+
+// the pool with only the entries of `members` and `iterations`, for the flows touching a few of them.
+// Addresses that aren't members and iterations that haven't started are left out
+pub fn load_entries(
+    e: &Env,
+    members: impl IntoIterator<Item = Address>,
+    iterations: impl IntoIterator<Item = u32>,
+) -> State {
+    let mut state = load_pool(e);
+    for address in members {
+        load_member(e, &mut state, address);
+    }
+    for iteration in iterations {
+        load_iteration(e, &mut state, iteration);
+    }
+    state
+}
+
+// add the entry of `member` to a pool loaded without it, once the flow knows which one it needs
+pub fn load_member(e: &Env, state: &mut State, member: Address) {
+    if let Some(subr) = read_member(e, member.clone(), &state.dividend_index) {
+        state.subcriber_map.set(member, subr);
+    }
+}
+
+// add the entry of `iteration` to a pool loaded without it, once the flow knows which one it needs
+pub fn load_iteration(e: &Env, state: &mut State, iteration: u32) {
+    if let Some(pool_iteration) = get_iteration(e, iteration) {
+        state.pool_iteration_map.set(iteration, pool_iteration);
+    }
+}
+
+fn pool_state(state: &State) -> PoolState {
+    PoolState {
+        pool_params: state.pool_params.clone(),
+        current_iteration: state.current_iteration,
+        shortfall: state.shortfall,
+        seized_collateral: state.seized_collateral,
+        penalties_collected: state.penalties_collected,
+        status: state.status.clone(),
+        insurance_fund: state.insurance_fund,
+        paid_out: state.paid_out,
+        started_at: state.started_at,
+        active_shares: state.active_shares,
+        eligible_shares: state.eligible_shares,
+        contributions: state.contributions,
+        defaulted_shares: state.defaulted_shares.clone(),
+        dividend_index: state.dividend_index.clone(),
+    }
+}
+
+// write the pool back, rewriting only the entries that changed since it was `loaded`. The comparison is made
+// in memory, the stored entries aren't read again. `loaded` can hold some of the members only, the ones left
+// out are kept in the index as they are. The share counts of the pool follow the members that changed
+pub fn save_state(e: &Env, loaded: &State, state: &State) {
+    let pool = saved_pool(loaded, state);
+    //a pool just set up has nothing stored yet
+    if pool != pool_state(loaded) || !has_instance(e, &STATE) {
+        set_instance(e, &STATE, &pool);
+    }
+
+    if loaded.subcriber_map.keys() != state.subcriber_map.keys() {
+        let mut members = get_member_addresses(e);
+        for address in loaded.subcriber_map.keys().iter() {
+            if !state.subcriber_map.contains_key(address.clone()) {
+                e.storage().persistent().remove(&(MEMBER, address.clone()));
+                if let Ok(index) = members.binary_search(&address) {
+                    members.remove(index);
+                }
+            }
+        }
+        for address in state.subcriber_map.keys().iter() {
+            if let Err(index) = members.binary_search(&address) {
+                members.insert(index, address);
+            }
+        }
+        if members.is_empty() {
+            remove_instance(e, &MEMBERS);
+        } else {
+            set_instance(e, &MEMBERS, &members);
+        }
+    }
+    //the entries of members who just joined and of the iteration just opened weren't loaded
+    for (address, subr) in state.subcriber_map.iter() {
+        if loaded.subcriber_map.get(address.clone()).as_ref() != Some(&subr) {
+            set_entry(e, (MEMBER, address), &subr);
+        }
+    }
+    for (iteration, pool_iteration) in state.pool_iteration_map.iter() {
+        if loaded.pool_iteration_map.get(iteration).as_ref() != Some(&pool_iteration) {
+            set_entry(e, (ITERATION, iteration), &pool_iteration);
+        }
+    }
+}
+
+// the instance entry as `state` is saved, its share counts following the members that changed since `loaded`
+fn saved_pool(loaded: &State, state: &State) -> PoolState {
+    let mut pool = pool_state(state);
+    for (address, before) in loaded.subcriber_map.iter() {
+        if state.subcriber_map.get(address).as_ref() != Some(&before) {
+            shares::uncount(&mut pool, &before);
+        }
+    }
+    for (address, after) in state.subcriber_map.iter() {
+        if loaded.subcriber_map.get(address).as_ref() != Some(&after) {
+            shares::count(&mut pool, &after);
+        }
+    }
+    pool
+}
+
+// ledger entries a flow reads when it works on `state`: the instance entry and the members and iterations loaded
+pub fn read_entries(state: &State) -> u32 {
+    1 + state.subcriber_map.len() + state.pool_iteration_map.len()
+}
+
+// ledger entries saving `state` rewrites, the same comparison save_state makes
+pub fn written_entries(loaded: &State, state: &State) -> u32 {
+    let mut written = u32::from(saved_pool(loaded, state) != pool_state(loaded));
+    for (address, subr) in state.subcriber_map.iter() {
+        if loaded.subcriber_map.get(address).as_ref() != Some(&subr) {
+            written += 1;
+        }
+    }
+    for (iteration, pool_iteration) in state.pool_iteration_map.iter() {
+        if loaded.pool_iteration_map.get(iteration).as_ref() != Some(&pool_iteration) {
+            written += 1;
+        }
+    }
+    written
+}
+
+// size in bytes of the largest of `entries` as it is stored, 0 when there is none
+pub fn largest_entry<V>(e: &Env, entries: Vec<V>) -> u32
+where
+    V: IntoVal<Env, Val> + TryFromVal<Env, Val> + Clone,
+{
+    entries.iter().map(|entry| entry.to_xdr(e).len()).max().unwrap_or(0)
+}
+
+// drop the member and iteration entries of the pool for good, along with the index of the members
+pub fn remove_entries(e: &Env, state: &State) {
+    for address in state.subcriber_map.keys().iter() {
+        e.storage().persistent().remove(&(MEMBER, address));
+    }
+    for iteration in state.pool_iteration_map.keys().iter() {
+        e.storage().persistent().remove(&(ITERATION, iteration));
+    }
+    remove_instance(e, &MEMBERS);
+}
+
+// members and iterations live as long as the pool is used, every read and write extends them
+fn get_entry<K, V>(e: &Env, key: K) -> Option<V>
+where
+    K: IntoVal<Env, Val>,
+    V: TryFromVal<Env, Val>,
+{
+    let key = key.into_val(e);
+    let value = e.storage().persistent().get(&key);
+    if value.is_some() {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, BALANCE_BUMP_THREASHOLD, BALANCE_BUMP_AMOUNT);
+    }
+    value
+}
+
+fn set_entry<K, V>(e: &Env, key: K, value: &V)
+where
+    K: IntoVal<Env, Val>,
+    V: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+{
+    e.storage().persistent().set(&key, value);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_BUMP_THREASHOLD, BALANCE_BUMP_AMOUNT);
+}
@@ -0,0 +1,53 @@
+use soroban_sdk::{contractclient, Address, Env, Vec};
+
+use crate::{event, storage, DataKey, FallbackPolicy, State, WinnerStrategy};
+
+// Interface a pluggable winner-strategy contract has to implement
+#[contractclient(name = "WinnerStrategyClient")]
+pub trait WinnerStrategyInterface {
+    // return the winner of `iteration`, which must be one of `candidates`
+    fn select_winner(e: Env, iteration: u32, candidates: Vec<Address>) -> Address;
+}
+
+pub fn get_winner_strategy(e: &Env) -> WinnerStrategy {
+    storage::get_instance(e, &DataKey::WinnerStrategy).unwrap_or(WinnerStrategy {
+            contract: None,
+            fallback: FallbackPolicy::RandomDraw,
+        })
+}
+
+pub fn set_winner_strategy(e: &Env, strategy: &WinnerStrategy) {
+    storage::set_instance(e, &DataKey::WinnerStrategy, strategy);
+}
+
+// ask the strategy contract for the winner, falling back according to the pool policy when it fails
+pub fn select_winner(e: &Env, state: &State, iteration: u32, candidates: &Vec<Address>) -> Address {
+    let strategy = get_winner_strategy(e);
+    if let Some(contract) = strategy.contract {
+        let client = WinnerStrategyClient::new(e, &contract);
+        if let Ok(Ok(winner)) = client.try_select_winner(&iteration, candidates) {
+            if candidates.contains(&winner) {
+                return winner;
+            }
+        }
+        match strategy.fallback {
+            FallbackPolicy::Halt => panic!("Winner strategy failed"),
+            FallbackPolicy::RandomDraw => event::strategy_degraded(e, contract, iteration),
+        }
+    }
+    random_draw(e, state, candidates)
+}
+
+// random draw among the candidates, each holding one ticket per share
+pub fn random_draw(e: &Env, state: &State, candidates: &Vec<Address>) -> Address {
+    let weight = |candidate: &Address| u64::from(state.subcriber_map.get(candidate.clone()).unwrap().shares);
+    let tickets: u64 = candidates.iter().map(|candidate| weight(&candidate)).sum();
+    let mut ticket: u64 = e.prng().gen_range(0..tickets);
+    for candidate in candidates.iter() {
+        if ticket < weight(&candidate) {
+            return candidate;
+        }
+        ticket -= weight(&candidate);
+    }
+    unreachable!()
+}
@@ -0,0 +1,99 @@
+use soroban_sdk::auth::{ContractExecutable, CreateContractHostFnContext, InvokerContractAuthEntry};
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{contractclient, symbol_short, vec, Address, BytesN, Env, IntoVal, Symbol, Val, Vec};
+
+use crate::{
+    access, collateral, event, reputation, storage, treasury, HelloContractClient, PoolConfig, PoolRecord, State,
+};
+
+const SUCCESSOR: Symbol = symbol_short!("SUCCESSOR");
+const PREDECESSOR: Symbol = symbol_short!("PREDECSR");
+
+// registry the pool was deployed from, the successor is deployed from the same wasm through it
+#[contractclient(name = "PoolFactoryClient")]
+pub trait PoolFactoryInterface {
+    fn deploy(e: Env, deployer: Address, wasm_hash: BytesN<32>, salt: BytesN<32>, init_args: Vec<Val>) -> Address;
+    fn get_pool(e: Env, pool: Address) -> Option<PoolRecord>;
+}
+
+pub fn get_successor(e: &Env) -> Option<Address> {
+    storage::get_instance(e, &SUCCESSOR)
+}
+
+pub fn get_predecessor(e: &Env) -> Option<Address> {
+    storage::get_instance(e, &PREDECESSOR)
+}
+
+pub fn set_predecessor(e: &Env, predecessor: &Address) {
+    storage::set_instance(e, &PREDECESSOR, predecessor);
+}
+
+// deploy a pool with the parameters of this one through the factory, and link it back to this pool.
+// The join deadline of this pool has passed so the successor has none
+pub fn create(e: &Env, state: &State, factory: Address) -> Address {
+    if get_successor(e).is_some() {
+        panic!("Pool already has a successor");
+    }
+    let factory_client = PoolFactoryClient::new(e, &factory);
+    let record = match factory_client.get_pool(&e.current_contract_address()) {
+        Some(record) => record,
+        None => panic!("Pool was not deployed by the factory"),
+    };
+    let params = &state.pool_params;
+    let config = PoolConfig {
+        name: params.name.clone(),
+        description: params.description.clone(),
+        no_of_subs: params.no_of_subs,
+        sub_amount: params.sub_amount,
+        frequency: params.frequency.clone(),
+        grace_period_secs: params.grace_period_secs,
+        token: params.token.clone(),
+        collateral_amount: params.collateral_amount,
+        join_deadline: None,
+        dividend_policy: params.dividend_policy.clone(),
+        attestation: access::get_attestation_contract(e),
+    };
+    let init_args: Vec<Val> = vec![
+        e,
+        params.pool_owner.into_val(e),
+        config.into_val(e),
+        treasury::get_protocol_fee(e).into_val(e),
+    ];
+    // one successor per pool, so the address of this pool is a unique salt
+    let salt: BytesN<32> = e.crypto().sha256(&e.current_contract_address().to_xdr(e)).to_bytes();
+    // the factory deploys on behalf of this pool
+    e.authorize_as_current_contract(vec![
+        e,
+        InvokerContractAuthEntry::CreateContractHostFn(CreateContractHostFnContext {
+            executable: ContractExecutable::Wasm(record.wasm_hash.clone()),
+            salt: salt.clone(),
+        }),
+    ]);
+    let successor = factory_client.deploy(&e.current_contract_address(), &record.wasm_hash, &salt, &init_args);
+    HelloContractClient::new(e, &successor).link_predecessor(
+        &e.current_contract_address(),
+        &reputation::get_reputation_contract(e),
+    );
+    storage::set_instance(e, &SUCCESSOR, &successor);
+    event::successor_created(e, successor.clone());
+    successor
+}
+
+// move a member in good standing over to the successor: their reputation, unless both pools share a ledger,
+// then their collateral, which goes back to them and is deposited again when they join
+pub fn opt_in(e: &Env, state: &State, member: Address) -> i128 {
+    let successor = match get_successor(e) {
+        Some(successor) => successor,
+        None => panic!("Pool has no successor"),
+    };
+    let successor_client = HelloContractClient::new(e, &successor);
+    if reputation::get_reputation_contract(e).is_none() {
+        let carried = reputation::get_effective_reputation(e, member.clone());
+        if carried > 0 {
+            successor_client.carry_over_reputation(&member, &carried);
+        }
+    }
+    let released = collateral::release_collateral(e, &state.pool_params, member.clone());
+    successor_client.join(&member, &None, &None);
+    released
+}
@@ -0,0 +1,58 @@
+use soroban_sdk::{contractclient, vec, Address, Env, Vec};
+
+use crate::{storage, DataKey, PoolParams, SwapConfig, PERCENTAGE_MULTIPLIER};
+
+// cap on the slippage a pool accepts when converting dues
+pub const MAX_SLIPPAGE_BPS: u32 = 500; // 5%
+
+// Interface of the Soroswap router used to convert dues paid in another token
+#[contractclient(name = "SwapRouterClient")]
+pub trait SwapRouterInterface {
+    fn router_get_amounts_in(e: Env, amount_out: i128, path: Vec<Address>) -> Vec<i128>;
+    fn swap_tokens_for_exact_tokens(
+        e: Env,
+        amount_out: i128,
+        amount_in_max: i128,
+        path: Vec<Address>,
+        to: Address,
+        deadline: u64,
+    ) -> Vec<i128>;
+}
+
+pub fn get_swap_config(e: &Env) -> Option<SwapConfig> {
+    storage::get_instance(e, &DataKey::SwapConfig)
+}
+
+pub fn set_swap_config(e: &Env, config: Option<SwapConfig>) {
+    match config {
+        Some(config) => storage::set_instance(e, &DataKey::SwapConfig, &config),
+        None => storage::remove_instance(e, &DataKey::SwapConfig),
+    }
+}
+
+// swap `token` held by `payer` into exactly `amount` of the pool token, left with the payer.
+// Returns the amount of `token` spent
+pub fn convert(e: &Env, pool_params: &PoolParams, payer: Address, token: Address, amount: u32) -> i128 {
+    let config = match get_swap_config(e) {
+        Some(config) => config,
+        None => panic!("Pool doesn't accept other tokens"),
+    };
+    if !config.tokens.contains(&token) {
+        panic!("Token is not accepted by the pool");
+    }
+    let router = SwapRouterClient::new(e, &config.router);
+    let path = vec![e, token, pool_params.token.clone()];
+    let amount_out = i128::from(amount);
+    //the quote is taken in the same transaction, the slippage bound protects against the price moving inside it
+    let quoted_in = router.router_get_amounts_in(&amount_out, &path).get(0).unwrap();
+    let amount_in_max = quoted_in
+        + quoted_in * i128::from(config.max_slippage_bps) / i128::from(PERCENTAGE_MULTIPLIER);
+    let amounts = router.swap_tokens_for_exact_tokens(
+        &amount_out,
+        &amount_in_max,
+        &path,
+        &payer,
+        &e.ledger().timestamp(),
+    );
+    amounts.get(0).unwrap()
+}
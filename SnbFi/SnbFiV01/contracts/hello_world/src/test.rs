@@ -1,17 +1,3932 @@
 #![cfg(test)]
+extern crate alloc;
 
 use super::*;
-use soroban_sdk::{symbol_short, vec, Env};
+use alloc::rc::Rc;
+use soroban_sdk::{
+    testutils::{storage::Instance as _, storage::Persistent as _, Address as _, EnvTestConfig, Events, Ledger},
+    token,
+    xdr::{self, WriteXdr},
+    Address, Bytes, Env, IntoVal, TryFromVal,
+};
+use token::Client as TokenClient;
+use token::StellarAssetClient as TokenAdminClient;
+
+// strategy always picking the last candidate
+mod last_candidate_strategy {
+    use soroban_sdk::{contract, contractimpl, Address, Env, Vec};
+
+    #[contract]
+    pub struct LastCandidateStrategy;
+
+    #[contractimpl]
+    impl LastCandidateStrategy {
+        pub fn select_winner(_e: Env, _iteration: u32, candidates: Vec<Address>) -> Address {
+            candidates.last().unwrap()
+        }
+    }
+}
+
+// oracle quoting a price set by the test, with 2 decimals
+mod fixed_price_oracle {
+    use crate::{Asset, PriceData};
+    use soroban_sdk::{contract, contractimpl, symbol_short, Env};
+
+    #[contract]
+    pub struct FixedPriceOracle;
+
+    #[contractimpl]
+    impl FixedPriceOracle {
+        pub fn set_price(e: Env, price: i128) {
+            e.storage().instance().set(&symbol_short!("PRICE"), &price);
+            e.storage().instance().set(&symbol_short!("UPDATED"), &e.ledger().timestamp());
+        }
+
+        pub fn decimals(_e: Env) -> u32 {
+            2
+        }
+
+        pub fn lastprice(e: Env, _asset: Asset) -> Option<PriceData> {
+            let price: i128 = e.storage().instance().get(&symbol_short!("PRICE"))?;
+            let timestamp: u64 = e.storage().instance().get(&symbol_short!("UPDATED"))?;
+            Some(PriceData { price, timestamp })
+        }
+    }
+}
+
+// attestation provider verifying the addresses the test lists
+mod listed_attestation {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    #[contract]
+    pub struct ListedAttestation;
+
+    #[contractimpl]
+    impl ListedAttestation {
+        pub fn verify(e: Env, user: Address) {
+            e.storage().persistent().set(&user, &true);
+        }
+
+        pub fn is_verified(e: Env, user: Address) -> bool {
+            e.storage().persistent().get(&user).unwrap_or(false)
+        }
+    }
+}
+
+// router swapping at 2 units of the input token per unit of the output, quoting 1% under what it charges
+mod fixed_rate_router {
+    use soroban_sdk::{contract, contractimpl, token, vec, Address, Env, Vec};
+
+    #[contract]
+    pub struct FixedRateRouter;
+
+    #[contractimpl]
+    impl FixedRateRouter {
+        pub fn router_get_amounts_in(e: Env, amount_out: i128, _path: Vec<Address>) -> Vec<i128> {
+            vec![&e, amount_out * 2, amount_out]
+        }
+
+        pub fn swap_tokens_for_exact_tokens(
+            e: Env,
+            amount_out: i128,
+            amount_in_max: i128,
+            path: Vec<Address>,
+            to: Address,
+            _deadline: u64,
+        ) -> Vec<i128> {
+            to.require_auth();
+            let amount_in = amount_out * 2 + amount_out * 2 / 100;
+            if amount_in > amount_in_max {
+                panic!("excessive input amount");
+            }
+            let router = e.current_contract_address();
+            token::Client::new(&e, &path.get(0).unwrap()).transfer(&to, &router, &amount_in);
+            token::Client::new(&e, &path.get(1).unwrap()).transfer(&router, &to, &amount_out);
+            vec![&e, amount_in, amount_out]
+        }
+    }
+}
+
+// adapter paying 10% on whatever it held, out of the tokens it was funded with
+mod flat_yield_adapter {
+    use soroban_sdk::{contract, contractimpl, symbol_short, token, Address, Env};
+
+    #[contract]
+    pub struct FlatYieldAdapter;
+
+    #[contractimpl]
+    impl FlatYieldAdapter {
+        pub fn init(e: Env, token: Address) {
+            e.storage().instance().set(&symbol_short!("TOKEN"), &token);
+        }
+
+        pub fn deposit(e: Env, _depositor: Address, amount: i128) {
+            let held: i128 = e.storage().instance().get(&symbol_short!("HELD")).unwrap_or(0);
+            e.storage().instance().set(&symbol_short!("HELD"), &(held + amount));
+        }
+
+        pub fn withdraw(e: Env, depositor: Address) -> i128 {
+            depositor.require_auth();
+            let held: i128 = e.storage().instance().get(&symbol_short!("HELD")).unwrap_or(0);
+            let token: Address = e.storage().instance().get(&symbol_short!("TOKEN")).unwrap();
+            let amount = held + held / 10;
+            token::Client::new(&e, &token).transfer(&e.current_contract_address(), &depositor, &amount);
+            e.storage().instance().set(&symbol_short!("HELD"), &0_i128);
+            amount
+        }
+    }
+}
+
+// strategy that always reverts
+mod broken_strategy {
+    use soroban_sdk::{contract, contractimpl, Address, Env, Vec};
+
+    #[contract]
+    pub struct BrokenStrategy;
+
+    #[contractimpl]
+    impl BrokenStrategy {
+        pub fn select_winner(_e: Env, _iteration: u32, _candidates: Vec<Address>) -> Address {
+            panic!("strategy is broken")
+        }
+    }
+}
+
+// factory standing in for the pool factory, it initializes a pool the test registered up front
+mod stub_factory {
+    use crate::PoolRecord;
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, BytesN, Env, Symbol, Val, Vec};
+
+    #[contract]
+    pub struct StubFactory;
+
+    #[contractimpl]
+    impl StubFactory {
+        pub fn register(e: Env, pool: Address) {
+            e.storage().persistent().set(&pool, &true);
+        }
+
+        pub fn set_next_pool(e: Env, pool: Address) {
+            e.storage().instance().set(&symbol_short!("next"), &pool);
+        }
+
+        pub fn deploy(e: Env, deployer: Address, _wasm_hash: BytesN<32>, _salt: BytesN<32>, init_args: Vec<Val>) -> Address {
+            deployer.require_auth();
+            let pool: Address = e.storage().instance().get(&symbol_short!("next")).unwrap();
+            let _res: Val = e.invoke_contract(&pool, &Symbol::new(&e, "initialize"), init_args);
+            pool
+        }
+
+        pub fn get_pool(e: Env, pool: Address) -> Option<PoolRecord> {
+            if !e.storage().persistent().has(&pool) {
+                return None;
+            }
+            Some(PoolRecord { wasm_hash: BytesN::from_array(&e, &[0; 32]), deployer: pool, flagged: false })
+        }
+    }
+}
+
+use broken_strategy::BrokenStrategy;
+use fixed_rate_router::FixedRateRouter;
+use flat_yield_adapter::{FlatYieldAdapter, FlatYieldAdapterClient};
+use listed_attestation::{ListedAttestation, ListedAttestationClient};
+use last_candidate_strategy::LastCandidateStrategy;
+use stub_factory::{StubFactory, StubFactoryClient};
+
+const NO_OF_SUBS: u32 = 3;
+const SUB_AMOUNT: u32 = 100;
+const GRACE_PERIOD: u64 = 3_600; // 1 hour
+const INITIAL_BALANCE: i128 = 10_000;
+const POOL_NAME: &str = "Weekly savings";
+const POOL_DESCRIPTION: &str = "Three friends saving 100 a week";
+
+//
+// TESTUTILS
+//
+fn create_token_contract<'a>(e: &Env, admin: &Address) -> (TokenClient<'a>, TokenAdminClient<'a>) {
+    let contract_address = e.register_stellar_asset_contract_v2(admin.clone()).address();
+    (
+        TokenClient::new(e, &contract_address),
+        TokenAdminClient::new(e, &contract_address),
+    )
+}
+
+// native XLM asset contract, with a classic account holding XLM to fund the test addresses from.
+// XLM can't be minted, so the account is written to the ledger directly
+fn create_native_token<'a>(e: &Env) -> (TokenClient<'a>, Address) {
+    let asset = xdr::Asset::Native.to_xdr(xdr::Limits::none()).unwrap();
+    let contract_address = e.deployer().with_stellar_asset(Bytes::from_slice(e, &asset)).deploy();
+    let account_id = xdr::AccountId(xdr::PublicKey::PublicKeyTypeEd25519(xdr::Uint256([7; 32])));
+    let key = xdr::LedgerKey::Account(xdr::LedgerKeyAccount { account_id: account_id.clone() });
+    let entry = xdr::LedgerEntry {
+        data: xdr::LedgerEntryData::Account(xdr::AccountEntry {
+            account_id: account_id.clone(),
+            balance: 1_000_000_000,
+            flags: 0,
+            home_domain: Default::default(),
+            inflation_dest: None,
+            num_sub_entries: 0,
+            seq_num: xdr::SequenceNumber(0),
+            thresholds: xdr::Thresholds([1; 4]),
+            signers: xdr::VecM::default(),
+            ext: xdr::AccountEntryExt::V0,
+        }),
+        last_modified_ledger_seq: 0,
+        ext: xdr::LedgerEntryExt::V0,
+    };
+    e.host().add_ledger_entry(&Rc::new(key), &Rc::new(entry), None).unwrap();
+    let account = Address::try_from_val(e, &xdr::ScAddress::Account(account_id)).unwrap();
+    (TokenClient::new(e, &contract_address), account)
+}
+
+fn create_pool_contract(e: &Env) -> HelloContractClient<'_> {
+    HelloContractClient::new(e, &e.register_contract(None, HelloContract {}))
+}
+
+fn advance_ledger(e: &Env, delta: u64) {
+    e.ledger().with_mut(|l| {
+        l.timestamp += delta;
+    });
+}
+
+// pool token with every generated address funded
+fn setup_token<'a>(e: &Env) -> (TokenClient<'a>, TokenAdminClient<'a>) {
+    let token_admin = Address::generate(e);
+    create_token_contract(e, &token_admin)
+}
+
+// weekly pool of NO_OF_SUBS seats in `token`, without deadline or attestation
+fn pool_config(e: &Env, token: &Address, collateral_amount: i128) -> PoolConfig {
+    PoolConfig {
+        name: String::from_str(e, POOL_NAME),
+        description: String::from_str(e, POOL_DESCRIPTION),
+        no_of_subs: NO_OF_SUBS,
+        sub_amount: SUB_AMOUNT,
+        frequency: Frequency::WEEK,
+        grace_period_secs: GRACE_PERIOD,
+        token: token.clone(),
+        collateral_amount,
+        join_deadline: None,
+        dividend_policy: DividendPolicy::EqualAmongNonWinners,
+        attestation: None,
+    }
+}
+
+fn setup_pool_with_collateral<'a>(
+    e: &'a Env,
+    collateral_amount: i128,
+) -> (HelloContractClient<'a>, TokenClient<'a>, Address, Address, Address) {
+    setup_pool_with_config(e, collateral_amount, |_| {})
+}
+
+fn setup_pool_with_config<'a>(
+    e: &'a Env,
+    collateral_amount: i128,
+    configure: impl FnOnce(&mut PoolConfig),
+) -> (HelloContractClient<'a>, TokenClient<'a>, Address, Address, Address) {
+    let (token, token_admin) = setup_token(e);
+    let client = create_pool_contract(e);
+    let owner = Address::generate(e);
+    let user_1 = Address::generate(e);
+    let user_2 = Address::generate(e);
+    for user in [&owner, &user_1, &user_2] {
+        token_admin.mint(user, &INITIAL_BALANCE);
+    }
+
+    let mut config = pool_config(e, &token.address, collateral_amount);
+    configure(&mut config);
+    client.initialize(&owner, &config, &None);
+    client.join(&user_1, &None, &None);
+    client.join(&user_2, &None, &None);
+
+    (client, token, owner, user_1, user_2)
+}
+
+fn setup_pool<'a>(e: &'a Env) -> (HelloContractClient<'a>, Address, Address, Address) {
+    let (client, _, owner, user_1, user_2) = setup_pool_with_collateral(e, 0);
+    (client, owner, user_1, user_2)
+}
+
+#[test]
+fn test_pool_lifecycle() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, owner, user_1, user_2) = setup_pool_with_collateral(&env, 0);
+
+    let state = client.get_state();
+    assert_eq!(state.pool_params.no_of_subs, NO_OF_SUBS);
+    assert_eq!(state.pool_params.pool_owner, owner);
+    assert_eq!(state.subcriber_map.len(), 3);
+
+    client.start_pool();
+    client.pay_due(&owner, &1, &None, &None);
+    client.pay_due(&user_1, &1, &None, &None);
+    client.pay_due(&user_2, &1, &None, &None);
+    assert_eq!(client.get_state().pool_iteration_map.get(1).unwrap().amount_collected, 300);
+    assert_eq!(token.balance(&client.address), 300);
+    assert_eq!(token.balance(&user_1), INITIAL_BALANCE - 100);
+
+    client.set_pool_winner(&1, &250, &user_1);
+    assert_eq!(client.get_pool_winner(&1), user_1);
+
+    let iteration = client.get_state().pool_iteration_map.get(1).unwrap();
+    assert_eq!(iteration.prize_money, 250);
+    assert_eq!(iteration.dividend_amount, 50);
+
+    let subscriber = client.get_subscriber_details(&user_1);
+    assert_eq!(subscriber.winner_at_iter, 1);
+    assert_eq!(subscriber.prize_money, 250);
+}
+
+#[test]
+fn test_pool_metadata() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, owner, _, _) = setup_pool_with_collateral(&env, 0);
+    assert_eq!(client.get_name(), String::from_str(&env, POOL_NAME));
+    assert_eq!(client.get_description(), String::from_str(&env, POOL_DESCRIPTION));
+    assert_eq!(client.get_token(), token.address);
+
+    // a pool needs a name
+    let client = create_pool_contract(&env);
+    assert!(client
+        .try_initialize(&owner, &PoolConfig { name: String::from_str(&env, ""), ..pool_config(&env, &token.address, 0) }, &None)
+        .is_err());
+}
+
+#[test]
+fn test_token_probe() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let owner = Address::generate(&env);
+    let client = create_pool_contract(&env);
+    // a pool can't be set up against a contract that isn't a token, or no contract at all
+    let router = env.register_contract(None, FixedRateRouter);
+    for not_a_token in [router, Address::generate(&env)] {
+        assert!(client.try_initialize(&owner, &pool_config(&env, &not_a_token, 0), &None).is_err());
+    }
+}
+
+#[test]
+fn test_start_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, _, user_2) = setup_pool(&env);
+    // iterations can't run before the pool is started
+    assert!(client.try_start_new_iteration(&1).is_err());
+    assert_eq!(client.get_min_subscribers(), NO_OF_SUBS);
+
+    // a seat is free again, the pool is short of members
+    client.leave(&user_2);
+    assert!(client.try_start_pool().is_err());
+    assert!(client.try_set_min_subscribers(&(NO_OF_SUBS + 1)).is_err());
+    client.set_min_subscribers(&2);
+
+    advance_ledger(&env, 100);
+    client.start_pool();
+    let state = client.get_state();
+    assert_eq!(state.status, PoolStatus::Active);
+    assert_eq!(state.started_at, env.ledger().timestamp());
+    assert_eq!(state.pool_iteration_map.get(1).unwrap().start_time, state.started_at);
+
+    // the member list is locked
+    assert!(client.try_join(&user_2, &None, &None).is_err());
+    assert!(client.try_start_pool().is_err());
+}
+
+#[test]
+fn test_abort_unfilled() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    const COLLATERAL: i128 = 50;
+    let (token, token_admin) = setup_token(&env);
+    let client = create_pool_contract(&env);
+    let owner = Address::generate(&env);
+    let user_1 = Address::generate(&env);
+    let user_2 = Address::generate(&env);
+    for user in [&owner, &user_1, &user_2] {
+        token_admin.mint(user, &INITIAL_BALANCE);
+    }
+    client.initialize(
+        &owner,
+        &PoolConfig {
+            join_deadline: Some(env.ledger().timestamp() + DAY_IN_SECS),
+            ..pool_config(&env, &token.address, COLLATERAL)
+        },
+        &None,
+    );
+    client.join(&user_1, &None, &None);
+    // nothing to abort before the deadline
+    assert!(client.try_abort_unfilled().is_err());
+
+    advance_ledger(&env, DAY_IN_SECS + 1);
+    assert!(client.try_join(&user_2, &None, &None).is_err());
+    client.abort_unfilled();
+    assert_eq!(client.get_state().status, PoolStatus::Cancelled);
+
+    // early joiners get their collateral back
+    client.claim_refund(&user_1);
+    assert_eq!(token.balance(&user_1), INITIAL_BALANCE);
+}
+
+#[test]
+fn test_pool_full() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    // the pool is at exact capacity once the setup members have joined
+    let (client, token, _, _, user_2) = setup_pool_with_collateral(&env, 0);
+    assert_eq!(client.get_state().subcriber_map.len(), NO_OF_SUBS);
+
+    let applicant = Address::generate(&env);
+    TokenAdminClient::new(&env, &token.address).mint(&applicant, &INITIAL_BALANCE);
+    assert_eq!(client.try_join(&applicant, &None, &None), Err(Ok(Error::PoolFull.into())));
+
+    // a freed seat can be taken again, up to the cap
+    client.leave(&user_2);
+    client.join(&applicant, &None, &None);
+    assert_eq!(client.try_join(&user_2, &None, &None), Err(Ok(Error::PoolFull.into())));
+    assert_eq!(client.get_state().subcriber_map.len(), NO_OF_SUBS);
+}
+
+#[test]
+fn test_sequential_iterations() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, _, _) = setup_pool(&env);
+    client.start_pool();
+    assert_eq!(client.get_state().current_iteration, 1);
+
+    // no gaps and no rewrites of past rounds
+    assert!(client.try_start_new_iteration(&3).is_err());
+    assert!(client.try_start_new_iteration(&1).is_err());
+    // only the owner closes a round
+    env.set_auths(&[]);
+    assert!(client.try_start_new_iteration(&2).is_err());
+    env.mock_all_auths();
+    // and not before the members had until the deadline and the grace period to pay
+    advance_ledger(&env, WEEK_IN_SECS + GRACE_PERIOD);
+    assert!(client.try_start_new_iteration(&2).is_err());
+    advance_ledger(&env, 1);
+    client.start_new_iteration(&2);
+    assert!(client.try_start_new_iteration(&2).is_err());
+    assert_eq!(client.get_state().current_iteration, 2);
+
+    // closing rounds late doesn't shift the schedule, and the cycle ends with the last seat
+    advance_ledger(&env, 3 * WEEK_IN_SECS);
+    client.start_new_iteration(&3);
+    assert_eq!(client.get_iteration(&3).start_time, client.get_iteration(&1).start_time + 2 * WEEK_IN_SECS);
+    advance_ledger(&env, WEEK_IN_SECS + GRACE_PERIOD + 1);
+    assert!(client.try_start_new_iteration(&4).is_err());
+}
+
+#[test]
+fn test_winner_requires_full_collection() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, owner, user_1, user_2) = setup_pool(&env);
+    client.start_pool();
+    client.pay_due(&owner, &1, &None, &None);
+    client.pay_due(&user_1, &1, &None, &None);
+    // user_2 hasn't paid, the round is underfunded
+    assert!(client.try_set_pool_winner(&1, &200, &user_1).is_err());
+    assert!(client.try_draw_winner(&1).is_err());
+
+    // the owner documents the shortfall to let the round close
+    assert!(client.try_approve_shortfall(&1, &String::from_str(&env, "")).is_err());
+    let reason = String::from_str(&env, "user_2 is travelling, pays next round");
+    client.approve_shortfall(&1, &reason);
+    assert_eq!(client.get_shortfall_approval(&1), Some(reason));
+    client.set_pool_winner(&1, &200, &user_1);
+    client.start_new_iteration(&2);
+
+    // a fully funded round needs no approval
+    for member in [&owner, &user_1, &user_2] {
+        client.pay_due(member, &2, &None, &None);
+    }
+    assert!(client.try_approve_shortfall(&2, &String::from_str(&env, "none")).is_err());
+    client.set_pool_winner(&2, &300, &user_2);
+}
+
+#[test]
+fn test_rollover() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, owner, user_1, user_2) = setup_pool(&env);
+    client.start_pool();
+    let start = env.ledger().timestamp();
+    let members = [&owner, &user_1, &user_2];
+    for (iteration, winner) in [(1, &owner), (2, &user_1), (3, &user_2)] {
+        for member in members {
+            client.pay_due(member, &iteration, &None, &None);
+        }
+        client.set_pool_winner(&iteration, &300, winner);
+        if iteration < NO_OF_SUBS {
+            client.start_new_iteration(&(iteration + 1));
+        }
+    }
+
+    // every iteration opened on schedule, and none after the last seat had its turn
+    assert!(client.try_start_new_iteration(&(NO_OF_SUBS + 1)).is_err());
+    let state = client.get_state();
+    assert_eq!(state.current_iteration, NO_OF_SUBS);
+    assert_eq!(state.pool_iteration_map.len(), NO_OF_SUBS);
+    assert_eq!(state.pool_iteration_map.get(2).unwrap().start_time, start + WEEK_IN_SECS);
+    assert_eq!(state.pool_iteration_map.get(3).unwrap().start_time, start + 2 * WEEK_IN_SECS);
+}
+
+#[test]
+fn test_force_draw() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, owner, user_1, user_2) = setup_pool(&env);
+    client.start_pool();
+    client.pay_due(&owner, &1, &None, &None);
+    client.pay_due(&user_1, &1, &None, &None);
+
+    // the owner still has time to pick the winner
+    advance_ledger(&env, WEEK_IN_SECS + GRACE_PERIOD + FORCE_DRAW_TIMEOUT_SECS);
+    assert!(client.try_force_draw(&user_1, &1).is_err());
+
+    advance_ledger(&env, 1);
+    assert!(client.try_force_draw(&Address::generate(&env), &1).is_err());
+    let winner = client.force_draw(&user_1, &1);
+    let subr = client.get_subscriber_details(&winner);
+    assert_eq!(subr.winner_at_iter, 1);
+    assert_eq!(subr.prize_money, 2 * SUB_AMOUNT);
+    assert!(client.try_force_draw(&user_2, &1).is_err());
+    client.start_new_iteration(&2);
+    assert_eq!(client.get_state().current_iteration, 2);
+}
+
+#[test]
+fn test_commission() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, owner, user_1, user_2) = setup_pool_with_collateral(&env, 0);
+    assert!(client.try_set_commission(&(MAX_COMMISSION_BPS + 1)).is_err());
+    client.set_commission(&500);
+    assert_eq!(client.get_commission(), 500);
+
+    client.start_pool();
+    for member in [&owner, &user_1, &user_2] {
+        client.pay_due(member, &1, &None, &None);
+    }
+    // 5% of the 300 pot goes to the owner, the prize can't eat into it
+    assert!(client.try_set_pool_winner(&1, &290, &user_1).is_err());
+    client.set_pool_winner(&1, &250, &user_1);
+    let pool_iteration = client.get_state().pool_iteration_map.get(1).unwrap();
+    assert_eq!(pool_iteration.commission, 15);
+    assert_eq!(pool_iteration.dividend_amount, 35);
+    assert_eq!(client.reconcile().discrepancy, 0);
+
+    // a drawn winner gets the pot less the commission, plus what was left of the first dividend
+    client.start_new_iteration(&2);
+    for member in [&owner, &user_1, &user_2] {
+        client.pay_due(member, &2, &None, &None);
+    }
+    let winner = client.draw_winner(&2);
+    assert_eq!(client.get_subscriber_details(&winner).prize_money, 286);
+
+    let balance = token.balance(&owner);
+    client.claim_commission();
+    assert_eq!(token.balance(&owner), balance + 30);
+    assert!(client.try_claim_commission().is_err());
+    assert_eq!(client.reconcile().discrepancy, 0);
+}
+
+#[test]
+fn test_protocol_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (token, token_admin) = setup_token(&env);
+    let client = create_pool_contract(&env);
+    let members = [Address::generate(&env), Address::generate(&env), Address::generate(&env)];
+    for member in members.iter() {
+        token_admin.mint(member, &INITIAL_BALANCE);
+    }
+    let treasury = Address::generate(&env);
+    let fee = ProtocolFee { fee_bps: 100, treasury: treasury.clone(), treasurer: Address::generate(&env) };
+    client.initialize(&members[0], &pool_config(&env, &token.address, 0), &Some(fee.clone()));
+    assert_eq!(client.get_protocol_fee(), Some(fee));
+    client.join(&members[1], &None, &None);
+    client.join(&members[2], &None, &None);
+    client.set_commission(&500);
+
+    client.start_pool();
+    for member in members.iter() {
+        client.pay_due(member, &1, &None, &None);
+    }
+    // 1% of the 300 pot accrues to the treasury on top of the 5% commission
+    let winner = client.draw_winner(&1);
+    assert_eq!(client.get_subscriber_details(&winner).prize_money, 282);
+    assert_eq!(client.get_state().pool_iteration_map.get(1).unwrap().protocol_fee, 3);
+    assert_eq!(client.get_treasury_balance(), 3);
+    let accrued = env
+        .events()
+        .all()
+        .iter()
+        .any(|event| event.1 == snbfi_events::topics(symbol_short!("fee"), 1_u32).into_val(&env));
+    assert!(accrued);
+
+    client.withdraw_treasury();
+    assert_eq!(token.balance(&treasury), 3);
+    assert_eq!(client.get_treasury_balance(), 0);
+    assert!(client.try_withdraw_treasury().is_err());
+    assert_eq!(client.reconcile().discrepancy, 0);
+}
+
+#[test]
+fn test_get_fee_balances() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, owner, user_1, user_2) = setup_pool(&env);
+    client.set_commission(&500);
+    client.set_protection_limits(&1_000, &1_000);
+    client.start_pool();
+    client.pay_due(&owner, &1, &None, &None);
+    client.pay_due(&user_1, &1, &None, &None);
+    advance_ledger(&env, WEEK_IN_SECS + GRACE_PERIOD + 1);
+    client.pay_due(&user_2, &1, &None, &None);
+    client.set_pool_winner(&1, &285, &user_1);
+
+    let fees = client.get_fee_balances();
+    assert_eq!(fees.commission_charged, 15);
+    assert_eq!(fees.commission_unclaimed, 15);
+    assert_eq!(fees.protocol_fees_charged, 0);
+    assert_eq!(fees.penalties_collected, 10);
+    // the prize and the fees add up to the dues collected, the late fee stays out of the pot
+    let pool_iteration = client.get_state().pool_iteration_map.get(1).unwrap();
+    assert_eq!(pool_iteration.amount_collected, 300);
+    assert_eq!(pool_iteration.prize_money + pool_iteration.commission + pool_iteration.dividend_amount, 300);
+
+    client.claim_commission();
+    let fees = client.get_fee_balances();
+    assert_eq!(fees.commission_charged, 15);
+    assert_eq!(fees.commission_unclaimed, 0);
+    assert_eq!(client.reconcile().discrepancy, 0);
+}
+
+// pool where every member paid the first iteration and user_1 won it for 240, leaving a dividend of 60, with the
+// second iteration opened
+fn setup_pool_with_dividend<'a>(
+    e: &'a Env,
+    dividend_policy: DividendPolicy,
+) -> (HelloContractClient<'a>, TokenClient<'a>, Address, Address, Address) {
+    let (client, token, owner, user_1, user_2) =
+        setup_pool_with_config(e, 0, |config| config.dividend_policy = dividend_policy);
+    client.start_pool();
+    for member in [&owner, &user_1, &user_2] {
+        client.pay_due(member, &1, &None, &None);
+    }
+    client.set_pool_winner(&1, &240, &user_1);
+    client.start_new_iteration(&2);
+    (client, token, owner, user_1, user_2)
+}
+
+#[test]
+fn test_dividend_policies() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    // the members who haven't won share the dividend
+    let (client, token, owner, user_1, user_2) = setup_pool_with_dividend(&env, DividendPolicy::EqualAmongNonWinners);
+    assert_eq!(client.get_subscriber_details(&owner).dividend_balance, 30);
+    assert_eq!(client.get_subscriber_details(&user_1).dividend_balance, 0);
+    assert_eq!(client.get_subscriber_details(&user_2).dividend_balance, 30);
+    assert_eq!(client.get_state().pool_iteration_map.get(1).unwrap().dividend_distributed, 60);
+    client.claim_dividends(&user_2);
+    assert_eq!(token.balance(&user_2), INITIAL_BALANCE - 100 + 30);
+    assert!(client.try_claim_dividends(&user_2).is_err());
+    assert_eq!(client.reconcile().discrepancy, 0);
+
+    // every member gets a share of what they paid in
+    let (client, _, owner, user_1, _) = setup_pool_with_dividend(&env, DividendPolicy::ProRataByContribution);
+    assert_eq!(client.get_subscriber_details(&owner).dividend_balance, 20);
+    assert_eq!(client.get_subscriber_details(&user_1).dividend_balance, 20);
+
+    // the next pot grows by the dividend
+    let (client, _, owner, user_1, user_2) = setup_pool_with_dividend(&env, DividendPolicy::CarryToNextPot);
+    assert_eq!(client.get_subscriber_details(&owner).dividend_balance, 0);
+    assert_eq!(client.get_state().pool_iteration_map.get(2).unwrap().carried_in, 60);
+    for member in [&owner, &user_1, &user_2] {
+        client.pay_due(member, &2, &None, &None);
+    }
+    let winner = client.draw_winner(&2);
+    assert_eq!(client.get_subscriber_details(&winner).prize_money, 360);
+
+    // the owner decides who gets what
+    let (client, _, _, _, user_2) = setup_pool_with_dividend(&env, DividendPolicy::OwnerDiscretion);
+    assert_eq!(client.get_subscriber_details(&user_2).dividend_balance, 0);
+    client.allocate_dividend(&1, &user_2, &50);
+    assert!(client.try_allocate_dividend(&1, &user_2, &11).is_err());
+    assert_eq!(client.get_subscriber_details(&user_2).dividend_balance, 50);
+}
+
+#[test]
+fn test_dividend_carryover() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    // a dividend of 69 can't be split evenly between the two members who haven't won
+    let (client, owner, user_1, user_2) = setup_pool(&env);
+    client.start_pool();
+    for member in [&owner, &user_1, &user_2] {
+        client.pay_due(member, &1, &None, &None);
+    }
+    client.set_pool_winner(&1, &231, &user_1);
+    assert_eq!(client.get_subscriber_details(&owner).dividend_balance, 34);
+
+    // the next iteration took the remainder into its pot
+    client.start_new_iteration(&2);
+    assert_eq!(client.get_state().pool_iteration_map.get(2).unwrap().carried_in, 1);
+    assert_eq!(client.get_carryover(), 0);
+    for member in [&owner, &user_1, &user_2] {
+        client.pay_due(member, &2, &None, &None);
+    }
+    client.set_pool_winner(&2, &301, &user_2);
+    client.start_new_iteration(&3);
+
+    // nobody is left to share the last dividend, it stays visible as carryover
+    for member in [&owner, &user_1, &user_2] {
+        client.pay_due(member, &3, &None, &None);
+    }
+    client.set_pool_winner(&3, &290, &owner);
+    assert_eq!(client.get_carryover(), 10);
+}
+
+#[test]
+fn test_prize_vesting() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, owner, user_1, user_2) = setup_pool_with_collateral(&env, 0);
+    assert!(client.try_set_prize_vesting(&(MAX_VESTING_TRANCHES + 1)).is_err());
+    client.set_prize_vesting(&3);
+    client.start_pool();
+    for member in [&owner, &user_1, &user_2] {
+        client.pay_due(member, &1, &None, &None);
+    }
+    client.set_pool_winner(&1, &200, &user_1);
+
+    // the first tranche unlocks with the win
+    client.claim_prize(&user_1);
+    assert_eq!(token.balance(&user_1), INITIAL_BALANCE - 100 + 66);
+    assert!(client.try_claim_prize(&user_1).is_err());
+
+    // the rest follows the schedule, the last tranche takes the remainder
+    advance_ledger(&env, WEEK_IN_SECS);
+    client.claim_prize(&user_1);
+    assert_eq!(token.balance(&user_1), INITIAL_BALANCE - 100 + 132);
+    advance_ledger(&env, 5 * WEEK_IN_SECS);
+    client.claim_prize(&user_1);
+    let subr = client.get_subscriber_details(&user_1);
+    assert_eq!(subr.prize_paid, 200);
+    assert!(subr.prize_claimed);
+    assert!(client.try_claim_prize(&user_1).is_err());
+}
+
+#[test]
+fn test_prize_loan() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, owner, user_1, user_2) = setup_pool_with_collateral(&env, 0);
+    assert!(client.try_set_prize_loan(&Some(MAX_LOAN_INTEREST_BPS + 1)).is_err());
+    client.set_prize_loan(&Some(1_000));
+    assert_eq!(client.get_prize_loan_interest(), Some(1_000));
+    client.start_pool();
+    for member in [&owner, &user_1, &user_2] {
+        client.pay_due(member, &1, &None, &None);
+    }
+    client.set_pool_winner(&1, &300, &user_1);
+    assert_eq!(client.get_prize_loan(&owner), None);
+
+    // the two dues left become the loan of the winner
+    let loan = client.get_prize_loan(&user_1).unwrap();
+    assert_eq!(loan.principal, 200);
+    assert_eq!(loan.outstanding, 200);
+    assert_eq!(loan.remaining_installments, 2);
+
+    // every due repays it with interest on top
+    client.start_new_iteration(&2);
+    assert_eq!(client.get_due_amount(&user_1), 110);
+    client.pay_due(&user_1, &2, &None, &None);
+    assert_eq!(token.balance(&user_1), INITIAL_BALANCE - 100 - 110);
+    let loan = client.get_prize_loan(&user_1).unwrap();
+    assert_eq!(loan.outstanding, 100);
+    assert_eq!(loan.interest_paid, 10);
+    assert_eq!(loan.remaining_installments, 1);
+    assert_eq!(client.get_fee_balances().penalties_collected, 10);
+
+    // members who haven't won pay their dues as usual
+    assert_eq!(client.get_due_amount(&user_2), 100);
+}
+
+#[test]
+fn test_partial_payment() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, owner, user_1, user_2) = setup_pool_with_collateral(&env, 0);
+    client.start_pool();
+    client.pay_due(&user_1, &1, &Some(40), &None);
+    assert!(client.try_pay_due(&user_1, &1, &Some(0), &None).is_err());
+
+    // the iteration isn't paid until the rest of the due comes in
+    let subr = client.get_subscriber_details(&user_1);
+    assert_eq!(subr.last_paid_iter, 0);
+    assert_eq!(subr.partial_amount, 40);
+    assert_eq!(client.get_due_amount(&user_1), 60);
+    assert_eq!(client.get_iterations(&1, &1).get(0).unwrap().amount_collected, 40);
+
+    client.pay_due(&user_1, &1, &Some(60), &None);
+    let subr = client.get_subscriber_details(&user_1);
+    assert_eq!(subr.last_paid_iter, 1);
+    assert_eq!(subr.partial_amount, 0);
+    assert_eq!(token.balance(&user_1), INITIAL_BALANCE - 100);
+    assert!(client.try_pay_due(&user_1, &1, &None, &None).is_err());
+
+    // paying in two chunks still counts as a single payment on time
+    client.pay_due(&owner, &1, &None, &None);
+    client.pay_due(&user_2, &1, &None, &None);
+    client.set_pool_winner(&1, &300, &user_2);
+    assert_eq!(client.get_subscriber_details(&user_1).missed_iterations, 0);
+    assert_eq!(client.get_pool_stats().on_time_rate_bps, PERCENTAGE_MULTIPLIER);
+}
+
+#[test]
+fn test_early_discount() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, owner, user_1, user_2) = setup_pool_with_collateral(&env, 0);
+    let discount = EarlyDiscount { discount_bps: 1_000, window_bps: 5_000 };
+    assert!(client.try_set_early_discount(&Some(discount)).is_err());
+    let discount = EarlyDiscount { discount_bps: 500, window_bps: 5_000 };
+    client.set_early_discount(&Some(discount.clone()));
+    assert_eq!(client.get_early_discount(), Some(discount));
+    client.start_pool();
+
+    // paying in the first half of the week takes 5% off the due
+    assert_eq!(client.get_due_amount(&user_1), 95);
+    client.pay_due(&user_1, &1, &None, &None);
+    assert_eq!(token.balance(&user_1), INITIAL_BALANCE - 95);
+    let record = client.get_payment_history(&user_1, &0, &1).get(0).unwrap();
+    assert_eq!(record.amount, 95);
+    assert_eq!(record.discount, 5);
+
+    advance_ledger(&env, WEEK_IN_SECS / 2 + 1);
+    client.pay_due(&owner, &1, &None, &None);
+    client.pay_due(&user_2, &1, &None, &None);
+    assert_eq!(token.balance(&user_2), INITIAL_BALANCE - 100);
+
+    // the discount comes out of the dividend, the pot still counts every due
+    assert!(client.try_set_pool_winner(&1, &300, &user_2).is_err());
+    client.set_pool_winner(&1, &295, &user_2);
+    let pool_iteration = client.get_iterations(&1, &1).get(0).unwrap();
+    assert_eq!(pool_iteration.amount_collected, 300);
+    assert_eq!(pool_iteration.discounts, 5);
+    assert_eq!(pool_iteration.dividend_amount, 0);
+    assert_eq!(client.reconcile().discrepancy, 0);
+}
+
+#[test]
+fn test_credit_balance() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, owner, user_1, user_2) = setup_pool_with_collateral(&env, 0);
+    client.start_pool();
+
+    // prepaying the whole cycle and a bit more keeps the rest as a credit
+    client.pay_due(&user_1, &1, &Some(350), &None);
+    assert_eq!(client.get_credit_balance(&user_1), 250);
+    assert_eq!(client.get_subscriber_details(&user_1).last_paid_iter, 1);
+    assert_eq!(client.reconcile().discrepancy, 0);
+    assert!(client.try_withdraw_credit(&user_1).is_err());
+
+    // the next dues are settled from the credit as soon as their iteration opens
+    for iteration in 1..=NO_OF_SUBS {
+        assert_eq!(client.get_subscriber_details(&user_1).last_paid_iter, iteration);
+        assert_eq!(client.get_due_amount(&user_1), 0);
+        client.pay_due(&owner, &iteration, &None, &None);
+        client.pay_due(&user_2, &iteration, &None, &None);
+        let winner = [&owner, &user_1, &user_2][iteration as usize - 1];
+        client.set_pool_winner(&iteration, &300, winner);
+        if iteration < NO_OF_SUBS {
+            client.start_new_iteration(&(iteration + 1));
+        }
+    }
+    assert_eq!(client.get_credit_balance(&user_1), 50);
+    assert_eq!(client.get_subscriber_details(&user_1).missed_iterations, 0);
+
+    // whatever is left is returned once the pool completes
+    client.withdraw_credit(&user_1);
+    assert_eq!(client.get_credit_balance(&user_1), 0);
+    assert_eq!(token.balance(&user_1), INITIAL_BALANCE - 300);
+    assert!(client.try_withdraw_credit(&user_1).is_err());
+}
+
+#[test]
+fn test_collect_due() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, owner, user_1, user_2) = setup_pool_with_collateral(&env, 0);
+    assert!(client.try_collect_due(&user_1).is_err());
+    client.start_pool();
+
+    // nothing can be pulled without an allowance
+    assert!(client.try_collect_due(&user_1).is_err());
+    token.approve(&user_1, &client.address, &(SUB_AMOUNT as i128 * 3), &1_000);
+    client.collect_due(&user_1);
+    assert_eq!(token.balance(&user_1), INITIAL_BALANCE - 100);
+    assert_eq!(token.allowance(&user_1, &client.address), 200);
+    assert_eq!(client.get_subscriber_details(&user_1).last_paid_iter, 1);
+    assert!(client.try_collect_due(&user_1).is_err());
+
+    // the allowance keeps paying as iterations open
+    client.pay_due(&owner, &1, &None, &None);
+    client.pay_due(&user_2, &1, &None, &None);
+    client.set_pool_winner(&1, &300, &owner);
+    client.start_new_iteration(&2);
+    client.collect_due(&user_1);
+    assert_eq!(client.get_subscriber_details(&user_1).last_paid_iter, 2);
+    assert_eq!(token.allowance(&user_1, &client.address), 100);
+}
+
+#[test]
+fn test_delegated_payer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, owner, user_1, user_2) = setup_pool_with_collateral(&env, 0);
+    let employer = Address::generate(&env);
+    token.transfer(&owner, &employer, &1_000);
+    client.start_pool();
+
+    // only the registered payer can pay for the subscriber
+    assert!(client.try_pay_due(&user_1, &1, &None, &Some(employer.clone())).is_err());
+    assert!(client.try_set_payer(&user_1, &Some(user_1.clone())).is_err());
+    client.set_payer(&user_1, &Some(employer.clone()));
+    client.pay_due(&user_1, &1, &None, &Some(employer.clone()));
+    assert_eq!(env.auths()[0].0, employer);
+    assert_eq!(token.balance(&employer), 900);
+    assert_eq!(token.balance(&user_1), INITIAL_BALANCE);
+    assert_eq!(client.get_subscriber_details(&user_1).last_paid_iter, 1);
+    assert_eq!(client.get_payer_stats(&user_1).get(0).unwrap().payer, employer);
+
+    // the seat stays with the subscriber, who can still pay and revoke the payer
+    client.pay_due(&owner, &1, &None, &None);
+    client.pay_due(&user_2, &1, &None, &None);
+    client.set_pool_winner(&1, &300, &user_1);
+    assert_eq!(client.get_subscriber_details(&user_1).winner_at_iter, 1);
+    client.start_new_iteration(&2);
+    client.set_payer(&user_1, &None);
+    assert!(client.try_pay_due(&user_1, &2, &None, &Some(employer.clone())).is_err());
+    client.pay_due(&user_1, &2, &None, &None);
+}
+
+#[test]
+fn test_sponsorship() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, owner, user_1, user_2) = setup_pool_with_collateral(&env, 0);
+    let sponsor = Address::generate(&env);
+    token.transfer(&owner, &sponsor, &1_000);
+    assert!(client.try_sponsor(&sponsor, &sponsor, &100).is_err());
+    client.sponsor(&sponsor, &user_2, &250);
+    assert!(client.try_sponsor(&owner, &user_2, &100).is_err());
+
+    // the sponsorship pays the dues of the subscriber as iterations open
+    client.start_pool();
+    assert_eq!(client.get_subscriber_details(&user_2).last_paid_iter, 1);
+    let sponsorship = client.get_sponsorship(&user_2).unwrap();
+    assert_eq!(sponsorship.balance, 150);
+    assert_eq!(sponsorship.drawn, 100);
+    assert!(client.try_refund_sponsorship(&user_2).is_err());
+    client.pay_due(&owner, &1, &None, &None);
+    client.pay_due(&user_1, &1, &None, &None);
+    client.set_pool_winner(&1, &300, &owner);
+    client.start_new_iteration(&2);
+    client.pay_due(&owner, &2, &None, &None);
+    client.pay_due(&user_1, &2, &None, &None);
+    client.set_pool_winner(&2, &300, &user_1);
+    client.start_new_iteration(&3);
+
+    // once it runs short the subscriber pays the rest
+    assert_eq!(client.get_subscriber_details(&user_2).last_paid_iter, 2);
+    assert_eq!(client.get_due_amount(&user_2), 50);
+    client.pay_due(&user_2, &3, &None, &None);
+    assert_eq!(token.balance(&user_2), INITIAL_BALANCE - 50);
+    assert_eq!(client.get_sponsorship(&user_2).unwrap().balance, 0);
+    assert_eq!(client.reconcile().discrepancy, 0);
+}
+
+#[test]
+fn test_refund_sponsorship() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, owner, user_1, _) = setup_pool_with_collateral(&env, 0);
+    let sponsor = Address::generate(&env);
+    token.transfer(&owner, &sponsor, &1_000);
+    client.sponsor(&sponsor, &user_1, &120);
+
+    // what the dues didn't use goes back to the sponsor when the pool is called off
+    client.cancel_pool();
+    client.refund_sponsorship(&user_1);
+    assert_eq!(token.balance(&sponsor), 1_000);
+    assert!(client.try_refund_sponsorship(&user_1).is_err());
+}
+
+#[test]
+fn test_batch_add_subscribers() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (token, token_admin) = setup_token(&env);
+    let client = create_pool_contract(&env);
+    let owner = Address::generate(&env);
+    let members = Vec::from_array(&env, [Address::generate(&env), Address::generate(&env)]);
+    token_admin.mint(&owner, &INITIAL_BALANCE);
+    for member in members.iter() {
+        token_admin.mint(&member, &INITIAL_BALANCE);
+    }
+    client.initialize(&owner, &pool_config(&env, &token.address, 50), &None);
+
+    client.batch_add_subscribers(&members);
+    // the owner and every member signed the same invocation
+    let auths = env.auths();
+    let signed = |signer: &Address| auths.iter().any(|(address, _)| address == signer);
+    assert!(signed(&owner));
+    for member in members.iter() {
+        assert!(signed(&member));
+        assert_eq!(client.get_collateral(&member), 50);
+    }
+    assert_eq!(client.get_state().subcriber_map.len(), NO_OF_SUBS);
+
+    // the seats are capped like with join
+    assert_eq!(
+        client.try_batch_add_subscribers(&Vec::from_array(&env, [Address::generate(&env)])),
+        Err(Ok(Error::PoolFull.into()))
+    );
+}
+
+#[test]
+fn test_collect_all_due() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, owner, user_1, user_2) = setup_pool_with_collateral(&env, 0);
+    client.start_pool();
+    let outsider = Address::generate(&env);
+    for member in [&owner, &user_1] {
+        token.approve(member, &client.address, &1_000, &1_000);
+    }
+    client.pay_due(&owner, &1, &None, &None);
+
+    // members that can't be collected are reported and skipped
+    let subscribers = Vec::from_array(&env, [owner.clone(), user_1.clone(), user_2.clone(), outsider.clone()]);
+    assert_eq!(client.collect_all_due(&subscribers), 1);
+    let events = env.events().all();
+    let reasons: Vec<(Address, Symbol)> = Vec::from_array(&env, [
+        (owner.clone(), symbol_short!("paid")),
+        (user_2.clone(), symbol_short!("allowance")),
+        (outsider.clone(), symbol_short!("member")),
+    ]);
+    for (subscriber, reason) in reasons.iter() {
+        assert!(events.contains((
+            client.address.clone(),
+            snbfi_events::topics(symbol_short!("nocollect"), subscriber).into_val(&env),
+            (1u32, reason).into_val(&env),
+        )));
+    }
+    assert!(events.contains((
+        client.address.clone(),
+        snbfi_events::topics(symbol_short!("collected"), user_1.clone()).into_val(&env),
+        (1u32, 100u32).into_val(&env),
+    )));
+    assert_eq!(client.get_subscriber_details(&user_1).last_paid_iter, 1);
+    assert_eq!(token.balance(&user_1), INITIAL_BALANCE - 100);
+
+    let mut too_many = Vec::new(&env);
+    for _ in 0..=MAX_COLLECTIONS_PER_CALL {
+        too_many.push_back(user_2.clone());
+    }
+    assert!(client.try_collect_all_due(&too_many).is_err());
+}
+
+#[test]
+fn test_allowlist() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, _, _, user_2) = setup_pool_with_collateral(&env, 0);
+    client.leave(&user_2);
+    client.set_allowlist_enabled(&true);
+    assert!(client.is_allowlist_enabled());
+
+    // only the addresses the owner added can take a seat
+    assert!(client.try_join(&user_2, &None, &None).is_err());
+    client.add_to_allowlist(&Vec::from_array(&env, [user_2.clone()]));
+    assert!(client.is_allowlisted(&user_2));
+    client.remove_from_allowlist(&user_2);
+    assert!(!client.is_allowlisted(&user_2));
+    assert!(client.try_join(&user_2, &None, &None).is_err());
+    client.add_to_allowlist(&Vec::from_array(&env, [user_2.clone()]));
+    client.join(&user_2, &None, &None);
+    assert!(client.get_state().subcriber_map.contains_key(user_2));
+
+    client.start_pool();
+    assert!(client.try_set_allowlist_enabled(&false).is_err());
+}
+
+#[test]
+fn test_denylist() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, owner, user_1, user_2) = setup_pool_with_collateral(&env, 0);
+    client.leave(&user_2);
+    assert!(client.try_add_to_denylist(&owner).is_err());
+    client.add_to_denylist(&user_2);
+    assert!(client.is_denied(&user_2));
+    let event = env.events().all().last().unwrap();
+    assert_eq!(event.1, snbfi_events::topics(symbol_short!("denylist"), user_2.clone()).into_val(&env));
+    let denied: bool = event.2.into_val(&env);
+    assert!(denied);
+
+    // denied addresses can't join, even when on the allowlist
+    client.set_allowlist_enabled(&true);
+    client.add_to_allowlist(&Vec::from_array(&env, [user_2.clone()]));
+    assert!(client.try_join(&user_2, &None, &None).is_err());
+    client.remove_from_denylist(&user_2);
+    let event = env.events().all().last().unwrap();
+    assert_eq!(event.1, snbfi_events::topics(symbol_short!("denylist"), user_2.clone()).into_val(&env));
+    let denied: bool = event.2.into_val(&env);
+    assert!(!denied);
+    client.join(&user_2, &None, &None);
+
+    // nor take or hand over a seat
+    let user_3 = Address::generate(&env);
+    client.add_to_allowlist(&Vec::from_array(&env, [user_3.clone()]));
+    client.add_to_denylist(&user_3);
+    assert!(client.try_transfer_slot(&user_1, &user_3).is_err());
+    client.add_to_denylist(&user_1);
+    client.remove_from_denylist(&user_3);
+    assert!(client.try_transfer_slot(&user_1, &user_3).is_err());
+}
+
+#[test]
+fn test_attestation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let attestation = ListedAttestationClient::new(&env, &env.register_contract(None, ListedAttestation));
+    let (token, token_admin) = setup_token(&env);
+    let client = create_pool_contract(&env);
+    let owner = Address::generate(&env);
+    let user = Address::generate(&env);
+    token_admin.mint(&user, &INITIAL_BALANCE);
+    let config = PoolConfig { attestation: Some(attestation.address.clone()), ..pool_config(&env, &token.address, 0) };
+    client.initialize(&owner, &config, &None);
+    assert_eq!(client.get_attestation_contract(), Some(attestation.address.clone()));
+
+    // unverified addresses are turned away
+    assert!(client.try_join(&user, &None, &None).is_err());
+    attestation.verify(&user);
+    client.join(&user, &None, &None);
+    assert!(client.get_state().subcriber_map.contains_key(user));
+}
+
+#[test]
+fn test_pay_due_with_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (other_token, other_token_admin) = setup_token(&env);
+    let router = env.register_contract(None, FixedRateRouter);
+    let config = SwapConfig {
+        router: router.clone(),
+        tokens: Vec::from_array(&env, [other_token.address.clone()]),
+        max_slippage_bps: 100,
+    };
+    let setup = |max_slippage_bps| {
+        let (client, token, owner, user_1, _) = setup_pool_with_collateral(&env, 0);
+        token.transfer(&owner, &router, &1_000);
+        other_token_admin.mint(&user_1, &1_000);
+        assert!(client.try_pay_due_with_token(&user_1, &1, &other_token.address).is_err());
+        client.set_swap_config(&Some(SwapConfig { max_slippage_bps, ..config.clone() }));
+        client.start_pool();
+        (client, token, user_1)
+    };
+
+    let (client, _, _) = setup(MAX_SLIPPAGE_BPS);
+    assert!(client.try_set_swap_config(&Some(SwapConfig { max_slippage_bps: MAX_SLIPPAGE_BPS + 1, ..config.clone() })).is_err());
+    assert_eq!(client.get_swap_config().unwrap().max_slippage_bps, MAX_SLIPPAGE_BPS);
+
+    // the due is bought with the other token and paid like any other
+    let (client, token, user_1) = setup(100);
+    assert_eq!(client.pay_due_with_token(&user_1, &1, &other_token.address), 202);
+    assert_eq!(other_token.balance(&user_1), 1_000 - 202);
+    assert_eq!(token.balance(&user_1), INITIAL_BALANCE);
+    assert_eq!(client.get_subscriber_details(&user_1).last_paid_iter, 1);
+
+    // the router charges 1% over its quote, more than this pool accepts
+    let (client, _, user_1) = setup(50);
+    assert!(client.try_pay_due_with_token(&user_1, &1, &other_token.address).is_err());
+
+    // only the listed tokens are swapped
+    let (client, token, user_1) = setup(100);
+    assert!(client.try_pay_due_with_token(&user_1, &1, &token.address).is_err());
+}
+
+#[test]
+fn test_deferred_settlement() {
+    let env = Env::default();
+    // the router takes the held tokens from the pool inside the swap the pool calls
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (other_token, other_token_admin) = setup_token(&env);
+    let router = env.register_contract(None, FixedRateRouter);
+    let (client, token, owner, user_1, user_2) = setup_pool_with_collateral(&env, 0);
+    token.transfer(&owner, &router, &1_000);
+    other_token_admin.mint(&user_1, &1_000);
+    assert!(client.try_set_deferred_settlement(&true).is_err());
+    client.set_swap_config(&Some(SwapConfig {
+        router: router.clone(),
+        tokens: Vec::from_array(&env, [other_token.address.clone()]),
+        max_slippage_bps: 100,
+    }));
+    client.set_deferred_settlement(&true);
+    assert!(client.is_deferred_settlement());
+    client.start_pool();
+
+    // the other token is held as paid, with the slippage the pool accepts on top of the quote
+    assert_eq!(client.pay_due_with_token(&user_1, &1, &other_token.address), 202);
+    assert_eq!(token.balance(&user_1), INITIAL_BALANCE);
+    assert_eq!(other_token.balance(&client.address), 202);
+    assert_eq!(client.get_subscriber_details(&user_1).last_paid_iter, 1);
+    assert_eq!(
+        client.get_asset_holdings(),
+        Vec::from_array(&env, [AssetHolding { token: other_token.address.clone(), held: 202, pending: SUB_AMOUNT }])
+    );
+    assert_eq!(client.reconcile().discrepancy, 0);
+
+    // it is converted into the pool token when the winner is picked, and the prize is paid in the pool token
+    client.pay_due(&owner, &1, &None, &None);
+    client.pay_due(&user_2, &1, &None, &None);
+    client.set_pool_winner(&1, &300, &user_2);
+    assert_eq!(
+        client.get_asset_holdings(),
+        Vec::from_array(&env, [AssetHolding { token: other_token.address.clone(), held: 0, pending: 0 }])
+    );
+    assert_eq!(other_token.balance(&client.address), 0);
+    let balance = token.balance(&user_2);
+    client.claim_prize(&user_2);
+    assert_eq!(token.balance(&user_2), balance + 300);
+    assert_eq!(client.settle_contributions(), 0);
+    assert_eq!(client.reconcile().discrepancy, 0);
+}
+
+#[test]
+fn test_yield_adapter() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, owner, user_1, user_2) = setup_pool_with_collateral(&env, 0);
+    let adapter = FlatYieldAdapterClient::new(&env, &env.register_contract(None, FlatYieldAdapter));
+    adapter.init(&token.address);
+    token.transfer(&owner, &adapter.address, &1_000);
+    client.set_yield_adapter(&Some(adapter.address.clone()));
+    assert_eq!(client.get_yield_adapter(), Some(adapter.address.clone()));
+    client.start_pool();
+    assert!(client.try_set_yield_adapter(&None).is_err());
+
+    // the pot collected so far is lent out, in as many steps as the owner likes
+    client.pay_due(&owner, &1, &None, &None);
+    assert_eq!(client.deposit_idle_funds(), 100);
+    client.pay_due(&user_1, &1, &None, &None);
+    client.pay_due(&user_2, &1, &None, &None);
+    assert_eq!(client.deposit_idle_funds(), 200);
+    assert!(client.try_deposit_idle_funds().is_err());
+    assert_eq!(client.get_idle_deposit(), Some(IdleDeposit { iteration: 1, amount: 300 }));
+    assert_eq!(token.balance(&client.address), 0);
+    assert_eq!(client.reconcile().discrepancy, 0);
+
+    // it comes back before the payout and the yield joins the dividend
+    client.set_pool_winner(&1, &300, &user_1);
+    let pool_iteration = client.get_iterations(&1, &1).get(0).unwrap();
+    assert_eq!(pool_iteration.yield_earned, 30);
+    assert_eq!(pool_iteration.dividend_amount, 30);
+    assert_eq!(client.get_idle_deposit(), None);
+    assert_eq!(token.balance(&client.address), 330);
+    assert_eq!(client.reconcile().discrepancy, 0);
+}
+
+#[test]
+fn test_emergency_withdraw() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, owner, _, _) = setup_pool_with_collateral(&env, 50);
+    let rescue = Address::generate(&env);
+    assert!(client.try_propose_emergency_withdraw(&rescue, &0).is_err());
+    assert!(client.try_execute_emergency_withdraw().is_err());
+
+    // the withdrawal is announced to the members first
+    let withdrawal = client.propose_emergency_withdraw(&rescue, &100);
+    assert_eq!(withdrawal.executable_at, env.ledger().timestamp() + EMERGENCY_DELAY_SECS);
+    let event = env.events().all().last().unwrap();
+    assert_eq!(event.1, snbfi_events::topics(symbol_short!("emergency"), rescue.clone()).into_val(&env));
+    let data: (i128, u64) = event.2.into_val(&env);
+    assert_eq!(data, (100, withdrawal.executable_at));
+    assert_eq!(client.get_emergency_withdrawal(), Some(withdrawal));
+    assert!(client.try_propose_emergency_withdraw(&rescue, &50).is_err());
+
+    // and can't be carried out before the delay passes
+    advance_ledger(&env, EMERGENCY_DELAY_SECS - 1);
+    assert!(client.try_execute_emergency_withdraw().is_err());
+    advance_ledger(&env, 1);
+    client.execute_emergency_withdraw();
+    assert_eq!(token.balance(&rescue), 100);
+    assert_eq!(token.balance(&client.address), 50);
+    assert_eq!(client.get_emergency_withdrawal(), None);
+
+    // the books account for the tokens that left
+    let report = client.reconcile();
+    assert_eq!(report.discrepancy, 0);
+    assert_eq!(report.actual_balance, 50);
+    assert!(!client.check_invariants().contains(symbol_short!("balance")));
+
+    // a withdrawal the owner drops can't be carried out
+    client.propose_emergency_withdraw(&owner, &50);
+    client.cancel_emergency_withdraw();
+    advance_ledger(&env, EMERGENCY_DELAY_SECS);
+    assert!(client.try_execute_emergency_withdraw().is_err());
+    assert_eq!(token.balance(&client.address), 50);
+}
+
+#[test]
+fn test_param_timelock() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, owner, user_1, _) = setup_pool_with_collateral(&env, 0);
+    assert!(client.try_set_timelock_delay(&(MAX_TIMELOCK_DELAY_SECS + 1)).is_err());
+    client.set_timelock_delay(&DAY_IN_SECS);
+    assert_eq!(client.get_timelock_delay(), DAY_IN_SECS);
+
+    // with a notice set, fee changes have to be proposed first
+    assert!(client.try_set_commission(&200).is_err());
+    assert!(client.try_propose_param_change(&ParamChange::Commission(MAX_COMMISSION_BPS + 1)).is_err());
+    let id = client.propose_param_change(&ParamChange::Commission(200));
+    let event = env.events().all().last().unwrap();
+    assert_eq!(event.1, snbfi_events::topics(symbol_short!("proposed"), id).into_val(&env));
+    let pending = client.get_pending_change(&id).unwrap();
+    assert_eq!(pending.executable_at, env.ledger().timestamp() + DAY_IN_SECS);
+    assert!(client.try_execute_param_change(&id).is_err());
+    advance_ledger(&env, DAY_IN_SECS);
+    client.execute_param_change(&id);
+    assert_eq!(client.get_commission(), 200);
+    assert_eq!(client.get_pending_change(&id), None);
+    assert!(client.try_execute_param_change(&id).is_err());
+
+    // the notice also lets the owner change the rules once the pool runs
+    client.start_pool();
+    let limits = PenaltyConfig { late_fee_bps: 500, max_penalty_bps: 1_000 };
+    let id = client.propose_param_change(&ParamChange::ProtectionLimits(limits));
+    advance_ledger(&env, DAY_IN_SECS);
+    client.execute_param_change(&id);
+    assert_eq!(client.get_protection_limits().late_fee_bps, 500);
+
+    // a dropped change can't be carried out
+    let id = client.propose_param_change(&ParamChange::Commission(0));
+    client.cancel_param_change(&id);
+    advance_ledger(&env, DAY_IN_SECS);
+    assert!(client.try_execute_param_change(&id).is_err());
+    assert_eq!(client.get_commission(), 200);
+
+    // the token can't change under a running pool
+    client.pay_due(&owner, &1, &None, &None);
+    client.pay_due(&user_1, &1, &None, &None);
+    let (other_token, _) = setup_token(&env);
+    assert!(client.try_propose_param_change(&ParamChange::Token(other_token.address.clone())).is_err());
+    assert_eq!(client.get_token(), token.address);
+}
+
+#[test]
+fn test_change_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (other_token, _) = setup_token(&env);
+    let (client, _, _, _, _) = setup_pool_with_collateral(&env, 0);
+    assert!(client.try_propose_param_change(&ParamChange::Token(Address::generate(&env))).is_err());
+    let id = client.propose_param_change(&ParamChange::Token(other_token.address.clone()));
+    client.execute_param_change(&id);
+    assert_eq!(client.get_token(), other_token.address);
+
+    // not while the pool holds collateral in the current one
+    let (client, _, _, _, _) = setup_pool_with_collateral(&env, 50);
+    assert!(client.try_propose_param_change(&ParamChange::Token(other_token.address)).is_err());
+}
+
+#[test]
+fn test_guardians() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, owner, user_1, user_2) = setup_pool_with_collateral(&env, 0);
+    let [guardian_1, guardian_2, guardian_3] = [0; 3].map(|_| Address::generate(&env));
+    let guardians = Vec::from_array(&env, [guardian_1.clone(), guardian_2.clone(), guardian_3.clone()]);
+    assert!(client.try_set_guardians(&Some(GuardianSet { guardians: guardians.clone(), threshold: 0 })).is_err());
+    assert!(client.try_set_guardians(&Some(GuardianSet { guardians: guardians.clone(), threshold: 4 })).is_err());
+    let with_owner = Vec::from_array(&env, [guardian_1.clone(), owner.clone()]);
+    assert!(client.try_set_guardians(&Some(GuardianSet { guardians: with_owner, threshold: 1 })).is_err());
+    client.set_guardians(&Some(GuardianSet { guardians, threshold: 2 }));
+    assert!(client.try_guardian_approve(&user_1, &GuardianAction::Pause).is_err());
+    assert!(client.try_guardian_approve(&guardian_1, &GuardianAction::Unpause).is_err());
+
+    // payouts stop once two guardians agree
+    client.start_pool();
+    client.pay_due(&owner, &1, &None, &None);
+    client.pay_due(&user_1, &1, &None, &None);
+    client.pay_due(&user_2, &1, &None, &None);
+    client.set_pool_winner(&1, &300, &user_1);
+    assert!(!client.guardian_approve(&guardian_1, &GuardianAction::Pause));
+    assert!(client.try_guardian_approve(&guardian_1, &GuardianAction::Pause).is_err());
+    assert_eq!(client.get_guardian_approvals(&GuardianAction::Pause), Vec::from_array(&env, [guardian_1.clone()]));
+    assert!(!client.is_paused());
+    assert!(client.guardian_approve(&guardian_2, &GuardianAction::Pause));
+    let event = env.events().all().last().unwrap();
+    assert_eq!(event.1, snbfi_events::topic(symbol_short!("guardian")).into_val(&env));
+    let action: GuardianAction = event.2.into_val(&env);
+    assert_eq!(action, GuardianAction::Pause);
+    assert!(client.is_paused());
+    assert_eq!(client.get_guardian_approvals(&GuardianAction::Pause).len(), 0);
+    assert!(client.try_claim_prize(&user_1).is_err());
+
+    // and start again the same way
+    client.guardian_approve(&guardian_2, &GuardianAction::Unpause);
+    client.guardian_approve(&guardian_3, &GuardianAction::Unpause);
+    assert!(!client.is_paused());
+    client.claim_prize(&user_1);
+
+    // they can stop an emergency withdrawal during its delay
+    assert!(client.try_guardian_approve(&guardian_1, &GuardianAction::VetoWithdrawal).is_err());
+    client.propose_emergency_withdraw(&owner, &10);
+    client.guardian_approve(&guardian_1, &GuardianAction::VetoWithdrawal);
+    client.guardian_approve(&guardian_3, &GuardianAction::VetoWithdrawal);
+    assert_eq!(client.get_emergency_withdrawal(), None);
+    advance_ledger(&env, EMERGENCY_DELAY_SECS);
+    assert!(client.try_execute_emergency_withdraw().is_err());
+}
+
+#[test]
+fn test_circuit_breaker() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, owner, user_1, user_2) = setup_pool_with_collateral(&env, 0);
+    let guardian = Address::generate(&env);
+    let limit = OutflowLimit { window_secs: DAY_IN_SECS, max_outflow: 300 };
+    // only guardians can unpause, so there have to be some
+    assert!(client.try_set_outflow_limit(&Some(limit.clone())).is_err());
+    client.set_guardians(&Some(GuardianSet { guardians: Vec::from_array(&env, [guardian.clone()]), threshold: 1 }));
+    client.set_outflow_limit(&Some(limit));
+    assert!(client.try_set_guardians(&None).is_err());
+    client.start_pool();
+
+    let pay_round = |iteration, winner: &Address| {
+        if iteration > 1 {
+            client.start_new_iteration(&iteration);
+        }
+        for member in [&owner, &user_1, &user_2] {
+            client.pay_due(member, &iteration, &None, &None);
+        }
+        client.set_pool_winner(&iteration, &300, winner);
+    };
+
+    // a pot a day is within the ceiling
+    pay_round(1, &user_1);
+    client.claim_prize(&user_1);
+    assert_eq!(client.get_outflow_window().outflow, 300);
+    assert!(!client.is_paused());
+
+    // the payout going over it trips the breaker
+    pay_round(2, &user_2);
+    client.claim_prize(&user_2);
+    assert_eq!(token.balance(&user_2), INITIAL_BALANCE - 200 + 300);
+    let events = env.events().all();
+    let tripped = events.get(events.len() - 2).unwrap();
+    assert_eq!(tripped.1, snbfi_events::topic(symbol_short!("tripped")).into_val(&env));
+    let data: (i128, i128) = tripped.2.into_val(&env);
+    assert_eq!(data, (600, 300));
+    assert!(client.is_paused());
+    pay_round(3, &owner);
+    assert!(client.try_claim_prize(&owner).is_err());
+
+    // and the guardians start payouts again with a fresh window
+    client.guardian_approve(&guardian, &GuardianAction::Unpause);
+    assert_eq!(client.get_outflow_window().outflow, 0);
+    client.claim_prize(&owner);
+    assert!(!client.is_paused());
+}
+
+#[test]
+fn test_collateral_release_paused() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, _, user_1, user_2) = setup_pool_with_collateral(&env, 50);
+    let guardian = Address::generate(&env);
+    client.set_guardians(&Some(GuardianSet { guardians: Vec::from_array(&env, [guardian.clone()]), threshold: 1 }));
+    client.set_outflow_limit(&Some(OutflowLimit { window_secs: DAY_IN_SECS, max_outflow: 300 }));
+
+    // collateral stays in the pool while payouts are paused
+    client.guardian_approve(&guardian, &GuardianAction::Pause);
+    assert!(client.try_leave(&user_1).is_err());
+    assert!(client.try_remove_member(&user_2).is_err());
+    assert_eq!(client.get_collateral(&user_1), 50);
+
+    // and counts toward the ceiling once they start again
+    client.guardian_approve(&guardian, &GuardianAction::Unpause);
+    client.leave(&user_1);
+    assert_eq!(token.balance(&user_1), INITIAL_BALANCE);
+    assert_eq!(client.get_outflow_window().outflow, 50);
+
+    // nor is it withdrawn from a completed pool while paused
+    let (client, _, owner, user_1, user_2) = setup_pool_with_collateral(&env, 50);
+    client.set_guardians(&Some(GuardianSet { guardians: Vec::from_array(&env, [guardian.clone()]), threshold: 1 }));
+    client.start_pool();
+    for (iteration, winner) in [(1, &owner), (2, &user_1), (3, &user_2)] {
+        if iteration > 1 {
+            client.start_new_iteration(&iteration);
+        }
+        for member in [&owner, &user_1, &user_2] {
+            client.pay_due(member, &iteration, &None, &None);
+        }
+        client.set_pool_winner(&iteration, &300, winner);
+    }
+    client.guardian_approve(&guardian, &GuardianAction::Pause);
+    assert!(client.try_withdraw_collateral(&user_1).is_err());
+    client.guardian_approve(&guardian, &GuardianAction::Unpause);
+    client.withdraw_collateral(&user_1);
+    assert_eq!(client.get_collateral(&user_1), 0);
+}
+
+#[test]
+fn test_disputes() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, owner, user_1, user_2) = setup_pool_with_collateral(&env, 0);
+    client.start_pool();
+    for member in [&owner, &user_1, &user_2] {
+        client.pay_due(member, &1, &None, &None);
+    }
+    client.set_pool_winner(&1, &300, &user_1);
+
+    // a member disputing the round holds back its prize
+    assert!(client.try_raise_dispute(&Address::generate(&env), &1, &7).is_err());
+    assert!(client.try_raise_dispute(&user_2, &3, &7).is_err());
+    let id = client.raise_dispute(&user_2, &1, &7);
+    let event = env.events().all().last().unwrap();
+    assert_eq!(event.1, snbfi_events::topics(symbol_short!("dispute"), user_2.clone()).into_val(&env));
+    let data: (u32, u32, u32) = event.2.into_val(&env);
+    assert_eq!(data, (id, 1, 7));
+    assert_eq!(client.get_dispute(&id).status, DisputeStatus::Open);
+    assert!(client.try_claim_prize(&user_1).is_err());
+
+    // until the owner, with no guardians around, settles it
+    assert!(client.try_resolve_dispute(&user_1, &id, &DisputeOutcome::Dismissed).is_err());
+    client.resolve_dispute(&owner, &id, &DisputeOutcome::Dismissed);
+    assert!(client.try_resolve_dispute(&owner, &id, &DisputeOutcome::Upheld).is_err());
+    client.claim_prize(&user_1);
+    assert_eq!(client.get_disputes(&0, &10), Vec::from_array(&env, [client.get_dispute(&id)]));
+    assert_eq!(client.get_dispute(&id).status, DisputeStatus::Dismissed);
+
+    // guardians take over from the owner when there are some
+    let (client, _, owner, user_1, _) = setup_pool_with_collateral(&env, 0);
+    let guardian = Address::generate(&env);
+    client.set_guardians(&Some(GuardianSet { guardians: Vec::from_array(&env, [guardian.clone()]), threshold: 1 }));
+    client.start_pool();
+    let id = client.raise_dispute(&user_1, &1, &1);
+    assert!(client.try_resolve_dispute(&owner, &id, &DisputeOutcome::Dismissed).is_err());
+    client.resolve_dispute(&guardian, &id, &DisputeOutcome::Upheld);
+    assert_eq!(client.get_dispute(&id).status, DisputeStatus::Upheld);
+}
+
+#[test]
+fn test_expel_vote() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, owner, user_1, user_2) = setup_pool_with_collateral(&env, 50);
+    client.start_pool();
+    client.pay_due(&owner, &1, &None, &None);
+    client.pay_due(&user_1, &1, &None, &None);
+    assert!(client.try_propose_expulsion(&user_1, &user_2).is_err());
+    client.mark_defaulted(&user_2);
+
+    // only paid-up members vote, and only on defaulters
+    assert!(client.try_propose_expulsion(&user_2, &user_1).is_err());
+    assert!(client.try_vote_expel(&owner, &user_2).is_err());
+    assert!(!client.propose_expulsion(&user_1, &user_2));
+    let event = env.events().all().last().unwrap();
+    assert_eq!(event.1, snbfi_events::topics(symbol_short!("expelvote"), user_2.clone()).into_val(&env));
+    assert!(client.try_propose_expulsion(&owner, &user_2).is_err());
+    assert!(client.try_vote_expel(&user_1, &user_2).is_err());
+    assert_eq!(client.get_expel_votes(&user_2), Vec::from_array(&env, [user_1.clone()]));
+
+    // the majority expels the member, whose collateral pays their due in the current pot
+    assert!(client.vote_expel(&owner, &user_2));
+    let event = env.events().all().last().unwrap();
+    assert_eq!(event.1, snbfi_events::topics(symbol_short!("expelled"), user_2.clone()).into_val(&env));
+    let data: (i128, u32) = event.2.into_val(&env);
+    assert_eq!(data, (50, 2));
+    assert!(client.try_get_subscriber_details(&user_2).is_err());
+    assert_eq!(client.get_collateral(&user_2), 0);
+    let state = client.get_state();
+    assert_eq!(state.pool_params.no_of_subs, 2);
+    assert_eq!(state.seized_collateral, 0);
+    assert_eq!(state.pool_iteration_map.get(1).unwrap().amount_collected, 250);
+    assert_eq!(client.get_expel_votes(&user_2).len(), 0);
+    assert_eq!(client.reconcile().discrepancy, 0);
+
+    // the round closes with the dues of the members left
+    client.set_pool_winner(&1, &200, &user_1);
+}
+
+#[test]
+fn test_terms_proposal() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, owner, user_1, user_2) = setup_pool_with_collateral(&env, 0);
+    let terms = PoolTerms { sub_amount: 200, frequency: Frequency::MONTH, no_of_subs: 4 };
+    assert!(client.try_propose_terms(&PoolTerms { no_of_subs: 2, ..terms.clone() }).is_err());
+
+    // the members who joined reject the change
+    let id = client.propose_terms(&terms);
+    let event = env.events().all().last().unwrap();
+    assert_eq!(event.1, snbfi_events::topics(symbol_short!("terms"), id).into_val(&env));
+    assert!(client.try_propose_terms(&terms).is_err());
+    assert!(client.try_vote_on_proposal(&owner, &id, &true).is_err());
+    client.vote_on_proposal(&user_1, &id, &false);
+    assert!(client.try_vote_on_proposal(&user_1, &id, &true).is_err());
+    assert_eq!(client.get_terms_proposal(), None);
+    assert_eq!(client.get_state().pool_params.sub_amount, SUB_AMOUNT);
+
+    // and approve it the next time
+    let id = client.propose_terms(&terms);
+    client.vote_on_proposal(&user_1, &id, &true);
+    assert_eq!(client.get_terms_proposal().unwrap().approvals, Vec::from_array(&env, [user_1.clone()]));
+    client.vote_on_proposal(&user_2, &id, &true);
+    let event = env.events().all().last().unwrap();
+    assert_eq!(event.1, snbfi_events::topics(symbol_short!("termsdone"), id).into_val(&env));
+    let approved: bool = event.2.into_val(&env);
+    assert!(approved);
+    let params = client.get_state().pool_params;
+    assert_eq!((params.sub_amount, params.frequency, params.no_of_subs), (200, Frequency::MONTH, 4));
+
+    // the owner withdraws a proposal before it is decided
+    let terms = PoolTerms { no_of_subs: NO_OF_SUBS, ..terms };
+    let id = client.propose_terms(&terms);
+    client.cancel_terms_proposal();
+    assert!(client.try_vote_on_proposal(&user_1, &id, &true).is_err());
+    let id = client.propose_terms(&terms);
+    client.vote_on_proposal(&user_1, &id, &true);
+    client.vote_on_proposal(&user_2, &id, &true);
+
+    // nothing is up for a vote once the pool started
+    client.start_pool();
+    assert!(client.try_propose_terms(&terms).is_err());
+}
+
+#[test]
+fn test_export_snapshot() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, owner, user_1, user_2) = setup_pool_with_collateral(&env, 0);
+    client.start_pool();
+    for user in [&owner, &user_1, &user_2] {
+        client.pay_due(user, &1, &None, &None);
+    }
+
+    let page = client.export_snapshot(&SnapshotSection::Subscribers, &0);
+    assert_eq!((page.version, page.total, page.next_cursor), (SNAPSHOT_VERSION, 3, 0));
+    assert_eq!(
+        page.entries.get(0).unwrap(),
+        SnapshotEntry::Subscriber(owner.clone(), client.get_subscriber_details(&owner))
+    );
+
+    let page = client.export_snapshot(&SnapshotSection::Iterations, &0);
+    assert_eq!(page.entries.len(), 1);
+    let pool_iteration = client.get_state().pool_iteration_map.get(1).unwrap();
+    assert_eq!(page.entries.get(0).unwrap(), SnapshotEntry::Iteration(1, pool_iteration));
+
+    // payments follow each other across the subscribers
+    let page = client.export_snapshot(&SnapshotSection::Payments, &1);
+    assert_eq!((page.total, page.entries.len(), page.next_cursor), (3, 2, 0));
+    let address = client.get_state().subcriber_map.keys().get(2).unwrap();
+    let record = client.get_payment_history(&address, &0, &1).get(0).unwrap();
+    assert_eq!(page.entries.get(1).unwrap(), SnapshotEntry::Payment(address, 0, record));
+    assert_eq!(client.export_snapshot(&SnapshotSection::Payments, &3).entries.len(), 0);
+}
+
+#[test]
+fn test_versioned_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, owner, user_1, user_2) = setup_pool_with_collateral(&env, 0);
+    client.start_pool();
+    client.pay_due(&user_1, &1, &None, &None);
+    let event = env.events().all().last().unwrap();
+    assert_eq!(
+        event.1,
+        (symbol_short!("SNBFI"), symbol_short!("v1"), symbol_short!("pay"), user_1.clone()).into_val(&env)
+    );
+    let payment: snbfi_events::Payment = event.2.into_val(&env);
+    assert_eq!(payment, snbfi_events::Payment { iteration: 1, payer: user_1.clone(), amount: SUB_AMOUNT, late: false });
+
+    client.pay_due(&owner, &1, &None, &None);
+    client.pay_due(&user_2, &1, &None, &None);
+    client.set_pool_winner(&1, &200, &user_2);
+    let win = env
+        .events()
+        .all()
+        .iter()
+        .find(|event| event.1 == snbfi_events::topics(snbfi_events::WIN, user_2.clone()).into_val(&env))
+        .unwrap();
+    let win: snbfi_events::Win = win.2.into_val(&env);
+    assert_eq!(win, snbfi_events::Win { iteration: 1, prize: 200 });
+}
+
+#[test]
+fn test_get_version() {
+    // the build id changes with every commit, no snapshot to keep in sync
+    let env = Env::new_with_config(EnvTestConfig { capture_snapshot_at_drop: false });
+    let client = create_pool_contract(&env);
+
+    // available before the pool is initialized
+    let (version, build_id) = client.get_version();
+    assert_eq!(version, SCHEMA_VERSION);
+    assert_eq!(build_id, Symbol::new(&env, env!("SNBFI_BUILD_ID")));
+}
+
+#[test]
+fn test_testutils_cycle() {
+    let env = Env::default();
+
+    let pool = testutils::TestPool::setup(&env, 4, 50, Frequency::DAY);
+    pool.run_cycle();
+    let state = pool.client.get_state();
+    assert_eq!(state.current_iteration, 4);
+    assert_eq!(state.pool_iteration_map.get(4).unwrap().winner, pool.subscribers.get(3).unwrap());
+    // everyone paid 4 dues and won one pot of 4
+    for subscriber in pool.subscribers.iter() {
+        assert_eq!(pool.token.balance(&subscriber), 400);
+    }
+    assert_eq!(pool.token.balance(&pool.client.address), 0);
+}
+
+#[test]
+fn test_check_invariants() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, owner, user_1, user_2) = setup_pool_with_collateral(&env, 50);
+    client.start_pool();
+    for member in [&owner, &user_1, &user_2] {
+        client.pay_due(member, &1, &None, &None);
+    }
+    client.set_pool_winner(&1, &300, &user_1);
+    assert_eq!(client.check_invariants(), Vec::new(&env));
+
+    // tokens leaving the contract outside of the pool accounting break both balance checks
+    env.as_contract(&client.address, || token.transfer(&client.address, &owner, &200));
+    assert_eq!(
+        client.check_invariants(),
+        Vec::from_array(&env, [symbol_short!("balance"), symbol_short!("solvent")])
+    );
+    token.transfer(&owner, &client.address, &200);
+
+    // dues collected without a payment behind them inflate the pot as well
+    env.as_contract(&client.address, || {
+        let mut state = storage::load_state(&env);
+        let loaded = state.clone();
+        let mut pool_iteration = state.pool_iteration_map.get(1).unwrap();
+        pool_iteration.amount_collected += 10;
+        state.pool_iteration_map.set(1, pool_iteration);
+        storage::save_state(&env, &loaded, &state);
+    });
+    assert_eq!(
+        client.check_invariants(),
+        Vec::from_array(&env, [symbol_short!("balance"), symbol_short!("collected")])
+    );
+}
+
+#[test]
+fn test_previews() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, owner, user_1, user_2) = setup_pool_with_collateral(&env, 50);
+    let user_3 = Address::generate(&env);
+    assert!(client.try_preview_join(&user_3).is_err());
+    client.leave(&user_2);
+    let preview = client.preview_join(&user_3);
+    assert_eq!(preview, JoinPreview { collateral: 50, due: SUB_AMOUNT, seats_left: 1 });
+    assert!(client.try_preview_join(&user_1).is_err());
+
+    // the preview matches what the payment takes, late fee included
+    client.set_min_subscribers(&2);
+    client.start_pool();
+    assert!(client.try_preview_pay(&user_3).is_err());
+    client.pay_due(&owner, &1, &None, &None);
+    assert!(client.try_preview_pay(&owner).is_err());
+    advance_ledger(&env, WEEK_IN_SECS + GRACE_PERIOD + 1);
+    let preview = client.preview_pay(&user_1);
+    assert!(preview.late);
+    assert_eq!((preview.iteration, preview.due), (1, SUB_AMOUNT));
+    let balance = token.balance(&user_1);
+    client.pay_due(&user_1, &1, &None, &None);
+    assert_eq!(balance - token.balance(&user_1), i128::from(preview.transfer));
+    assert_eq!(preview.transfer, SUB_AMOUNT + preview.late_fee);
+
+    // the pot previewed is the largest prize the winner can get
+    let payout = client.preview_winner_payout(&1);
+    assert_eq!((payout.collected, payout.commission, payout.pot), (200, 0, 200));
+    assert!(client.try_set_pool_winner(&1, &(payout.pot + 1), &owner).is_err());
+    client.set_pool_winner(&1, &payout.pot, &owner);
+    assert!(client.try_preview_winner_payout(&5).is_err());
+}
+
+#[test]
+fn test_insurance_reserve() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, owner, user_1, user_2) = setup_pool_with_collateral(&env, 0);
+    assert!(client.try_set_reserve_rate(&(MAX_RESERVE_BPS + 1)).is_err());
+    client.set_reserve_rate(&1_000);
+    client.start_pool();
+    assert!(client.try_set_reserve_rate(&500).is_err());
+
+    // a tenth of the pot goes to the reserve
+    for member in [&owner, &user_1, &user_2] {
+        client.pay_due(member, &1, &None, &None);
+    }
+    assert!(client.try_set_pool_winner(&1, &300, &owner).is_err());
+    client.set_pool_winner(&1, &270, &owner);
+    client.start_new_iteration(&2);
+    assert_eq!(client.get_reserve_balance(), 30);
+
+    // the reserve makes up for the due of the defaulter
+    client.mark_defaulted(&user_2);
+    client.pay_due(&owner, &2, &None, &None);
+    client.pay_due(&user_1, &2, &None, &None);
+    let payout = client.preview_winner_payout(&2);
+    assert_eq!((payout.reserve_cut, payout.reserve_backfill, payout.pot), (20, 30, 210));
+    client.set_pool_winner(&2, &210, &user_1);
+
+    // the rest is shared between the members once the pool completes
+    let event = env.events().all().last().unwrap();
+    assert_eq!(event.1, snbfi_events::topic(symbol_short!("reserve")).into_val(&env));
+    let released: (u32, u32) = event.2.into_val(&env);
+    assert_eq!(released, (20, 10));
+    assert_eq!(client.get_reserve_balance(), 0);
+    assert_eq!(client.get_subscriber_details(&owner).dividend_balance, 10);
+    assert_eq!(client.get_subscriber_details(&user_2).dividend_balance, 0);
+    assert_eq!(client.reconcile().discrepancy, 0);
+    assert_eq!(client.check_invariants().len(), 0);
+    assert_eq!(token.balance(&client.address), 500);
+}
+
+#[test]
+fn test_slashing_settlement() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, owner, user_1, user_2) = setup_pool_with_collateral(&env, 501);
+    client.set_insurance_premium(&100);
+    client.buy_seat_insurance(&user_1);
+    client.start_pool();
+    for member in [&owner, &user_1, &user_2] {
+        client.pay_due(member, &1, &None, &None);
+    }
+    // the fund pays the due user_1 misses in iteration 2
+    advance_ledger(&env, WEEK_IN_SECS + GRACE_PERIOD + 1);
+    client.start_new_iteration(&2);
+    client.pay_due(&owner, &2, &None, &None);
+    client.pay_due(&user_2, &2, &None, &None);
+    advance_ledger(&env, WEEK_IN_SECS + GRACE_PERIOD + 1);
+    client.start_new_iteration(&3);
+    assert_eq!(client.get_state().insurance_fund, 0);
+    client.mark_defaulted(&user_1);
+
+    // the due in the open pot first, then the fund, then equal shares with the remainder kept
+    let settlement = client.seize_collateral(&user_1);
+    assert_eq!(
+        settlement,
+        SlashSettlement { seized: 501, to_pot: 100, to_insurance: 100, to_members: 300, kept: 1 }
+    );
+    let state = client.get_state();
+    assert_eq!(state.pool_iteration_map.get(3).unwrap().amount_collected, 100);
+    assert_eq!(state.insurance_fund, 100);
+    assert_eq!(state.seized_collateral, 1);
+    assert_eq!(client.get_seat_insurance(&user_1).unwrap().repaid, 100);
+    assert_eq!(client.get_subscriber_details(&user_2).credit_balance, 150);
+    assert_eq!(client.get_subscriber_details(&user_1).credit_balance, 0);
+    assert_eq!(client.reconcile().discrepancy, 0);
+    assert_eq!(client.check_invariants().len(), 0);
+}
+
+#[test]
+fn test_referral_reward() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, owner, user_1, user_2) = setup_pool_with_collateral(&env, 0);
+    client.set_commission(&500);
+    client.set_referral_reward(&10);
+    client.leave(&user_2);
+    assert!(client.try_join(&user_2, &None, &Some(user_2.clone())).is_err());
+    client.join(&user_2, &None, &Some(user_1.clone()));
+    assert_eq!(
+        client.get_referral(&user_2),
+        Some(Referral { referrer: user_1.clone(), reward: 10, paid: false })
+    );
+    client.start_pool();
+    assert!(client.try_set_referral_reward(&20).is_err());
+
+    for (iteration, winner) in [(1, &owner), (2, &user_1), (3, &user_2)] {
+        if iteration > 1 {
+            client.start_new_iteration(&iteration);
+        }
+        for member in [&owner, &user_1, &user_2] {
+            client.pay_due(member, &iteration, &None, &None);
+        }
+        if iteration == 3 {
+            // the reward waits for the pool to complete
+            assert!(client.try_claim_referral_reward(&user_2).is_err());
+        }
+        client.set_pool_winner(&iteration, &250, winner);
+    }
+
+    // the reward comes out of the owner commission, once
+    let balance = token.balance(&user_1);
+    client.claim_referral_reward(&user_2);
+    assert_eq!(token.balance(&user_1), balance + 10);
+    assert_eq!(client.get_fee_balances().commission_unclaimed, 3 * 15 - 10);
+    assert!(client.get_referral(&user_2).unwrap().paid);
+    assert!(client.try_claim_referral_reward(&user_2).is_err());
+    assert!(client.try_claim_referral_reward(&user_1).is_err());
+    assert_eq!(client.reconcile().discrepancy, 0);
+}
+
+#[test]
+fn test_successor_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, owner, user_1, user_2) = setup_pool_with_collateral(&env, 50);
+    let factory = StubFactoryClient::new(&env, &env.register_contract(None, StubFactory));
+    let next = create_pool_contract(&env);
+    factory.set_next_pool(&next.address);
+    client.start_pool();
+
+    for (iteration, winner) in [(1, &owner), (2, &user_1), (3, &user_2)] {
+        if iteration > 1 {
+            client.start_new_iteration(&iteration);
+        }
+        for member in [&owner, &user_1, &user_2] {
+            client.pay_due(member, &iteration, &None, &None);
+        }
+        if iteration == 3 {
+            // the pool has to complete first
+            assert!(client.try_create_successor(&factory.address).is_err());
+        }
+        client.set_pool_winner(&iteration, &300, winner);
+    }
+    assert!(client.try_opt_into_successor(&user_1).is_err());
+    // only pools the factory deployed have a wasm to clone
+    assert!(client.try_create_successor(&factory.address).is_err());
+    factory.register(&client.address);
+
+    let successor = client.create_successor(&factory.address);
+    assert_eq!(successor, next.address);
+    assert_eq!(client.get_successor(), Some(successor.clone()));
+    assert_eq!(next.get_predecessor(), Some(client.address.clone()));
+    assert!(client.try_create_successor(&factory.address).is_err());
+    let params = next.get_state().pool_params;
+    assert_eq!(params.pool_owner, owner);
+    assert_eq!(params.no_of_subs, NO_OF_SUBS);
+    assert_eq!(params.sub_amount, SUB_AMOUNT);
+    assert_eq!(params.collateral_amount, 50);
+    assert_eq!(params.join_deadline, None);
+
+    // the collateral and reputation of the member move over in one call
+    let reputation = client.getReputation(&user_1);
+    assert!(reputation > 0);
+    let balance = token.balance(&user_1);
+    client.opt_into_successor(&user_1);
+    assert_eq!(token.balance(&user_1), balance);
+    assert_eq!(client.get_collateral(&user_1), 0);
+    assert_eq!(next.get_collateral(&user_1), 50);
+    assert_eq!(next.getReputation(&user_1), reputation);
+    assert!(next.get_state().subcriber_map.contains_key(user_1.clone()));
+    assert!(client.try_opt_into_successor(&user_1).is_err());
+    assert!(client.try_opt_into_successor(&Address::generate(&env)).is_err());
+    assert!(next.try_link_predecessor(&user_2, &None).is_err());
+}
+
+#[test]
+fn test_tiered_shares() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, owner, user_1, user_2) = setup_pool_with_collateral(&env, 0);
+    assert!(client.try_set_shares(&user_1, &0).is_err());
+    assert!(client.try_set_shares(&user_1, &(MAX_SHARES + 1)).is_err());
+    client.set_shares(&user_1, &2);
+    assert_eq!(client.get_subscriber_details(&user_1).shares, 2);
+    assert_eq!(client.get_subscriber_details(&user_2).shares, 1);
+    client.start_pool();
+    assert!(client.try_set_shares(&user_2, &2).is_err());
+
+    // the member holding two shares pays double
+    assert_eq!(client.get_due_amount(&user_1), 2 * i128::from(SUB_AMOUNT));
+    let balance = token.balance(&user_1);
+    client.pay_due(&user_1, &1, &None, &None);
+    assert_eq!(token.balance(&user_1), balance - 2 * i128::from(SUB_AMOUNT));
+    client.pay_due(&owner, &1, &None, &None);
+    // the pot isn't complete until every share is paid for
+    assert!(client.try_set_pool_winner(&1, &360, &owner).is_err());
+    client.pay_due(&user_2, &1, &None, &None);
+
+    // the dividend is shared by share: 40 over the three shares of the members who haven't won
+    client.set_pool_winner(&1, &360, &owner);
+    assert_eq!(client.get_subscriber_details(&user_1).dividend_balance, 26);
+    assert_eq!(client.get_subscriber_details(&user_2).dividend_balance, 13);
+    assert_eq!(client.reconcile().discrepancy, 0);
+}
+
+#[test]
+fn test_prize_assignment() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, owner, user_1, user_2) = setup_pool_with_collateral(&env, 0);
+    client.start_pool();
+    for member in [&owner, &user_1, &user_2] {
+        client.pay_due(member, &1, &None, &None);
+    }
+    // only a prize that was won can be offered, and only to another member
+    assert!(client.try_offer_prize(&user_1, &user_2, &250).is_err());
+    client.set_pool_winner(&1, &300, &user_1);
+    assert!(client.try_offer_prize(&user_1, &user_1, &250).is_err());
+    assert!(client.try_offer_prize(&user_1, &Address::generate(&env), &250).is_err());
+
+    client.offer_prize(&user_1, &user_2, &250);
+    assert_eq!(client.get_prize_offer(&user_1).unwrap().price, 250);
+    assert!(client.try_accept_prize_offer(&owner, &user_1).is_err());
+
+    // the price and the prize change hands in the same call
+    let seller_balance = token.balance(&user_1);
+    let buyer_balance = token.balance(&user_2);
+    client.accept_prize_offer(&user_2, &user_1);
+    assert_eq!(token.balance(&user_1), seller_balance + 250);
+    assert_eq!(token.balance(&user_2), buyer_balance - 250);
+    assert_eq!(client.get_prize_offer(&user_1), None);
+    let assignment = client.get_iterations(&1, &1).get(0).unwrap().prize_assignments.get(0).unwrap();
+    assert_eq!(assignment.from, user_1);
+    assert_eq!(assignment.to, user_2);
+    assert_eq!(assignment.price, 250);
+    assert_eq!(assignment.unclaimed, 300);
+    assert!(client.try_offer_prize(&user_1, &owner, &100).is_err());
+
+    // the buyer signs for the claim and gets paid
+    client.claim_prize(&user_1);
+    assert_eq!(env.auths()[0].0, user_2);
+    assert_eq!(token.balance(&user_2), buyer_balance - 250 + 300);
+    assert_eq!(token.balance(&user_1), seller_balance + 250);
+    assert_eq!(client.reconcile().discrepancy, 0);
+}
+
+#[test]
+fn test_arrears_interest() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, owner, user_1, user_2) = setup_pool_with_collateral(&env, 0);
+    assert!(client.try_set_arrears_interest(&(MAX_ARREARS_INTEREST_BPS + 1)).is_err());
+    client.set_arrears_interest(&100);
+    assert_eq!(client.get_arrears_interest(), 100);
+    client.start_pool();
+
+    // user_2 misses the first due, it is rolled into their arrears when the iteration closes
+    client.pay_due(&owner, &1, &None, &None);
+    client.pay_due(&user_1, &1, &None, &None);
+    client.approve_shortfall(&1, &String::from_str(&env, "user_2 is travelling"));
+    client.set_pool_winner(&1, &200, &owner);
+    client.start_new_iteration(&2);
+    assert_eq!(client.get_arrears(&user_2), Arrears { principal: SUB_AMOUNT, interest: 0 });
+    assert!(client.try_pay_due(&user_2, &1, &None, &None).is_err());
+
+    // interest accrues on the arrears carried through the next iteration
+    for member in [&owner, &user_1, &user_2] {
+        client.pay_due(member, &2, &None, &None);
+    }
+    client.set_pool_winner(&2, &300, &user_1);
+    client.start_new_iteration(&3);
+    assert_eq!(client.get_arrears(&user_2), Arrears { principal: SUB_AMOUNT, interest: 1 });
+    assert_eq!(client.get_due_amount(&user_2), i128::from(SUB_AMOUNT) + 1 + i128::from(SUB_AMOUNT));
+
+    // the interest is paid first, then the missed dues which go to the next pot
+    client.pay_arrears(&user_2, &Some(50));
+    assert_eq!(client.get_arrears(&user_2), Arrears { principal: 51, interest: 0 });
+    let balance = token.balance(&user_2);
+    client.pay_arrears(&user_2, &None);
+    assert_eq!(token.balance(&user_2), balance - 51);
+    assert_eq!(client.get_arrears(&user_2), Arrears { principal: 0, interest: 0 });
+    assert!(client.try_pay_arrears(&user_2, &None).is_err());
+    assert_eq!(client.get_carryover(), SUB_AMOUNT);
+    assert_eq!(client.reconcile().discrepancy, 0);
+}
+
+#[test]
+fn test_trial_balance() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, owner, user_1, user_2) = setup_pool_with_collateral(&env, 50);
+    client.set_protection_limits(&1_000, &1_000);
+    client.start_pool();
+    client.pay_due(&owner, &1, &None, &None);
+    client.pay_due(&user_1, &1, &None, &None);
+    advance_ledger(&env, WEEK_IN_SECS + GRACE_PERIOD + 1);
+    client.pay_due(&user_2, &1, &None, &None);
+    client.set_pool_winner(&1, &250, &user_1);
+    client.claim_prize(&user_1);
+    client.claim_dividends(&user_2);
+
+    let trial_balance = client.trial_balance();
+    assert!(trial_balance.balanced);
+    assert_eq!(trial_balance.total_debits, trial_balance.total_credits);
+    let balance_of = |account| {
+        trial_balance
+            .accounts
+            .iter()
+            .find(|entry| entry.account == account)
+            .unwrap()
+            .balance
+    };
+    // 150 collateral and 310 dues and late fee came in, the prize and a dividend went out
+    assert_eq!(balance_of(LedgerAccount::Cash), 185);
+    assert_eq!(balance_of(LedgerAccount::Cash), token.balance(&client.address));
+    assert_eq!(balance_of(LedgerAccount::Pot), 0);
+    assert_eq!(balance_of(LedgerAccount::Dividends), 25);
+    assert_eq!(balance_of(LedgerAccount::Fees), 10);
+    assert_eq!(balance_of(LedgerAccount::Collateral), 150);
+    assert_eq!(balance_of(LedgerAccount::Members), 0);
+    let cash = trial_balance.accounts.get(0).unwrap();
+    assert_eq!(cash.debits, 460);
+    assert_eq!(cash.credits, 275);
+}
+
+#[test]
+fn test_subscriber_profile() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _owner, user_1, _user_2) = setup_pool(&env);
+    assert_eq!(client.get_profile(&user_1), None);
+    let name = String::from_str(&env, "Asha");
+    let contact_hash = BytesN::from_array(&env, &[7; 32]);
+    client.set_profile(&user_1, &name, &Some(contact_hash.clone()));
+    assert_eq!(
+        client.get_profile(&user_1),
+        Some(Profile { display_name: name, contact_hash: contact_hash.into() })
+    );
+
+    // names are short and only members get a profile
+    assert!(client.try_set_profile(&user_1, &String::from_str(&env, ""), &None).is_err());
+    let long_name = String::from_str(&env, "a name well over thirty-two bytes");
+    assert!(client.try_set_profile(&user_1, &long_name, &None).is_err());
+    assert!(client.try_set_profile(&Address::generate(&env), &String::from_str(&env, "Ravi"), &None).is_err());
+
+    // the owner can take an abusive name down
+    client.clear_profile(&user_1);
+    assert_eq!(client.get_profile(&user_1), None);
+    assert!(client.try_clear_profile(&user_1).is_err());
+}
+
+#[test]
+fn test_due_reminders() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, owner, user_1, user_2) = setup_pool(&env);
+    assert!(client.try_emit_reminders().is_err());
+    client.start_pool();
+    client.pay_due(&owner, &1, &None, &None);
+
+    // anyone can list the members still to pay
+    let unpaid = client.emit_reminders();
+    assert_eq!(unpaid.len(), 2);
+    assert!(unpaid.contains(&user_1) && unpaid.contains(&user_2));
+    let event = env.events().all().last().unwrap();
+    assert_eq!(event.1, snbfi_events::topics(symbol_short!("duesoon"), 1u32).into_val(&env));
+    let listed: Vec<Address> = event.2.into_val(&env);
+    assert_eq!(listed, unpaid);
+
+    // once a day
+    assert!(client.try_emit_reminders().is_err());
+    advance_ledger(&env, DAY_IN_SECS);
+    client.pay_due(&user_1, &1, &None, &None);
+    assert_eq!(client.emit_reminders(), Vec::from_array(&env, [user_2.clone()]));
+
+    // and only while the dues are on time
+    advance_ledger(&env, WEEK_IN_SECS + GRACE_PERIOD);
+    assert!(client.try_emit_reminders().is_err());
+}
+
+#[test]
+fn test_targeted_views() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, owner, _user_1, _user_2) = setup_pool(&env);
+    let params = client.get_pool_params();
+    assert_eq!(params.pool_owner, owner);
+    assert_eq!(params.no_of_subs, NO_OF_SUBS);
+    assert_eq!(params.sub_amount, SUB_AMOUNT);
+    assert_eq!(client.get_current_iteration(), 0);
+    assert!(client.try_get_iteration(&1).is_err());
+
+    client.start_pool();
+    client.pay_due(&owner, &1, &None, &None);
+    assert_eq!(client.get_current_iteration(), 1);
+    let iteration = client.get_iteration(&1);
+    assert_eq!(iteration.amount_collected, SUB_AMOUNT);
+    assert_eq!(iteration, client.get_state().pool_iteration_map.get(1).unwrap());
+}
+
+#[test]
+fn test_member_and_iteration_entries() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, owner, user_1, user_2) = setup_pool(&env);
+    client.start_pool();
+    client.pay_due(&owner, &1, &None, &None);
+    client.pay_due(&user_1, &1, &None, &None);
+    client.pay_due(&user_2, &1, &None, &None);
+    client.set_pool_winner(&1, &250, &user_1);
+    client.start_new_iteration(&2);
+
+    // the instance entry keeps the pool without its members and iterations, each has an entry of its own
+    let pool_state = client.get_pool_state();
+    assert_eq!(pool_state.current_iteration, 2);
+    env.as_contract(&client.address, || {
+        let pool: PoolState = env.storage().instance().get(&storage::STATE).unwrap();
+        assert_eq!(pool, pool_state);
+        let winner: Subscriber = env.storage().persistent().get(&(storage::MEMBER, user_1.clone())).unwrap();
+        assert_eq!(winner.winner_at_iter, 1);
+        let closed: PoolIterationParams = env.storage().persistent().get(&(storage::ITERATION, 1u32)).unwrap();
+        assert_eq!(closed.winner, user_1);
+        assert!(env.storage().persistent().has(&(storage::ITERATION, 2u32)));
+    });
+    let state = client.get_state();
+    assert_eq!(state.subcriber_map.len(), 3);
+    assert_eq!(state.pool_iteration_map.len(), 2);
+
+    // closing an iteration reads the winner and that iteration alone, the dividend waits for the others
+    client.pay_due(&owner, &2, &None, &None);
+    client.pay_due(&user_1, &2, &None, &None);
+    client.pay_due(&user_2, &2, &None, &None);
+    let owner_entry: Subscriber = client.get_subscriber_details(&owner);
+    env.as_contract(&client.address, || {
+        env.storage().persistent().remove(&(storage::ITERATION, 1u32));
+        env.storage().persistent().remove(&(storage::MEMBER, owner.clone()));
+    });
+    client.set_pool_winner(&2, &250, &user_2);
+    assert_eq!(client.get_pool_winner(&2), user_2);
+    env.as_contract(&client.address, || {
+        env.storage().persistent().set(&(storage::MEMBER, owner.clone()), &owner_entry);
+    });
+    assert_eq!(client.get_subscriber_details(&owner).dividend_balance, owner_entry.dividend_balance + 50);
+
+    // a member leaving drops their entry
+    let (client, owner, user_1, _user_2) = setup_pool(&env);
+    client.leave(&user_1);
+    env.as_contract(&client.address, || {
+        assert!(!env.storage().persistent().has(&(storage::MEMBER, user_1.clone())));
+    });
+
+    // joining and setters don't read the entries of the other members
+    env.as_contract(&client.address, || {
+        env.storage().persistent().remove(&(storage::MEMBER, owner.clone()));
+    });
+    client.join(&user_1, &None, &None);
+    client.set_min_subscribers(&2);
+    assert_eq!(client.get_min_subscribers(), 2);
+    assert!(client.try_get_state().is_err());
+}
+
+#[test]
+fn test_restore_hints() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _owner, _user_1, _user_2) = setup_pool(&env);
+    client.start_pool();
+
+    // an entry for every member then one for every started iteration
+    let hints = client.restore_hints();
+    assert_eq!(hints.len(), NO_OF_SUBS + 1);
+    for (index, member) in client.get_state().subcriber_map.keys().iter().enumerate() {
+        let key: (Symbol, Address) = hints.get(index as u32).unwrap().into_val(&env);
+        assert_eq!(key, (storage::MEMBER, member));
+    }
+    let key: (Symbol, u32) = hints.get(NO_OF_SUBS).unwrap().into_val(&env);
+    assert_eq!(key, (storage::ITERATION, 1));
+}
+
+#[test]
+fn test_entry_ttl_extended_on_read() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _owner, user_1, _user_2) = setup_pool(&env);
+    let key = (storage::MEMBER, user_1.clone());
+    let ttl = || env.as_contract(&client.address, || env.storage().persistent().get_ttl(&key));
+    let instance_ttl = || env.as_contract(&client.address, || env.storage().instance().get_ttl());
+    let fresh = ttl();
+    assert_eq!(instance_ttl(), fresh);
+
+    // the entries age without being written, reading them extends them again
+    env.ledger().with_mut(|l| l.sequence_number += fresh - BALANCE_BUMP_THREASHOLD + 1);
+    assert!(ttl() < BALANCE_BUMP_THREASHOLD);
+    assert!(instance_ttl() < BALANCE_BUMP_THREASHOLD);
+    client.get_subscriber_details(&user_1);
+    assert_eq!(ttl(), fresh);
+    client.get_pool_state();
+    assert_eq!(instance_ttl(), fresh);
+}
+
+#[test]
+fn test_owner_council() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, _, _) = setup_pool(&env);
+    let [owner_1, owner_2, owner_3] = [0; 3].map(|_| Address::generate(&env));
+    let owners = Vec::from_array(&env, [owner_1.clone(), owner_2.clone(), owner_3.clone()]);
+    assert!(client.try_set_owner_council(&Some(OwnerCouncil { owners: owners.clone(), threshold: 0 })).is_err());
+    assert!(client.try_set_owner_council(&Some(OwnerCouncil { owners: owners.clone(), threshold: 4 })).is_err());
+    client.set_owner_council(&Some(OwnerCouncil { owners, threshold: 2 }));
+    assert_eq!(client.get_owner_council().unwrap().threshold, 2);
+
+    // the owner's signature no longer carries admin operations, the council approves each one
+    assert!(client.try_set_commission(&100).is_err());
+    let operation: Vec<Val> = (Symbol::new(&env, "set_commission"), 100u32).into_val(&env);
+    assert!(client.try_approve_operation(&Address::generate(&env), &operation).is_err());
+    assert_eq!(client.approve_operation(&owner_1, &operation), 1);
+    assert!(client.try_approve_operation(&owner_1, &operation).is_err());
+    assert!(client.try_set_commission(&100).is_err());
+    assert_eq!(client.approve_operation(&owner_3, &operation), 2);
+    assert_eq!(client.get_operation_approvals(&operation), Vec::from_array(&env, [owner_1.clone(), owner_3.clone()]));
+
+    // approvals are for the exact arguments
+    assert!(client.try_set_commission(&200).is_err());
+    client.set_commission(&100);
+    let event = env.events().all().last().unwrap();
+    assert_eq!(event.1, snbfi_events::topic(symbol_short!("opexec")).into_val(&env));
+    assert_eq!(client.get_commission(), 100);
+
+    // and used up by the operation
+    assert_eq!(client.get_operation_approvals(&operation).len(), 0);
+    assert!(client.try_set_commission(&100).is_err());
+
+    // replacing the council drops the approvals given under the old one
+    let pending: Vec<Val> = (Symbol::new(&env, "set_commission"), 300u32).into_val(&env);
+    client.approve_operation(&owner_1, &pending);
+    let owner_4 = Address::generate(&env);
+    let council = OwnerCouncil { owners: Vec::from_array(&env, [owner_2.clone(), owner_3.clone(), owner_4.clone()]), threshold: 2 };
+    let operation: Vec<Val> = (Symbol::new(&env, "set_owner_council"), Some(council.clone())).into_val(&env);
+    client.approve_operation(&owner_1, &operation);
+    client.approve_operation(&owner_2, &operation);
+    client.set_owner_council(&Some(council));
+    assert_eq!(client.get_operation_approvals(&pending).len(), 0);
+    assert!(client.try_approve_operation(&owner_1, &pending).is_err());
+    client.approve_operation(&owner_2, &pending);
+    assert!(client.try_set_commission(&300).is_err());
+
+    // the council can hand the role back to the owner
+    let operation: Vec<Val> = (Symbol::new(&env, "set_owner_council"), None::<OwnerCouncil>).into_val(&env);
+    client.approve_operation(&owner_3, &operation);
+    client.approve_operation(&owner_4, &operation);
+    client.set_owner_council(&None);
+    assert_eq!(client.get_owner_council(), None);
+    client.set_commission(&200);
+    assert_eq!(client.get_commission(), 200);
+}
+
+#[test]
+fn test_freeze_member() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, owner, user_1, user_2) = setup_pool_with_collateral(&env, 0);
+    assert!(client.try_freeze_member(&Address::generate(&env)).is_err());
+    assert!(client.try_unfreeze_member(&user_1).is_err());
+    client.freeze_member(&user_1);
+    let event = env.events().all().last().unwrap();
+    assert_eq!(event.1, snbfi_events::topics(symbol_short!("frozen"), user_1.clone()).into_val(&env));
+    assert!(client.try_freeze_member(&user_1).is_err());
+    assert_eq!(client.get_frozen_members(), Vec::from_array(&env, [user_1.clone()]));
+
+    // a frozen member keeps paying but can't win
+    client.start_pool();
+    client.pay_due(&owner, &1, &None, &None);
+    client.pay_due(&user_1, &1, &None, &None);
+    client.pay_due(&user_2, &1, &None, &None);
+    assert!(client.try_set_pool_winner(&1, &300, &user_1).is_err());
+    client.set_pool_winner(&1, &300, &user_2);
+
+    // nor claim
+    client.freeze_member(&user_2);
+    assert!(client.try_claim_prize(&user_2).is_err());
+    client.unfreeze_member(&user_2);
+    let event = env.events().all().last().unwrap();
+    assert_eq!(event.1, snbfi_events::topics(symbol_short!("unfrozen"), user_2.clone()).into_val(&env));
+    client.claim_prize(&user_2);
+    assert_eq!(client.get_frozen_members(), Vec::from_array(&env, [user_1.clone()]));
+
+    // nor hand the seat to another address
+    let new_user = Address::generate(&env);
+    assert!(client.try_rotate_address(&user_1, &new_user).is_err());
+    assert!(client.try_transfer_slot(&user_1, &new_user).is_err());
+    assert!(client.try_substitute_member(&user_1, &new_user).is_err());
+    client.unfreeze_member(&user_1);
+    client.rotate_address(&user_1, &new_user);
+    assert_eq!(client.get_subscriber_details(&new_user).last_paid_iter, 1);
+}
+
+#[test]
+fn test_remove_member() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, owner, user_1, user_2) = setup_pool_with_collateral(&env, 50);
+    assert!(client.try_remove_member(&owner).is_err());
+    assert!(client.try_remove_member(&Address::generate(&env)).is_err());
+    client.remove_member(&user_1);
+    let event = env.events().all().last().unwrap();
+    assert_eq!(event.1, snbfi_events::topics(symbol_short!("removed"), user_1.clone()).into_val(&env));
+    let refunded: i128 = event.2.into_val(&env);
+    assert_eq!(refunded, 50);
+    assert_eq!(client.get_state().subcriber_map.len(), 2);
+    assert_eq!(client.get_collateral(&user_1), 0);
+    assert_eq!(token.balance(&user_1), INITIAL_BALANCE);
+    assert!(client.try_remove_member(&user_1).is_err());
+
+    // the seat is free for someone else
+    let user_3 = Address::generate(&env);
+    TokenAdminClient::new(&env, &token.address).mint(&user_3, &INITIAL_BALANCE);
+    client.join(&user_3, &None, &None);
+
+    // members stay once the pool has started
+    client.start_pool();
+    assert!(client.try_remove_member(&user_2).is_err());
+}
+
+#[test]
+fn test_substitute_member() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, owner, user_1, user_2) = setup_pool_with_collateral(&env, 50);
+    client.start_pool();
+
+    // user_2 misses the first due and has to drop out
+    client.pay_due(&owner, &1, &None, &None);
+    client.pay_due(&user_1, &1, &None, &None);
+    client.approve_shortfall(&1, &String::from_str(&env, "user_2 dropped out"));
+    client.set_pool_winner(&1, &200, &owner);
+    client.start_new_iteration(&2);
+    assert_eq!(client.get_arrears(&user_2), Arrears { principal: SUB_AMOUNT, interest: 0 });
+
+    let incoming = Address::generate(&env);
+    TokenAdminClient::new(&env, &token.address).mint(&incoming, &INITIAL_BALANCE);
+    assert!(client.try_substitute_member(&owner, &incoming).is_err());
+    assert!(client.try_substitute_member(&user_2, &user_1).is_err());
+    client.substitute_member(&user_2, &incoming);
+    let event = env.events().all().last().unwrap();
+    assert_eq!(event.1, snbfi_events::topics(symbol_short!("subst"), user_2.clone()).into_val(&env));
+
+    // the incoming member pays the missed due and takes over the seat, still able to win
+    assert_eq!(token.balance(&incoming), INITIAL_BALANCE - 50 - i128::from(SUB_AMOUNT));
+    assert_eq!(token.balance(&user_2), INITIAL_BALANCE);
+    let subr = client.get_subscriber_details(&incoming);
+    assert_eq!(subr.winner_at_iter, 0);
+    assert_eq!(client.get_arrears(&incoming), Arrears { principal: 0, interest: 0 });
+    assert!(client.try_get_subscriber_details(&user_2).is_err());
+    assert_eq!(client.get_carryover(), SUB_AMOUNT);
+
+    for member in [&owner, &user_1, &incoming] {
+        client.pay_due(member, &2, &None, &None);
+    }
+    client.set_pool_winner(&2, &300, &incoming);
+    assert_eq!(client.reconcile().discrepancy, 0);
+}
+
+#[test]
+fn test_correct_iteration() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, owner, user_1, user_2) = setup_pool_with_collateral(&env, 0);
+    let [guardian_1, guardian_2] = [0; 2].map(|_| Address::generate(&env));
+    let guardians = Vec::from_array(&env, [guardian_1.clone(), guardian_2.clone()]);
+    client.set_guardians(&Some(GuardianSet { guardians, threshold: 2 }));
+    client.start_pool();
+    for member in [&owner, &user_1, &user_2] {
+        client.pay_due(member, &1, &None, &None);
+    }
+    // user_1 was picked by mistake
+    client.set_pool_winner(&1, &300, &user_1);
+    client.start_new_iteration(&2);
+
+    // the guardians have to approve the correction
+    assert!(client.try_correct_iteration(&1, &user_2, &250).is_err());
+    client.guardian_approve(&guardian_1, &GuardianAction::CorrectIteration(1));
+    assert!(client.try_correct_iteration(&1, &user_2, &250).is_err());
+    assert!(client.guardian_approve(&guardian_2, &GuardianAction::CorrectIteration(1)));
+    assert!(client.try_correct_iteration(&1, &user_2, &301).is_err());
+    assert!(client.try_correct_iteration(&2, &user_2, &250).is_err());
+
+    let correction = client.correct_iteration(&1, &user_2, &250);
+    assert_eq!(
+        correction,
+        Correction { winner_before: user_1.clone(), winner_after: user_2.clone(), prize_before: 300, prize_after: 250 }
+    );
+    let event = env.events().all().last().unwrap();
+    assert_eq!(event.1, snbfi_events::topics(symbol_short!("corrected"), 1u32).into_val(&env));
+    let emitted: Correction = event.2.into_val(&env);
+    assert_eq!(emitted, correction);
+
+    // user_1 can win again, and the prize taken off joins the next pot
+    assert_eq!(client.get_pool_winner(&1), user_2);
+    assert_eq!(client.get_subscriber_details(&user_1).winner_at_iter, 0);
+    assert_eq!(client.get_subscriber_details(&user_2).prize_money, 250);
+    assert_eq!(client.get_iteration(&2).carried_in, 50);
+    assert!(client.try_claim_prize(&user_1).is_err());
+
+    // each correction needs its own approval, and none once the prize is paid out
+    assert!(client.try_correct_iteration(&1, &user_1, &300).is_err());
+    client.claim_prize(&user_2);
+    client.guardian_approve(&guardian_1, &GuardianAction::CorrectIteration(1));
+    client.guardian_approve(&guardian_2, &GuardianAction::CorrectIteration(1));
+    assert!(client.try_correct_iteration(&1, &user_2, &200).is_err());
+    assert_eq!(client.reconcile().discrepancy, 0);
+}
+
+#[test]
+fn test_settle_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, owner, user_1, user_2) = setup_pool_with_collateral(&env, 50);
+    client.start_pool();
+    for (iteration, prize, winner) in [(1, 250, &owner), (2, 300, &user_1), (3, 290, &user_2)] {
+        if iteration > 1 {
+            client.start_new_iteration(&iteration);
+        }
+        assert!(client.try_settle_pool().is_err());
+        for member in [&owner, &user_1, &user_2] {
+            client.pay_due(member, &iteration, &None, &None);
+        }
+        client.set_pool_winner(&iteration, &prize, winner);
+    }
+
+    // everyone has won, the dividend of the last iteration had nobody to go to
+    assert_eq!(client.get_carryover(), 10);
+    let summary = client.settle_pool();
+    assert_eq!(
+        summary,
+        SettlementSummary {
+            iterations: 3,
+            total_collected: 900,
+            total_prizes: 840,
+            total_dividends: 60,
+            dust_swept: 10,
+            collateral_returned: 150
+        }
+    );
+    let event = env.events().all().last().unwrap();
+    assert_eq!(event.1, snbfi_events::topic(symbol_short!("settled")).into_val(&env));
+    assert_eq!(client.get_state().status, PoolStatus::Completed);
+    assert_eq!(client.get_carryover(), 0);
+    assert_eq!(client.get_collateral(&user_1), 0);
+
+    // the dust is shared out, the remainder to the owner, and stays claimable
+    assert_eq!(client.get_subscriber_details(&owner).dividend_balance, 4);
+    assert_eq!(client.get_subscriber_details(&user_1).dividend_balance, 28);
+    assert_eq!(client.get_subscriber_details(&user_2).dividend_balance, 28);
+    client.claim_dividends(&user_1);
+    client.claim_prize(&user_2);
+    assert_eq!(token.balance(&user_2), INITIAL_BALANCE - 300 + 290);
+    assert!(client.try_settle_pool().is_err());
+    assert_eq!(client.reconcile().discrepancy, 0);
+    assert!(client.trial_balance().balanced);
+}
+
+#[test]
+fn test_archive_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, owner, user_1, user_2) = setup_pool_with_collateral(&env, 50);
+    client.start_pool();
+    for (iteration, winner) in [(1, &owner), (2, &user_1), (3, &user_2)] {
+        if iteration > 1 {
+            client.start_new_iteration(&iteration);
+        }
+        for member in [&owner, &user_1, &user_2] {
+            client.pay_due(member, &iteration, &None, &None);
+        }
+        client.set_pool_winner(&iteration, &300, winner);
+    }
+    assert!(client.try_archive_pool().is_err());
+    client.settle_pool();
+
+    // every prize has to be claimed first
+    client.claim_prize(&owner);
+    client.claim_prize(&user_1);
+    assert!(client.try_archive_pool().is_err());
+    client.claim_prize(&user_2);
+    assert_eq!(client.get_archive(), None);
+
+    let archive = client.archive_pool();
+    let winners = Vec::from_array(&env, [owner.clone(), user_1.clone(), user_2.clone()]);
+    assert_eq!(
+        archive,
+        PoolArchive {
+            iterations: 3,
+            members: 3,
+            total_collected: 900,
+            total_prizes: 900,
+            total_dividends: 0,
+            winners_hash: env.crypto().sha256(&winners.to_xdr(&env)).to_bytes(),
+            archived_at: env.ledger().timestamp()
+        }
+    );
+    let event = env.events().all().last().unwrap();
+    assert_eq!(event.1, snbfi_events::topic(symbol_short!("archived")).into_val(&env));
+    assert_eq!(client.get_archive(), Some(archive));
+
+    // the granular records are gone, the summary stays
+    let state = client.get_state();
+    assert_eq!(state.status, PoolStatus::Archived);
+    assert!(state.subcriber_map.is_empty());
+    assert!(client.try_get_subscriber_details(&user_1).is_err());
+    assert!(client.try_get_iteration(&1).is_err());
+    assert_eq!(client.get_payment_history(&user_1, &0, &10).len(), 0);
+    assert_eq!(client.restore_hints().len(), 0);
+    env.as_contract(&client.address, || {
+        assert!(!env.storage().persistent().has(&(storage::MEMBER, user_1.clone())));
+        assert!(!env.storage().persistent().has(&(storage::ITERATION, 1u32)));
+    });
+    assert!(client.try_archive_pool().is_err());
+}
+
+#[test]
+fn test_set_pool_winner_requires_owner() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, owner, user_1, user_2) = setup_pool(&env);
+    client.start_pool();
+    for member in [&owner, &user_1, &user_2] {
+        client.pay_due(member, &1, &None, &None);
+    }
+
+    // nobody signs for the owner
+    env.set_auths(&[]);
+    assert!(client.try_set_pool_winner(&1, &300, &user_1).is_err());
+
+    // a council needs to approve the exact pick
+    env.mock_all_auths();
+    let [owner_1, owner_2] = [0; 2].map(|_| Address::generate(&env));
+    let owners = Vec::from_array(&env, [owner_1.clone(), owner_2.clone()]);
+    client.set_owner_council(&Some(OwnerCouncil { owners, threshold: 2 }));
+    assert!(client.try_set_pool_winner(&1, &300, &user_1).is_err());
+    let operation: Vec<Val> = (Symbol::new(&env, "set_pool_winner"), 1u32, 300u32, user_1.clone()).into_val(&env);
+    client.approve_operation(&owner_1, &operation);
+    client.approve_operation(&owner_2, &operation);
+    assert!(client.try_set_pool_winner(&1, &300, &user_2).is_err());
+    client.set_pool_winner(&1, &300, &user_1);
+    assert_eq!(client.get_pool_winner(&1), user_1);
+}
+
+#[test]
+fn test_cancel_vote_follows_seat() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    // a rotated voter keeps the one vote it already cast
+    let (client, _, owner, user_1, user_2) = setup_pool_with_collateral(&env, 0);
+    client.vote_cancel(&user_2);
+    let new_user = Address::generate(&env);
+    client.rotate_address(&user_2, &new_user);
+    assert!(client.try_vote_cancel(&new_user).is_err());
+    assert!(client.try_vote_cancel(&user_2).is_err());
+    assert_eq!(client.get_state().status, PoolStatus::Open);
+
+    // the vote of a member who left is forgotten, the others can still vote
+    client.leave(&new_user);
+    client.vote_cancel(&owner);
+    assert_eq!(client.get_state().status, PoolStatus::Open);
+    client.vote_cancel(&user_1);
+    assert_eq!(client.get_state().status, PoolStatus::Cancelled);
+}
+
+#[test]
+fn test_expel_vote_follows_seat() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, owner, user_1, user_2) = setup_pool_with_collateral(&env, 50);
+    client.start_pool();
+    client.pay_due(&owner, &1, &None, &None);
+    client.pay_due(&user_1, &1, &None, &None);
+    client.mark_defaulted(&user_2);
+    assert!(!client.propose_expulsion(&user_1, &user_2));
+
+    // a rotated voter can't vote a second time
+    let new_user = Address::generate(&env);
+    client.rotate_address(&user_1, &new_user);
+    assert_eq!(client.get_expel_votes(&user_2), Vec::from_array(&env, [new_user.clone()]));
+    assert!(client.try_vote_expel(&new_user, &user_2).is_err());
+
+    // the open vote follows the defaulter to their new address
+    let new_defaulter = Address::generate(&env);
+    client.rotate_address(&user_2, &new_defaulter);
+    assert_eq!(client.get_expel_votes(&user_2).len(), 0);
+    assert_eq!(client.get_expel_votes(&new_defaulter), Vec::from_array(&env, [new_user.clone()]));
+    assert!(client.vote_expel(&owner, &new_defaulter));
+    assert!(client.try_get_subscriber_details(&new_defaulter).is_err());
+}
+
+#[test]
+fn test_default_shortfall_counts_paid_parts() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, owner, user_1, user_2) = setup_pool(&env);
+    client.start_pool();
+    client.pay_due(&owner, &1, &None, &None);
+    client.pay_due(&user_1, &1, &None, &None);
+    // user_2 only pays part of the first due
+    client.pay_due(&user_2, &1, &Some(40), &None);
+    advance_ledger(&env, WEEK_IN_SECS + GRACE_PERIOD + 1);
+    client.start_new_iteration(&2);
+
+    client.pay_due(&owner, &2, &None, &None);
+    client.pay_due(&user_1, &2, &None, &None);
+    advance_ledger(&env, WEEK_IN_SECS + GRACE_PERIOD + 1);
+    client.start_new_iteration(&3);
+    assert!(client.get_subscriber_details(&user_2).defaulted);
+    // the pool is short of what each missed iteration still owed
+    assert_eq!(client.get_state().shortfall, 2 * SUB_AMOUNT - 40);
+
+    // so it is for a member missing a due after defaulting
+    let (client, owner, user_1, user_2) = setup_pool(&env);
+    client.start_pool();
+    client.pay_due(&owner, &1, &None, &None);
+    client.pay_due(&user_1, &1, &None, &None);
+    client.pay_due(&user_2, &1, &Some(40), &None);
+    client.mark_defaulted(&user_2);
+    assert_eq!(client.get_state().shortfall, 0);
+    advance_ledger(&env, WEEK_IN_SECS + GRACE_PERIOD + 1);
+    client.start_new_iteration(&2);
+    assert_eq!(client.get_state().shortfall, SUB_AMOUNT - 40);
+}
+
+#[test]
+fn test_initialize_twice() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, owner, _, _) = setup_pool_with_collateral(&env, 0);
+    assert!(client.try_initialize(&owner, &pool_config(&env, &token.address, 0), &None).is_err());
+}
+
+#[test]
+fn test_grace_period() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, user_1, user_2) = setup_pool(&env);
+    client.start_pool();
+
+    // Paying after the weekly deadline but inside the grace window is on-time
+    advance_ledger(&env, WEEK_IN_SECS + GRACE_PERIOD);
+    client.pay_due(&user_1, &1, &None, &None);
+    assert_eq!(client.get_subscriber_details(&user_1).late_payments, 0);
+
+    // One second past the grace window counts as a late payment
+    advance_ledger(&env, 1);
+    client.pay_due(&user_2, &1, &None, &None);
+    assert_eq!(client.get_subscriber_details(&user_2).late_payments, 1);
+    assert_eq!(client.get_subscriber_details(&user_2).last_paid_iter, 1);
+}
+
+#[test]
+#[should_panic(expected = "Subscriber has already paid for this iteration")]
+fn test_pay_due_twice() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, user_1, _) = setup_pool(&env);
+    client.start_pool();
+    client.pay_due(&user_1, &1, &None, &None);
+    client.pay_due(&user_1, &1, &None, &None);
+}
+
+#[test]
+fn test_estimate_resources() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, _, _) = setup_pool(&env);
+    client.start_pool();
+
+    let estimate = client.estimate_resources();
+    assert_eq!(estimate.subscribers, 3);
+    assert_eq!(estimate.iterations, 1);
+    assert!(estimate.pool_entry_bytes > 0);
+    // every entry is sized on its own, and grows as the pool runs
+    assert!(estimate.projected_member_bytes >= estimate.member_entry_bytes);
+    assert!(estimate.projected_iteration_bytes >= estimate.iteration_entry_bytes);
+    // closing touches the instance entry, the winner and the closed iteration only
+    assert_eq!(estimate.close_read_entries, 3);
+    assert_eq!(estimate.close_write_entries, 3);
+
+    // the dry-run must not change the stored pool
+    assert_eq!(client.get_state().pool_iteration_map.len(), 1);
+
+    // a pot past what a u32 holds is still sized, the first iteration is closed before the pool starts
+    let (client, _, _, _, _) = setup_pool_with_config(&env, 0, |config| config.sub_amount = u32::MAX);
+    let estimate = client.estimate_resources();
+    assert_eq!(estimate.iterations, 0);
+    assert_eq!(estimate.close_read_entries, 2);
+    assert_eq!(estimate.close_write_entries, 3);
+}
+
+#[test]
+fn test_default_after_missed_iterations() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, owner, user_1, user_2) = setup_pool(&env);
+    client.start_pool();
+    client.pay_due(&owner, &1, &None, &None);
+    client.pay_due(&user_1, &1, &None, &None);
+
+    // user_2 missed the first iteration
+    advance_ledger(&env, WEEK_IN_SECS + GRACE_PERIOD + 1);
+    client.start_new_iteration(&2);
+    let subscriber = client.get_subscriber_details(&user_2);
+    assert_eq!(subscriber.missed_iterations, 1);
+    assert!(!subscriber.defaulted);
+
+    // and the second one, reaching the default threshold
+    client.pay_due(&owner, &2, &None, &None);
+    client.pay_due(&user_1, &2, &None, &None);
+    advance_ledger(&env, WEEK_IN_SECS + GRACE_PERIOD + 1);
+    client.start_new_iteration(&3);
+    let subscriber = client.get_subscriber_details(&user_2);
+    assert_eq!(subscriber.missed_iterations, 2);
+    assert!(subscriber.defaulted);
+    assert_eq!(client.get_state().shortfall, 2 * SUB_AMOUNT);
+
+    let event = env.events().all().last().unwrap();
+    assert_eq!(event.1, snbfi_events::topics(symbol_short!("default"), user_2.clone()).into_val(&env));
+
+    // defaulted subscribers can't be selected as winner
+    assert!(client.try_set_pool_winner(&3, &250, &user_2).is_err());
+}
+
+#[test]
+fn test_mark_defaulted() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, user_1, _) = setup_pool(&env);
+    client.set_max_missed_iterations(&1);
+    assert_eq!(client.get_state().pool_params.max_missed_iterations, 1);
+
+    client.mark_defaulted(&user_1);
+    assert!(client.get_subscriber_details(&user_1).defaulted);
+    assert!(client.try_mark_defaulted(&user_1).is_err());
+}
+
+#[test]
+fn test_payer_stats() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, user_1, _) = setup_pool(&env);
+    assert_eq!(client.get_payer_stats(&user_1).len(), 0);
+
+    client.start_pool();
+    client.pay_due(&user_1, &1, &None, &None);
+    advance_ledger(&env, WEEK_IN_SECS + GRACE_PERIOD + 1);
+    client.start_new_iteration(&2);
+    client.pay_due(&user_1, &2, &None, &None);
+
+    let stats = client.get_payer_stats(&user_1);
+    assert_eq!(stats.len(), 1);
+    let entry = stats.get(0).unwrap();
+    assert_eq!(entry.payer, user_1);
+    assert_eq!(entry.payments, 2);
+    assert_eq!(entry.total_amount, 2 * SUB_AMOUNT);
+    assert_eq!(entry.last_iteration, 2);
+}
+
+#[test]
+fn test_bootstrap_demo() {
+    let env = Env::default();
+    let client = create_pool_contract(&env);
+
+    let members = client.bootstrap_demo();
+    assert_eq!(members.len(), 4);
+
+    let state = client.get_state();
+    assert_eq!(state.subcriber_map.len(), 4);
+    assert_eq!(state.pool_iteration_map.len(), 3);
+    assert_eq!(client.get_pool_winner(&1), members.get(1).unwrap());
+    assert_eq!(client.get_pool_winner(&2), members.get(2).unwrap());
+    assert_eq!(state.pool_iteration_map.get(3).unwrap().amount_collected, 0);
+    assert_eq!(client.get_payer_stats(&members.get(3).unwrap()).get(0).unwrap().payments, 2);
+
+    assert!(client.try_bootstrap_demo().is_err());
+}
+
+#[test]
+fn test_guarantor_covers_due() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, owner, _, user_2) = setup_pool_with_collateral(&env, 0);
+    client.leave(&user_2);
+    let user_3 = Address::generate(&env);
+    let guarantor = Address::generate(&env);
+    TokenAdminClient::new(&env, &token.address).mint(&guarantor, &INITIAL_BALANCE);
+    client.join(&user_3, &Some(guarantor.clone()), &None);
+    assert_eq!(client.get_subscriber_details(&user_3).guarantor, Some(guarantor.clone()));
+
+    client.start_pool();
+    // the guarantor can't pay before the subscriber is overdue
+    assert!(client.try_cover_due(&guarantor, &user_3).is_err());
+
+    advance_ledger(&env, WEEK_IN_SECS + GRACE_PERIOD + 1);
+    // only the registered guarantor can cover the due
+    assert!(client.try_cover_due(&owner, &user_3).is_err());
+    client.cover_due(&guarantor, &user_3);
+
+    let subscriber = client.get_subscriber_details(&user_3);
+    assert_eq!(subscriber.last_paid_iter, 1);
+    let stats = client.get_payer_stats(&user_3);
+    assert_eq!(stats.get(0).unwrap().payer, guarantor);
+    assert_eq!(client.get_state().pool_iteration_map.get(1).unwrap().amount_collected, SUB_AMOUNT);
+    assert_eq!(token.balance(&guarantor), INITIAL_BALANCE - i128::from(SUB_AMOUNT));
+}
+
+#[test]
+fn test_collateral() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    const COLLATERAL: i128 = 500;
+    let (client, token, owner, user_1, user_2) = setup_pool_with_collateral(&env, COLLATERAL);
+    assert_eq!(client.get_collateral(&user_1), COLLATERAL);
+    assert_eq!(token.balance(&user_1), INITIAL_BALANCE - COLLATERAL);
+    assert_eq!(token.balance(&client.address), 3 * COLLATERAL);
+
+    // collateral stays locked while the pool runs
+    assert!(client.try_withdraw_collateral(&user_1).is_err());
+    // and can only be seized from defaulted subscribers
+    assert!(client.try_seize_collateral(&user_2).is_err());
+
+    client.set_max_missed_iterations(&1);
+    client.start_pool();
+    client.pay_due(&owner, &1, &None, &None);
+    client.pay_due(&user_1, &1, &None, &None);
+    client.approve_shortfall(&1, &String::from_str(&env, "user_2 stopped paying"));
+    // opening iteration 2 defaults user_2
+    client.set_pool_winner(&1, &200, &owner);
+    client.start_new_iteration(&2);
+    assert!(client.get_subscriber_details(&user_2).defaulted);
+    assert_eq!(client.get_state().shortfall, SUB_AMOUNT);
+
+    // the collateral pays the due of user_2 in the open pot, the rest is shared by the others
+    let settlement = client.seize_collateral(&user_2);
+    assert_eq!(
+        settlement,
+        SlashSettlement { seized: COLLATERAL, to_pot: SUB_AMOUNT, to_insurance: 0, to_members: 400, kept: 0 }
+    );
+    let event = env.events().all().last().unwrap();
+    assert_eq!(event.1, snbfi_events::topics(symbol_short!("slashed"), user_2.clone()).into_val(&env));
+    let state = client.get_state();
+    assert_eq!(state.shortfall, SUB_AMOUNT);
+    assert_eq!(state.seized_collateral, 0);
+    assert_eq!(state.pool_iteration_map.get(2).unwrap().amount_collected, SUB_AMOUNT);
+    assert_eq!(client.get_subscriber_details(&owner).credit_balance, 200);
+    assert_eq!(client.get_collateral(&user_2), 0);
+    assert_eq!(client.reconcile().discrepancy, 0);
+
+    // user_2 can't win anymore, so the pool completes once user_1 wins
+    client.pay_due(&owner, &2, &None, &None);
+    client.pay_due(&user_1, &2, &None, &None);
+    client.set_pool_winner(&2, &200, &user_1);
+    client.withdraw_collateral(&user_1);
+    assert_eq!(client.get_collateral(&user_1), 0);
+    // the second due came out of the share of user_1
+    assert_eq!(token.balance(&user_1), INITIAL_BALANCE - i128::from(SUB_AMOUNT));
+    assert!(client.try_withdraw_collateral(&user_1).is_err());
+}
+
+#[test]
+fn test_draw_winner_with_strategy() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, _, user_2) = setup_pool(&env);
+    let strategy = env.register_contract(None, LastCandidateStrategy);
+    client.set_winner_strategy(&WinnerStrategy {
+        contract: Some(strategy),
+        fallback: FallbackPolicy::RandomDraw,
+    });
+
+    client.start_pool();
+    for member in client.get_state().subcriber_map.keys().iter() {
+        client.pay_due(&member, &1, &None, &None);
+    }
+    let winner = client.draw_winner(&1);
+    let candidates = client.get_state().subcriber_map.keys();
+    assert_eq!(winner, candidates.last().unwrap());
+    assert_eq!(client.get_subscriber_details(&winner).prize_money, NO_OF_SUBS * SUB_AMOUNT);
+    assert!(winner == user_2 || client.get_subscriber_details(&user_2).winner_at_iter == 0);
+}
+
+#[test]
+fn test_draw_winner_falls_back_when_strategy_fails() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, owner, user_1, user_2) = setup_pool(&env);
+    let strategy = env.register_contract(None, BrokenStrategy);
+    client.set_winner_strategy(&WinnerStrategy {
+        contract: Some(strategy.clone()),
+        fallback: FallbackPolicy::RandomDraw,
+    });
+
+    client.start_pool();
+    for member in [&owner, &user_1, &user_2] {
+        client.pay_due(member, &1, &None, &None);
+    }
+    let winner = client.draw_winner(&1);
+    assert_eq!(client.get_subscriber_details(&winner).winner_at_iter, 1);
+
+    let degraded = env
+        .events()
+        .all()
+        .iter()
+        .any(|event| event.1 == snbfi_events::topics(symbol_short!("degraded"), strategy.clone()).into_val(&env));
+    assert!(degraded);
+
+    // with the halt policy the failure blocks the draw
+    client.set_winner_strategy(&WinnerStrategy {
+        contract: Some(strategy),
+        fallback: FallbackPolicy::Halt,
+    });
+    client.start_new_iteration(&2);
+    for member in [&owner, &user_1, &user_2] {
+        client.pay_due(member, &2, &None, &None);
+    }
+    assert!(client.try_draw_winner(&2).is_err());
+}
+
+#[test]
+fn test_reputation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, user_1, user_2) = setup_pool(&env);
+    assert_eq!(client.getReputation(&user_1), 0);
+
+    client.start_pool();
+    client.pay_due(&user_1, &1, &None, &None);
+    assert_eq!(client.getReputation(&user_1), 1);
+
+    // a late payment costs reputation
+    client.addReputation(&user_2, &3);
+    advance_ledger(&env, WEEK_IN_SECS + GRACE_PERIOD + 1);
+    client.pay_due(&user_2, &1, &None, &None);
+    assert_eq!(client.getReputation(&user_2), 2);
+
+    // and defaulting costs more, never going below zero
+    client.mark_defaulted(&user_2);
+    assert_eq!(client.getReputation(&user_2), 0);
+}
+
+#[test]
+fn test_shared_reputation_ledger() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let ledger = ::reputation::ReputationLedgerClient::new(&env, &env.register_contract(None, ::reputation::ReputationLedger {}));
+    ledger.initialize(&Address::generate(&env));
+
+    let (pool_1, _, user_1, _) = setup_pool(&env);
+    let (pool_2, token_2, _, _, pool_2_member) = setup_pool_with_collateral(&env, 0);
+    TokenAdminClient::new(&env, &token_2.address).mint(&user_1, &INITIAL_BALANCE);
+    pool_1.set_reputation_contract(&Some(ledger.address.clone()));
+    pool_2.set_reputation_contract(&Some(ledger.address.clone()));
+    assert_eq!(pool_2.get_reputation_contract(), Some(ledger.address.clone()));
+
+    // the ledger rejects writes from pools it has not registered
+    pool_1.start_pool();
+    assert!(pool_1.try_pay_due(&user_1, &1, &None, &None).is_err());
+
+    ledger.register_pool(&pool_1.address);
+    ledger.register_pool(&pool_2.address);
+    pool_1.pay_due(&user_1, &1, &None, &None);
+
+    // the same member joining another pool brings their reputation along
+    pool_2.leave(&pool_2_member);
+    pool_2.join(&user_1, &None, &None);
+    pool_2.start_pool();
+    pool_2.pay_due(&user_1, &1, &None, &None);
+    assert_eq!(ledger.get_reputation(&user_1), 2);
+    assert_eq!(pool_1.getReputation(&user_1), 2);
+}
+
+#[test]
+fn test_rotate_address() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, owner, user_1, user_2) = setup_pool_with_collateral(&env, 50);
+    client.start_pool();
+    for member in [&owner, &user_1, &user_2] {
+        client.pay_due(member, &1, &None, &None);
+    }
+    client.set_pool_winner(&1, &100, &user_1);
+
+    let new_user = Address::generate(&env);
+    client.rotate_address(&user_1, &new_user);
+
+    let subr = client.get_subscriber_details(&new_user);
+    assert_eq!(subr.winner_at_iter, 1);
+    assert_eq!(subr.last_paid_iter, 1);
+    assert!(client.try_get_subscriber_details(&user_1).is_err());
+    assert_eq!(client.get_pool_winner(&1), new_user);
+    assert_eq!(client.get_collateral(&new_user), 50);
+    assert_eq!(client.get_collateral(&user_1), 0);
+    assert_eq!(client.get_payer_stats(&new_user).len(), 1);
+    assert_eq!(client.getReputation(&new_user), 1);
+    assert_eq!(client.getReputation(&user_1), 0);
+
+    // a seat can't be rotated onto another member
+    assert!(client.try_rotate_address(&new_user, &user_2).is_err());
+
+    // rotating the owner's key hands over the pool too
+    let new_owner = Address::generate(&env);
+    client.rotate_address(&owner, &new_owner);
+    assert_eq!(client.get_state().pool_params.pool_owner, new_owner);
+}
+
+#[test]
+fn test_rotate_address_records() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, _, user_1, user_2) = setup_pool_with_collateral(&env, 0);
+    client.leave(&user_2);
+    let referred = Address::generate(&env);
+    client.join(&referred, &None, &Some(user_1.clone()));
+    client.set_profile(&user_1, &String::from_str(&env, "alice"), &None);
+
+    // the new address has to be admitted like any member joining
+    let denied = Address::generate(&env);
+    client.add_to_denylist(&denied);
+    assert!(client.try_rotate_address(&user_1, &denied).is_err());
+
+    // the profile and the referrals move along with the seat
+    let new_user = Address::generate(&env);
+    client.rotate_address(&user_1, &new_user);
+    assert_eq!(client.get_profile(&new_user).unwrap().display_name, String::from_str(&env, "alice"));
+    assert!(client.get_profile(&user_1).is_none());
+    assert_eq!(client.get_referral(&referred).unwrap().referrer, new_user);
+
+    let new_referred = Address::generate(&env);
+    client.rotate_address(&referred, &new_referred);
+    assert!(client.get_referral(&referred).is_none());
+    assert_eq!(client.get_referral(&new_referred).unwrap().referrer, new_user);
+}
+
+#[test]
+fn test_min_reputation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, _, _, user_2) = setup_pool_with_collateral(&env, 0);
+    client.set_min_reputation(&2);
+    assert_eq!(client.get_state().pool_params.min_reputation, 2);
+    client.leave(&user_2);
+
+    let applicant = Address::generate(&env);
+    TokenAdminClient::new(&env, &token.address).mint(&applicant, &INITIAL_BALANCE);
+    assert!(client.try_join(&applicant, &None, &None).is_err());
+
+    client.addReputation(&applicant, &2);
+    client.join(&applicant, &None, &None);
+    assert_eq!(client.get_state().subcriber_map.len(), NO_OF_SUBS);
+
+    // the threshold is fixed once the pool starts
+    client.start_pool();
+    assert!(client.try_set_min_reputation(&0).is_err());
+}
+
+#[test]
+fn test_leave() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, owner, user_1, user_2) = setup_pool_with_collateral(&env, 50);
+    client.leave(&user_1);
+    assert_eq!(client.get_state().subcriber_map.len(), 2);
+    assert_eq!(client.get_collateral(&user_1), 0);
+    assert_eq!(token.balance(&user_1), INITIAL_BALANCE);
+    assert!(client.try_leave(&user_1).is_err());
+    assert!(client.try_leave(&owner).is_err());
+
+    // the freed seat can be taken again
+    client.join(&user_1, &None, &None);
+    assert_eq!(client.get_collateral(&user_1), 50);
+
+    // nobody leaves once the pool has started
+    client.start_pool();
+    assert!(client.try_leave(&user_2).is_err());
+}
+
+#[test]
+fn test_config_history() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, owner, _, _) = setup_pool(&env);
+    assert_eq!(client.get_config_history(&0, &10).len(), 0);
+
+    client.set_max_missed_iterations(&3);
+    client.set_protection_limits(&500, &1_000);
+    client.start_pool();
+    advance_ledger(&env, WEEK_IN_SECS + GRACE_PERIOD + 1);
+    client.start_new_iteration(&2);
+    let strategy = WinnerStrategy { contract: None, fallback: FallbackPolicy::Halt };
+    client.set_winner_strategy(&strategy);
+
+    let history = client.get_config_history(&0, &10);
+    assert_eq!(history.len(), 3);
+    let change = history.get(0).unwrap();
+    assert_eq!(change.key, symbol_short!("max_miss"));
+    assert_eq!(change.changed_by, owner);
+    assert_eq!(change.old_value, ConfigValue::U32(DEFAULT_MAX_MISSED_ITERATIONS));
+    assert_eq!(change.new_value, ConfigValue::U32(3));
+    assert_eq!(change.effective_iteration, 1);
+    // the strategy can change mid-cycle and applies to the running iteration
+    let change = history.get(2).unwrap();
+    assert_eq!(change.new_value, ConfigValue::Strategy(strategy));
+    assert_eq!(change.effective_iteration, 2);
+
+    let page = client.get_config_history(&1, &1);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().key, symbol_short!("penalty"));
+    assert_eq!(client.get_config_history(&3, &10).len(), 0);
+}
+
+#[test]
+fn test_claim_prize_with_winner_note() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, owner, user_1, user_2) = setup_pool_with_collateral(&env, 0);
+    let note = Bytes::from_array(&env, &[7; 8]);
+    // notes are only kept in privacy mode
+    assert!(client.try_set_winner_note(&user_1, &note).is_err());
+    client.set_privacy_mode(&true);
+    client.set_winner_note(&user_1, &note);
+
+    client.start_pool();
+    for user in [&owner, &user_1, &user_2] {
+        client.pay_due(user, &1, &None, &None);
+    }
+    client.set_pool_winner(&1, &300, &user_1);
+    assert!(client.try_claim_prize(&user_2).is_err());
+
+    client.claim_prize(&user_1);
+    let event = env.events().all().last().unwrap();
+    assert_eq!(event.1, snbfi_events::topics(symbol_short!("claim"), user_1.clone()).into_val(&env));
+    let data: (u32, u32, Option<Bytes>) = event.2.into_val(&env);
+    assert_eq!(data, (1, 300, Some(note)));
+    assert_eq!(token.balance(&user_1), INITIAL_BALANCE + 200);
+    assert!(client.get_subscriber_details(&user_1).prize_claimed);
+    assert!(client.try_claim_prize(&user_1).is_err());
+}
+
+#[test]
+fn test_cancel_pool_before_start() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, _, user_1, _) = setup_pool_with_collateral(&env, 50);
+    assert_eq!(client.get_state().status, PoolStatus::Open);
+    client.cancel_pool();
+    assert_eq!(client.get_state().status, PoolStatus::Cancelled);
+    assert!(client.try_start_pool().is_err());
+
+    client.claim_refund(&user_1);
+    assert_eq!(token.balance(&user_1), INITIAL_BALANCE);
+    assert!(client.try_claim_refund(&user_1).is_err());
+}
+
+#[test]
+fn test_cancel_pool_by_vote() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, owner, user_1, user_2) = setup_pool_with_collateral(&env, 0);
+    client.start_pool();
+    assert_eq!(client.get_state().status, PoolStatus::Active);
+    for user in [&owner, &user_1, &user_2] {
+        client.pay_due(user, &1, &None, &None);
+    }
+    client.set_pool_winner(&1, &300, &user_1);
+    // the owner can't cancel on their own once the pool runs
+    assert!(client.try_cancel_pool().is_err());
+    assert!(client.try_claim_refund(&user_2).is_err());
+
+    client.vote_cancel(&user_2);
+    assert!(client.try_vote_cancel(&user_2).is_err());
+    client.vote_cancel(&owner);
+    assert_eq!(client.get_state().status, PoolStatus::Cancelled);
+    assert!(client.try_pay_due(&user_2, &2, &None, &None).is_err());
+
+    // an unclaimed prize is void, everybody gets their dues back
+    assert!(client.try_claim_prize(&user_1).is_err());
+    for user in [&owner, &user_1, &user_2] {
+        client.claim_refund(user);
+        assert_eq!(token.balance(user), INITIAL_BALANCE);
+    }
+    assert_eq!(token.balance(&client.address), 0);
+}
+
+#[test]
+fn test_seat_insurance() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, owner, user_1, user_2) = setup_pool_with_collateral(&env, 0);
+    assert!(client.try_buy_seat_insurance(&user_1).is_err());
+    client.set_insurance_premium(&100);
+    client.buy_seat_insurance(&user_1);
+    client.buy_seat_insurance(&user_2);
+    assert!(client.try_buy_seat_insurance(&user_1).is_err());
+
+    // premiums are paid along with the dues
+    client.start_pool();
+    client.pay_due(&owner, &1, &None, &None);
+    client.pay_due(&user_1, &1, &None, &None);
+    assert_eq!(token.balance(&user_1), INITIAL_BALANCE - 200);
+    assert_eq!(client.get_state().insurance_fund, 100);
+    assert_eq!(client.get_seat_insurance(&user_1).unwrap().premiums_paid, 100);
+
+    // user_2 misses iteration 1, the fund pays instead
+    advance_ledger(&env, WEEK_IN_SECS + GRACE_PERIOD + 1);
+    client.start_new_iteration(&2);
+    let state = client.get_state();
+    assert_eq!(state.insurance_fund, 0);
+    assert_eq!(state.pool_iteration_map.get(1).unwrap().amount_collected, 300);
+    let subr = client.get_subscriber_details(&user_2);
+    assert_eq!(subr.missed_iterations, 0);
+    assert_eq!(subr.last_paid_iter, 1);
+    let insurance = client.get_seat_insurance(&user_2).unwrap();
+    assert_eq!(insurance.claims, 1);
+    assert_eq!(insurance.covered_iteration, 1);
+    assert_eq!(insurance.premiums_paid, 0);
+
+    // only one missed due is covered per cycle
+    client.pay_due(&owner, &2, &None, &None);
+    client.pay_due(&user_1, &2, &None, &None);
+    advance_ledger(&env, WEEK_IN_SECS + GRACE_PERIOD + 1);
+    client.start_new_iteration(&3);
+    assert_eq!(client.get_state().insurance_fund, 100);
+    assert_eq!(client.get_subscriber_details(&user_2).missed_iterations, 1);
+}
+
+#[test]
+fn test_waitlist() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, _, user_1, _) = setup_pool_with_collateral(&env, 50);
+    let token_admin = TokenAdminClient::new(&env, &token.address);
+    let user_3 = Address::generate(&env);
+    let user_4 = Address::generate(&env);
+    let user_5 = Address::generate(&env);
+    for user in [&user_3, &user_4, &user_5] {
+        token_admin.mint(user, &INITIAL_BALANCE);
+    }
+
+    client.join_waitlist(&user_3);
+    client.join_waitlist(&user_4);
+    client.join_waitlist(&user_5);
+    assert!(client.try_join_waitlist(&user_3).is_err());
+    assert_eq!(token.balance(&user_3), INITIAL_BALANCE - 50);
+
+    client.leave_waitlist(&user_5);
+    assert_eq!(token.balance(&user_5), INITIAL_BALANCE);
+    assert_eq!(client.get_waitlist(), Vec::from_array(&env, [user_3.clone(), user_4.clone()]));
+
+    // the first one in line takes the freed seat
+    client.leave(&user_1);
+    assert_eq!(client.get_subscriber_details(&user_3).winner_at_iter, 0);
+    assert_eq!(client.get_collateral(&user_3), 50);
+    assert_eq!(client.get_waitlist(), Vec::from_array(&env, [user_4.clone()]));
+    assert_eq!(
+        env.events().all().last().unwrap().1,
+        snbfi_events::topics(symbol_short!("promoted"), user_3.clone()).into_val(&env)
+    );
+}
+
+#[test]
+fn test_reputation_decay() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, user_1, _) = setup_pool(&env);
+    let decay = ReputationDecay { period_secs: WEEK_IN_SECS, points_per_period: 1 };
+    client.set_reputation_decay(&Some(decay.clone()));
+    assert_eq!(client.get_reputation_decay(), Some(decay));
+
+    client.addReputation(&user_1, &5);
+    advance_ledger(&env, 3 * WEEK_IN_SECS + 1);
+    assert_eq!(client.getReputation(&user_1), 5);
+    assert_eq!(client.get_effective_reputation(&user_1), 2);
+
+    // an update settles the decay and restarts the clock
+    client.start_pool();
+    client.pay_due(&user_1, &1, &None, &None);
+    assert_eq!(client.getReputation(&user_1), 3);
+    assert_eq!(client.get_effective_reputation(&user_1), 3);
+
+    advance_ledger(&env, 10 * WEEK_IN_SECS);
+    assert_eq!(client.get_effective_reputation(&user_1), 0);
+}
+
+#[test]
+fn test_accounting_currency() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, _, user_1, _) = setup_pool_with_collateral(&env, 0);
+    let oracle = fixed_price_oracle::FixedPriceOracleClient::new(&env, &env.register_contract(None, fixed_price_oracle::FixedPriceOracle));
+    // dues are worth 1.00 of the reference currency
+    let currency = AccountingCurrency { oracle: oracle.address.clone(), reference_amount: 100, max_price_age_secs: DAY_IN_SECS };
+    assert!(client.try_set_accounting_currency(&Some(AccountingCurrency { max_price_age_secs: 0, ..currency.clone() })).is_err());
+    client.set_accounting_currency(&Some(currency.clone()));
+    assert_eq!(client.get_accounting_currency(), Some(currency));
+
+    // no price, no iteration
+    assert!(client.try_start_pool().is_err());
+
+    // the token trades at 0.50, so a due is 200 tokens
+    oracle.set_price(&50);
+    client.start_pool();
+    client.pay_due(&user_1, &1, &None, &None);
+    assert_eq!(token.balance(&user_1), INITIAL_BALANCE - 200);
+
+    // a price older than a day isn't used
+    advance_ledger(&env, WEEK_IN_SECS + GRACE_PERIOD + 1);
+    assert!(client.try_start_new_iteration(&2).is_err());
+
+    // the token doubled, next iteration's due halves
+    oracle.set_price(&100);
+    client.start_new_iteration(&2);
+    client.pay_due(&user_1, &2, &None, &None);
+    assert_eq!(client.get_state().pool_iteration_map.get(2).unwrap().due_amount, 100);
+    assert_eq!(token.balance(&user_1), INITIAL_BALANCE - 300);
+}
+
+#[test]
+fn test_transfer_slot() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, owner, user_1, user_2) = setup_pool_with_collateral(&env, 50);
+    client.start_pool();
+    for member in [&owner, &user_1, &user_2] {
+        client.pay_due(member, &1, &None, &None);
+    }
+    client.set_pool_winner(&1, &100, &user_1);
+
+    let buyer = Address::generate(&env);
+    TokenAdminClient::new(&env, &token.address).mint(&buyer, &INITIAL_BALANCE);
+    client.transfer_slot(&user_1, &buyer);
+
+    let subr = client.get_subscriber_details(&buyer);
+    assert_eq!(subr.winner_at_iter, 1);
+    assert_eq!(subr.last_paid_iter, 1);
+    assert_eq!(client.get_pool_winner(&1), buyer);
+    assert!(client.try_get_subscriber_details(&user_1).is_err());
+    // collateral is swapped, reputation stays with the seller
+    assert_eq!(token.balance(&user_1), INITIAL_BALANCE - 100);
+    assert_eq!(client.get_collateral(&buyer), 50);
+    assert_eq!(client.getReputation(&user_1), 1);
+    assert_eq!(client.getReputation(&buyer), 0);
+
+    assert!(client.try_transfer_slot(&buyer, &user_2).is_err());
+    assert!(client.try_transfer_slot(&owner, &user_1).is_err());
+}
+
+#[test]
+fn test_reconcile() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, owner, user_1, user_2) = setup_pool_with_collateral(&env, 50);
+    client.set_protection_limits(&1_000, &1_000);
+    client.start_pool();
+    client.pay_due(&owner, &1, &None, &None);
+    client.pay_due(&user_1, &1, &None, &None);
+    advance_ledger(&env, WEEK_IN_SECS + GRACE_PERIOD + 1);
+    client.pay_due(&user_2, &1, &None, &None);
+    client.set_pool_winner(&1, &250, &user_1);
+    client.claim_prize(&user_1);
+
+    let report = client.reconcile();
+    assert_eq!(report.token, token.address);
+    let bucket = |name, amount| BucketBalance { name, amount };
+    assert_eq!(
+        report.buckets,
+        Vec::from_array(&env, [
+            bucket(symbol_short!("pot"), 50),
+            bucket(symbol_short!("penalties"), 10),
+            bucket(symbol_short!("insurance"), 0),
+            bucket(symbol_short!("reserve"), 0),
+            bucket(symbol_short!("collatrl"), 150),
+            bucket(symbol_short!("seized"), 0),
+            bucket(symbol_short!("credit"), 0),
+            bucket(symbol_short!("sponsored"), 0),
+            bucket(symbol_short!("comm"), 0),
+            bucket(symbol_short!("treasury"), 0),
+            bucket(symbol_short!("unsettled"), 0),
+        ])
+    );
+    assert_eq!(report.expected_balance, 210);
+    assert_eq!(report.actual_balance, 210);
+    assert_eq!(report.discrepancy, 0);
+
+    // tokens sent outside of the pool flows show up as a discrepancy
+    TokenAdminClient::new(&env, &token.address).mint(&client.address, &5);
+    assert_eq!(client.reconcile().discrepancy, 5);
+}
+
+#[test]
+fn test_membership_badge() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, owner, user_1, user_2) = setup_pool(&env);
+    assert_eq!(client.get_badge(&owner), Some(0));
+    assert_eq!(client.get_badge(&user_1), Some(1));
+    assert_eq!(client.badge_owner(&2), user_2);
+
+    // leaving burns the badge, the next member gets a new slot index
+    client.leave(&user_1);
+    assert_eq!(client.get_badge(&user_1), None);
+    assert!(client.try_badge_owner(&1).is_err());
+    let user_3 = Address::generate(&env);
+    client.join(&user_3, &None, &None);
+    assert_eq!(client.get_badge(&user_3), Some(3));
+
+    // and it moves along with the slot
+    let buyer = Address::generate(&env);
+    client.transfer_slot(&user_2, &buyer);
+    assert_eq!(client.get_badge(&user_2), None);
+    assert_eq!(client.badge_owner(&2), buyer);
+}
+
+#[test]
+fn test_get_due_amount() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, owner, user_1, user_2) = setup_pool(&env);
+    client.set_protection_limits(&1_000, &1_000);
+    client.set_insurance_premium(&20);
+    client.buy_seat_insurance(&user_2);
+    assert_eq!(client.get_due_amount(&user_1), 0);
+
+    client.start_pool();
+    assert_eq!(client.get_due_amount(&user_1), 100);
+    assert_eq!(client.get_due_amount(&user_2), 120);
+    client.pay_due(&user_1, &1, &None, &None);
+    assert_eq!(client.get_due_amount(&user_1), 0);
+
+    // a late payer owes the late fee as well
+    advance_ledger(&env, WEEK_IN_SECS + GRACE_PERIOD + 1);
+    assert_eq!(client.get_due_amount(&owner), 110);
+    assert_eq!(client.get_due_amount(&user_2), 130);
+}
+
+#[test]
+fn test_get_pool_schedule() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+
+    let (client, _, _, _) = setup_pool(&env);
+    let schedule = client.get_pool_schedule();
+    assert_eq!(schedule.len(), NO_OF_SUBS);
+    assert_eq!(schedule.get(0).unwrap().status, IterationStatus::Upcoming);
+
+    client.start_pool();
+    advance_ledger(&env, WEEK_IN_SECS + GRACE_PERIOD + 1);
+    client.start_new_iteration(&2);
+    let schedule = client.get_pool_schedule();
+    assert_eq!(
+        schedule.get(0).unwrap(),
+        ScheduleEntry {
+            iteration: 1,
+            open_ts: 1_000,
+            deadline_ts: 1_000 + WEEK_IN_SECS + GRACE_PERIOD,
+            status: IterationStatus::Closed
+        }
+    );
+    assert_eq!(schedule.get(1).unwrap().status, IterationStatus::Open);
+    // the last iteration is projected one period after the running one
+    let last = schedule.get(2).unwrap();
+    assert_eq!(schedule.get(1).unwrap().open_ts, 1_000 + WEEK_IN_SECS);
+    assert_eq!(last.open_ts, 1_000 + 2 * WEEK_IN_SECS);
+    assert_eq!(last.status, IterationStatus::Upcoming);
+}
 
 #[test]
-fn test() {
+fn test_payment_history() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, HelloContract);
-    let client = HelloContractClient::new(&env, &contract_id);
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+
+    let (client, _, user_1, _) = setup_pool(&env);
+    client.start_pool();
+    client.pay_due(&user_1, &1, &None, &None);
+    advance_ledger(&env, WEEK_IN_SECS + GRACE_PERIOD + 1);
+    client.start_new_iteration(&2);
+    advance_ledger(&env, WEEK_IN_SECS + GRACE_PERIOD + 1);
+    client.pay_due(&user_1, &2, &None, &None);
 
-    let words = client.hello(&symbol_short!("Dev"));
+    let history = client.get_payment_history(&user_1, &0, &10);
+    assert_eq!(history.len(), 2);
     assert_eq!(
-        words,
-        vec![&env, symbol_short!("Hello"), symbol_short!("Dev"),]
+        history.get(0).unwrap(),
+        PaymentRecord { iteration: 1, payer: user_1.clone(), amount: 100, discount: 0, timestamp: 1_000, late: false }
     );
+    assert!(history.get(1).unwrap().late);
+
+    let page = client.get_payment_history(&user_1, &1, &5);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().iteration, 2);
+
+    // the history follows the member to a new key
+    let new_user = Address::generate(&env);
+    client.rotate_address(&user_1, &new_user);
+    assert_eq!(client.get_payment_history(&new_user, &0, &10), history);
+    assert_eq!(client.get_payment_history(&user_1, &0, &10).len(), 0);
+}
+
+#[test]
+fn test_get_subscribers() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _, _, _) = setup_pool(&env);
+    let all = client.get_subscribers(&0, &10);
+    assert_eq!(all.len(), 3);
+    let subcriber_map = client.get_state().subcriber_map;
+    for (address, subr) in all.iter() {
+        assert_eq!(subcriber_map.get(address).unwrap(), subr);
+    }
+
+    let page = client.get_subscribers(&1, &1);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap(), all.get(1).unwrap());
+    assert_eq!(client.get_subscribers(&3, &10).len(), 0);
+    assert_eq!(client.get_subscribers(&0, &MAX_SUBSCRIBERS_PER_QUERY).len(), 3);
+    assert!(client.try_get_subscribers(&0, &(MAX_SUBSCRIBERS_PER_QUERY + 1)).is_err());
+
+    // the members outside the page aren't read
+    let (first, _) = all.get(0).unwrap();
+    env.as_contract(&client.address, || {
+        env.storage().persistent().remove(&(storage::MEMBER, first));
+    });
+    assert_eq!(client.get_subscribers(&1, &2), all.slice(1..));
+}
+
+#[test]
+fn test_get_iterations() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, owner, user_1, user_2) = setup_pool(&env);
+    client.start_pool();
+    for (iteration, winner) in [(1, &user_1), (2, &user_2)] {
+        if iteration > 1 {
+            client.start_new_iteration(&iteration);
+        }
+        for member in [&owner, &user_1, &user_2] {
+            client.pay_due(member, &iteration, &None, &None);
+        }
+        client.set_pool_winner(&iteration, &100, winner);
+    }
+    client.start_new_iteration(&3);
+
+    // the third iteration is open, without a winner yet
+    let iterations = client.get_iterations(&1, &4);
+    assert_eq!(iterations.len(), 3);
+    assert_eq!(iterations.get(0).unwrap().winner, user_1);
+    assert_eq!(iterations.get(1).unwrap().winner, user_2);
+    assert_eq!(iterations.get(2).unwrap().current_iteration, 3);
+    assert_eq!(client.get_iterations(&2, &2).len(), 1);
+    assert_eq!(client.get_iterations(&3, &1).len(), 0);
+    assert!(client.try_get_iterations(&1, &(MAX_ITERATIONS_PER_QUERY + 1)).is_err());
+}
+
+#[test]
+fn test_get_pool_stats() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, owner, user_1, user_2) = setup_pool(&env);
+    let stats = client.get_pool_stats();
+    assert_eq!(stats.subscribers, 3);
+    assert_eq!(stats.on_time_rate_bps, PERCENTAGE_MULTIPLIER);
+    assert_eq!(stats.remaining_iterations, 3);
+
+    client.start_pool();
+    client.pay_due(&owner, &1, &None, &None);
+    client.pay_due(&user_1, &1, &None, &None);
+    client.pay_due(&user_2, &1, &None, &None);
+    client.set_pool_winner(&1, &200, &user_1);
+    client.start_new_iteration(&2);
+    client.pay_due(&owner, &2, &None, &None);
+    advance_ledger(&env, 2 * WEEK_IN_SECS + GRACE_PERIOD + 1);
+    client.pay_due(&user_1, &2, &None, &None);
+
+    let stats = client.get_pool_stats();
+    assert_eq!(stats.total_collected, 500);
+    assert_eq!(stats.total_prizes, 200);
+    assert_eq!(stats.total_dividends, 100);
+    assert_eq!(stats.remaining_iterations, 1);
+    // 4 of the 5 payments were on-time
+    assert_eq!(stats.on_time_rate_bps, 8_000);
+}
+
+#[test]
+fn test_penalty_cap() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, token, _, user_1, _) = setup_pool_with_collateral(&env, 0);
+    // 15% late fee, capped at 10% of the 300 a member pays over the cycle
+    client.set_protection_limits(&1_500, &1_000);
+    let limits = client.get_protection_limits();
+    assert_eq!(limits.max_penalty_amount, 30);
+
+    client.start_pool();
+    advance_ledger(&env, WEEK_IN_SECS + GRACE_PERIOD + 1);
+    client.pay_due(&user_1, &1, &None, &None);
+    assert_eq!(client.get_subscriber_details(&user_1).penalties_paid, 15);
+    assert_eq!(token.balance(&user_1), INITIAL_BALANCE - 115);
+
+    // once the cap is reached further late fees are waived
+    advance_ledger(&env, WEEK_IN_SECS + GRACE_PERIOD + 1);
+    client.start_new_iteration(&2);
+    advance_ledger(&env, WEEK_IN_SECS + GRACE_PERIOD + 1);
+    client.pay_due(&user_1, &2, &None, &None);
+    advance_ledger(&env, WEEK_IN_SECS + GRACE_PERIOD + 1);
+    client.start_new_iteration(&3);
+    advance_ledger(&env, WEEK_IN_SECS + GRACE_PERIOD + 1);
+    client.pay_due(&user_1, &3, &None, &None);
+    assert_eq!(client.get_subscriber_details(&user_1).penalties_paid, 30);
+    assert_eq!(client.get_state().penalties_collected, 30);
+    assert_eq!(token.balance(&user_1), INITIAL_BALANCE - 330);
+}
+
+#[test]
+fn test_fair_launch() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    const COLLATERAL: i128 = 50;
+    let (token, token_admin) = setup_token(&env);
+    let client = create_pool_contract(&env);
+    let owner = Address::generate(&env);
+    token_admin.mint(&owner, &INITIAL_BALANCE);
+    client.initialize(&owner, &pool_config(&env, &token.address, COLLATERAL), &None);
+    client.open_fair_launch(&(env.ledger().timestamp() + DAY_IN_SECS));
+
+    let mut applicants = Vec::new(&env);
+    for _ in 0..4 {
+        let user = Address::generate(&env);
+        token_admin.mint(&user, &INITIAL_BALANCE);
+        client.register_for_seat(&user);
+        applicants.push_back(user);
+    }
+    // first-come-first-served joins are closed during the window
+    assert!(client.try_join(&Address::generate(&env), &None, &None).is_err());
+    assert!(client.try_close_fair_launch().is_err());
+
+    advance_ledger(&env, DAY_IN_SECS + 1);
+    assert!(client.try_register_for_seat(&Address::generate(&env)).is_err());
+    let seated = client.close_fair_launch();
+    assert_eq!(seated.len(), 2);
+    assert_eq!(client.get_state().subcriber_map.len(), NO_OF_SUBS);
+    assert_eq!(client.get_fair_launch(), None);
+
+    for user in applicants.iter() {
+        if seated.contains(&user) {
+            assert_eq!(client.get_collateral(&user), COLLATERAL);
+        } else {
+            assert_eq!(client.get_collateral(&user), 0);
+            assert_eq!(token.balance(&user), INITIAL_BALANCE);
+        }
+    }
+}
+
+#[test]
+fn test_native_xlm_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    // the pool runs on XLM itself, members paying from classic accounts as well as contracts
+    let (token, account) = create_native_token(&env);
+    assert_eq!(token.decimals(), 7);
+    let client = create_pool_contract(&env);
+    let owner = Address::generate(&env);
+    let user_1 = Address::generate(&env);
+    for user in [&owner, &user_1] {
+        token.transfer(&account, user, &INITIAL_BALANCE);
+    }
+    client.initialize(&owner, &pool_config(&env, &token.address, 50), &None);
+    client.join(&user_1, &None, &None);
+    client.join(&account, &None, &None);
+    let account_balance = token.balance(&account);
+
+    client.start_pool();
+    client.pay_due(&owner, &1, &None, &None);
+    client.pay_due(&user_1, &1, &None, &None);
+    client.pay_due(&account, &1, &None, &None);
+    assert_eq!(token.balance(&client.address), 300 + 150);
+    assert_eq!(token.balance(&account), account_balance - 100);
+
+    client.set_pool_winner(&1, &250, &account);
+    client.claim_prize(&account);
+    assert_eq!(token.balance(&account), account_balance - 100 + 250);
+    assert_eq!(client.reconcile().discrepancy, 0);
 }
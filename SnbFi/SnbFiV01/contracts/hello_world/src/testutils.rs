@@ -0,0 +1,98 @@
+#![cfg(any(test, feature = "testutils"))]
+
+// helpers to write scenario tests against a pool in a few lines, for this crate, the factory and integrators
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Env, String, Vec,
+};
+
+use crate::{frequency_to_secs, DividendPolicy, Frequency, HelloContract, HelloContractClient, PoolConfig};
+
+// a pool with every seat taken, the owner first, and the members funded for every due of the cycle twice over
+pub struct TestPool<'a> {
+    pub env: &'a Env,
+    pub client: HelloContractClient<'a>,
+    pub token: token::Client<'a>,
+    pub token_admin: token::StellarAssetClient<'a>,
+    pub owner: Address,
+    // every member in the order they joined, the owner included
+    pub subscribers: Vec<Address>,
+    pub sub_amount: u32,
+    pub frequency: Frequency,
+}
+
+impl<'a> TestPool<'a> {
+    // register a token and a pool of `no_of_subs` seats, and join generated members to it.
+    // All the auths of the env are mocked
+    pub fn setup(e: &'a Env, no_of_subs: u32, sub_amount: u32, frequency: Frequency) -> TestPool<'a> {
+        e.mock_all_auths();
+        let token_address = e.register_stellar_asset_contract_v2(Address::generate(e)).address();
+        let token = token::Client::new(e, &token_address);
+        let token_admin = token::StellarAssetClient::new(e, &token_address);
+        let client = HelloContractClient::new(e, &e.register_contract(None, HelloContract {}));
+        let funding = 2 * i128::from(sub_amount) * i128::from(no_of_subs);
+
+        let owner = Address::generate(e);
+        token_admin.mint(&owner, &funding);
+        client.initialize(
+            &owner,
+            &PoolConfig {
+                name: String::from_str(e, "Test pool"),
+                description: String::from_str(e, "Pool set up by the testutils"),
+                no_of_subs,
+                sub_amount,
+                frequency: frequency.clone(),
+                grace_period_secs: 0,
+                token: token_address.clone(),
+                collateral_amount: 0,
+                join_deadline: None,
+                dividend_policy: DividendPolicy::EqualAmongNonWinners,
+                attestation: None,
+            },
+            &None,
+        );
+        let mut subscribers = Vec::from_array(e, [owner.clone()]);
+        for _ in 1..no_of_subs {
+            let subscriber = Address::generate(e);
+            token_admin.mint(&subscriber, &funding);
+            client.join(&subscriber, &None, &None);
+            subscribers.push_back(subscriber);
+        }
+        TestPool { env: e, client, token, token_admin, owner, subscribers, sub_amount, frequency }
+    }
+
+    // move the ledger time forward by one iteration of the pool
+    pub fn advance_iteration(&self) {
+        let delta = frequency_to_secs(&self.frequency);
+        self.env.ledger().with_mut(|l| {
+            l.timestamp += delta;
+        });
+    }
+
+    // every member pays the due of `iteration`
+    pub fn pay_all(&self, iteration: u32) {
+        for subscriber in self.subscribers.iter() {
+            self.client.pay_due(&subscriber, &iteration, &None, &None);
+        }
+    }
+
+    // collect `iteration` and hand its whole pot to `winner`, who claims it. Pools with fees set their winners themselves
+    pub fn run_iteration(&self, iteration: u32, winner: &Address) {
+        self.pay_all(iteration);
+        let collected = self.client.get_state().pool_iteration_map.get(iteration).unwrap().amount_collected;
+        self.client.set_pool_winner(&iteration, &collected, winner);
+        self.client.claim_prize(winner);
+    }
+
+    // start the pool and run every iteration on schedule, the members winning in the order they joined
+    pub fn run_cycle(&self) {
+        self.client.start_pool();
+        for (index, winner) in self.subscribers.iter().enumerate() {
+            if index > 0 {
+                self.advance_iteration();
+                self.client.start_new_iteration(&(index as u32 + 1));
+            }
+            self.run_iteration(index as u32 + 1, &winner);
+        }
+    }
+}
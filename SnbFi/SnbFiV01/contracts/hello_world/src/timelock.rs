@@ -0,0 +1,62 @@
+use soroban_sdk::Env;
+
+use crate::{event, storage, GovernanceKey, ParamChange, PendingChange};
+
+// longest notice the owner can give, so a pool can't lock its own rules for good
+pub const MAX_TIMELOCK_DELAY_SECS: u64 = 30 * 24 * 60 * 60;
+
+// notice given on the sensitive parameter changes, 0 when they apply right away
+pub fn get_delay(e: &Env) -> u64 {
+    storage::get_instance(e, &GovernanceKey::TimelockDelay).unwrap_or(0)
+}
+
+pub fn set_delay(e: &Env, delay_secs: u64) {
+    storage::set_instance(e, &GovernanceKey::TimelockDelay, &delay_secs);
+}
+
+// changes made directly don't give the members any notice
+pub fn require_unlocked(e: &Env) {
+    if get_delay(e) > 0 {
+        panic!("Change must go through the timelock");
+    }
+}
+
+pub fn get_pending(e: &Env, id: u32) -> Option<PendingChange> {
+    storage::get_instance(e, &GovernanceKey::PendingChange(id))
+}
+
+// queue `change` until the notice is over. Returns the id to carry it out with
+pub fn propose(e: &Env, change: ParamChange) -> u32 {
+    let id: u32 = storage::get_instance(e, &GovernanceKey::PendingChangeCount).unwrap_or(0);
+    let proposed_at = e.ledger().timestamp();
+    let pending = PendingChange {
+        change,
+        proposed_at,
+        executable_at: proposed_at + get_delay(e),
+    };
+    storage::set_instance(e, &GovernanceKey::PendingChange(id), &pending);
+    storage::set_instance(e, &GovernanceKey::PendingChangeCount, &(id + 1));
+    event::change_proposed(e, id, &pending);
+    id
+}
+
+// take the change out of the queue once its notice is over
+pub fn take_executable(e: &Env, id: u32) -> ParamChange {
+    let pending = match get_pending(e, id) {
+        Some(pending) => pending,
+        None => panic!("No pending change with this id"),
+    };
+    if e.ledger().timestamp() < pending.executable_at {
+        panic!("Change is still timelocked");
+    }
+    storage::remove_instance(e, &GovernanceKey::PendingChange(id));
+    pending.change
+}
+
+pub fn cancel(e: &Env, id: u32) {
+    if get_pending(e, id).is_none() {
+        panic!("No pending change with this id");
+    }
+    storage::remove_instance(e, &GovernanceKey::PendingChange(id));
+    event::change_cancelled(e, id);
+}
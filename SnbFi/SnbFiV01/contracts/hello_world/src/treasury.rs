@@ -0,0 +1,50 @@
+use soroban_sdk::Env;
+
+use crate::{event, storage, DataKey, ProtocolFee, PERCENTAGE_MULTIPLIER};
+
+// cap on the protocol fee a pool can be initialized with
+pub const MAX_PROTOCOL_FEE_BPS: u32 = 100; // 1%
+
+pub fn get_protocol_fee(e: &Env) -> Option<ProtocolFee> {
+    storage::get_instance(e, &DataKey::ProtocolFee)
+}
+
+pub fn set_protocol_fee(e: &Env, fee: &ProtocolFee) {
+    storage::set_instance(e, &DataKey::ProtocolFee, fee);
+}
+
+// fees accrued to the treasury and not withdrawn yet
+pub fn get_treasury_balance(e: &Env) -> u32 {
+    storage::get_instance(e, &DataKey::TreasuryBalance).unwrap_or(0)
+}
+
+fn set_treasury_balance(e: &Env, balance: u32) {
+    storage::set_instance(e, &DataKey::TreasuryBalance, &balance);
+}
+
+// protocol fee due on a pot of `collected`, 0 when the pool doesn't pay one
+pub fn fee_on(e: &Env, collected: u32) -> u32 {
+    match get_protocol_fee(e) {
+        Some(fee) => {
+            (u64::from(collected) * u64::from(fee.fee_bps) / u64::from(PERCENTAGE_MULTIPLIER)) as u32
+        }
+        None => 0,
+    }
+}
+
+// accrue the protocol fee taken out of the pot of `iteration` to the treasury
+pub fn accrue(e: &Env, iteration: u32, fee: u32) {
+    if fee == 0 {
+        return;
+    }
+    let balance = get_treasury_balance(e) + fee;
+    set_treasury_balance(e, balance);
+    event::fee_accrued(e, iteration, fee, balance);
+}
+
+// empty the treasury balance, returns the amount to transfer
+pub fn take_treasury_balance(e: &Env) -> u32 {
+    let balance = get_treasury_balance(e);
+    set_treasury_balance(e, 0);
+    balance
+}
@@ -0,0 +1,30 @@
+use soroban_sdk::Env;
+
+use crate::{frequency_to_secs, storage, DataKey, State, Subscriber};
+
+// most tranches a prize can be split into
+pub const MAX_VESTING_TRANCHES: u32 = 12;
+
+// number of tranches prizes are released in, 1 pays the whole prize at once
+pub fn get_vesting_tranches(e: &Env) -> u32 {
+    storage::get_instance(e, &DataKey::VestingTranches).unwrap_or(1)
+}
+
+pub fn set_vesting_tranches(e: &Env, tranches: u32) {
+    storage::set_instance(e, &DataKey::VestingTranches, &tranches);
+}
+
+// part of the prize of the winner unlocked so far: the first tranche when the iteration is won,
+// then one more every time the schedule moves on by an iteration
+pub fn unlocked_prize(e: &Env, state: &State, subr: &Subscriber) -> u32 {
+    let tranches = get_vesting_tranches(e);
+    let won_at = state.pool_iteration_map.get(subr.winner_at_iter).unwrap().start_time;
+    let elapsed = e.ledger().timestamp().saturating_sub(won_at);
+    let periods = elapsed / frequency_to_secs(&state.pool_params.frequency);
+    let unlocked = (periods + 1).min(u64::from(tranches)) as u32;
+    if unlocked == tranches {
+        // the last tranche takes the rounding remainder
+        return subr.prize_money;
+    }
+    subr.prize_money / tranches * unlocked
+}
@@ -0,0 +1,23 @@
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::{badge, event, new_subscriber, storage, DataKey, State};
+
+pub fn get_waitlist(e: &Env) -> Vec<Address> {
+    storage::get_instance(e, &DataKey::Waitlist).unwrap_or(Vec::new(e))
+}
+
+pub fn set_waitlist(e: &Env, waitlist: &Vec<Address>) {
+    storage::set_instance(e, &DataKey::Waitlist, waitlist);
+}
+
+// seat the longest waiting address in a free slot, its collateral was taken when it joined the waitlist.
+// Returns the promoted address, if anyone was waiting
+pub fn promote(e: &Env, state: &mut State) -> Option<Address> {
+    let mut waitlist = get_waitlist(e);
+    let user = waitlist.pop_front()?;
+    set_waitlist(e, &waitlist);
+    state.subcriber_map.set(user.clone(), new_subscriber());
+    badge::mint(e, user.clone());
+    event::promoted(e, user.clone());
+    Some(user)
+}
@@ -0,0 +1,66 @@
+use soroban_sdk::{contractclient, token, Address, Env};
+
+use crate::{breaker, ledger, storage, DataKey, IdleDeposit, LedgerAccount, PoolParams};
+
+// Interface an adapter lending idle pot funds out, e.g. to a Blend pool, has to implement
+#[contractclient(name = "YieldAdapterClient")]
+pub trait YieldAdapterInterface {
+    // put `amount` the depositor already sent to the adapter to work
+    fn deposit(e: Env, depositor: Address, amount: i128);
+    // send everything held for the depositor back to them, returning the amount sent
+    fn withdraw(e: Env, depositor: Address) -> i128;
+}
+
+pub fn get_yield_adapter(e: &Env) -> Option<Address> {
+    storage::get_instance(e, &DataKey::YieldAdapter)
+}
+
+pub fn set_yield_adapter(e: &Env, adapter: Option<Address>) {
+    match adapter {
+        Some(adapter) => storage::set_instance(e, &DataKey::YieldAdapter, &adapter),
+        None => storage::remove_instance(e, &DataKey::YieldAdapter),
+    }
+}
+
+// pot funds currently with the adapter
+pub fn get_idle_deposit(e: &Env) -> Option<IdleDeposit> {
+    storage::get_instance(e, &DataKey::IdleDeposit)
+}
+
+// move `amount` of the pot of `iteration` to the adapter
+pub fn deposit(e: &Env, pool_params: &PoolParams, iteration: u32, amount: u32) {
+    let adapter = match get_yield_adapter(e) {
+        Some(adapter) => adapter,
+        None => panic!("Pool has no yield adapter"),
+    };
+    let deposited = match get_idle_deposit(e) {
+        Some(deposit) if deposit.iteration != iteration => panic!("Funds of an earlier iteration are still deposited"),
+        Some(deposit) => deposit.amount,
+        None => 0,
+    };
+    let pool = e.current_contract_address();
+    token::Client::new(e, &pool_params.token).transfer(&pool, &adapter, &i128::from(amount));
+    breaker::record_outflow(e, i128::from(amount));
+    ledger::post(e, LedgerAccount::Lent, LedgerAccount::Cash, i128::from(amount));
+    YieldAdapterClient::new(e, &adapter).deposit(&pool, &i128::from(amount));
+    storage::set_instance(e, &DataKey::IdleDeposit, &IdleDeposit { iteration, amount: deposited + amount });
+}
+
+// bring back the funds deposited for the pot of `iteration` ahead of its payout.
+// Returns the yield earned on them
+pub fn withdraw(e: &Env, iteration: u32) -> u32 {
+    let deposit = match get_idle_deposit(e) {
+        Some(deposit) if deposit.iteration == iteration => deposit,
+        _ => return 0,
+    };
+    //the adapter is fixed once the pool starts, so it still holds the funds
+    let adapter = get_yield_adapter(e).unwrap();
+    let returned = YieldAdapterClient::new(e, &adapter).withdraw(&e.current_contract_address());
+    if returned < i128::from(deposit.amount) {
+        panic!("Yield adapter returned less than was deposited");
+    }
+    storage::remove_instance(e, &DataKey::IdleDeposit);
+    ledger::post(e, LedgerAccount::Cash, LedgerAccount::Lent, i128::from(deposit.amount));
+    ledger::receive(e, LedgerAccount::Pot, returned - i128::from(deposit.amount));
+    (returned - i128::from(deposit.amount)) as u32
+}
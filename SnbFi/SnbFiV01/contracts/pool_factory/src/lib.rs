@@ -0,0 +1,239 @@
+#![no_std]
+use soroban_sdk::{
+    contract, contractimpl, contracttype, Address, BytesN, Env, String, Symbol, Val, Vec,
+};
+use snbfi_events::Deployment;
+use soroban_sdk::xdr::ToXdr;
+
+pub(crate) const BALANCE_BUMP_AMOUNT: u32 = 518400; // 30 days
+pub(crate) const BALANCE_BUMP_THREASHOLD: u32 = 120960; // 7 days
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PoolRecord {
+    // wasm the pool was deployed from
+    pub wasm_hash: BytesN<32>,
+    // address that deployed the pool
+    pub deployer: Address,
+    // the wasm of the pool was quarantined after the deployment
+    pub flagged: bool
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Advisory {
+    // compromised pool wasm
+    pub wasm_hash: BytesN<32>,
+    // what members of pools on this wasm should know
+    pub message: String,
+    // ledger timestamp at which the wasm was quarantined
+    pub issued_at: u64
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PoolTemplate {
+    // wasm the pools are deployed from
+    pub wasm_hash: BytesN<32>,
+    // name given to the pools, e.g. "10 people / weekly / 100 USDC"
+    pub name: String,
+    // description given to the pools
+    pub description: String,
+    pub no_of_subs: u32,
+    pub sub_amount: u32,
+    pub frequency: Frequency,
+    pub grace_period_secs: u64,
+    // token dues and collateral are paid in
+    pub token: Address,
+    pub collateral_amount: i128,
+    pub dividend_policy: DividendPolicy
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    Admin,                     // Address
+    Advisories,                // Vec<Advisory>
+    Pool(Address),             // PoolRecord
+    PoolsByWasm(BytesN<32>),   // Vec<Address>
+    TemplateCount,             // u32
+    Template(u32),             // PoolTemplate
+    TemplatePoolCount          // u32
+}
+
+pub trait PoolFactoryTrait {
+
+    //Initialise the factory with the admin allowed to quarantine wasm hashes
+    fn initialize(e: Env, admin: Address);
+
+    //Deploy a pool from an uploaded wasm and call its initialize with init_args
+    fn deploy(e: Env, deployer: Address, wasm_hash: BytesN<32>, salt: BytesN<32>, init_args: Vec<Val>) -> Address;
+
+    //Get the registry record of a pool deployed by the factory
+    fn get_pool(e: Env, pool: Address) -> Option<PoolRecord>;
+
+    //Mark a pool wasm as compromised: its pools are flagged and it can't be deployed anymore
+    fn quarantine_wasm(e: Env, wasm_hash: BytesN<32>, message: String);
+
+    //Check if a pool wasm has been quarantined
+    fn is_quarantined(e: Env, wasm_hash: BytesN<32>) -> bool;
+
+    //Get every advisory issued for compromised pool wasms
+    fn get_advisories(e: Env) -> Vec<Advisory>;
+
+    //Add a preset of pool parameters organizers can deploy pools from, only the admin can do it
+    fn add_template(e: Env, template: PoolTemplate) -> u32;
+
+    //Withdraw a template, only the admin can do it
+    fn remove_template(e: Env, template_id: u32);
+
+    //Get a template, if it hasn't been withdrawn
+    fn get_template(e: Env, template_id: u32) -> Option<PoolTemplate>;
+
+    //Deploy a pool owned by owner on the parameters of a template
+    fn create_from_template(e: Env, template_id: u32, owner: Address) -> Address;
+}
+
+#[contract]
+pub struct PoolFactory;
+
+#[contractimpl]
+impl PoolFactoryTrait for PoolFactory {
+
+    fn initialize(e: Env, admin: Address) {
+        if e.storage().instance().has(&DataKey::Admin) {
+            panic!("Factory is already initialized");
+        }
+        admin.require_auth();
+        e.storage().instance().set(&DataKey::Admin, &admin);
+    }
+
+    fn deploy(e: Env, deployer: Address, wasm_hash: BytesN<32>, salt: BytesN<32>, init_args: Vec<Val>) -> Address {
+        deployer.require_auth();
+        if Self::is_quarantined(e.clone(), wasm_hash.clone()) {
+            panic!("Wasm hash is quarantined");
+        }
+        let pool = e
+            .deployer()
+            .with_address(deployer.clone(), salt)
+            .deploy(wasm_hash.clone());
+        let _res: Val = e.invoke_contract(&pool, &Symbol::new(&e, "initialize"), init_args);
+
+        set_pool(&e, pool.clone(), &PoolRecord { wasm_hash: wasm_hash.clone(), deployer: deployer.clone(), flagged: false });
+        let mut pools = get_pools_by_wasm(&e, wasm_hash.clone());
+        pools.push_back(pool.clone());
+        set_pools_by_wasm(&e, wasm_hash.clone(), &pools);
+        snbfi_events::publish(&e, snbfi_events::DEPLOYED, pool.clone(), Deployment { wasm_hash, deployer });
+        pool
+    }
+
+    fn get_pool(e: Env, pool: Address) -> Option<PoolRecord> {
+        e.storage().persistent().get(&DataKey::Pool(pool))
+    }
+
+    fn quarantine_wasm(e: Env, wasm_hash: BytesN<32>, message: String) {
+        get_admin(&e).require_auth();
+        if Self::is_quarantined(e.clone(), wasm_hash.clone()) {
+            panic!("Wasm hash is already quarantined");
+        }
+        // flag the pools already running the compromised wasm
+        let pools = get_pools_by_wasm(&e, wasm_hash.clone());
+        for pool in pools.iter() {
+            let mut record = Self::get_pool(e.clone(), pool.clone()).unwrap();
+            record.flagged = true;
+            set_pool(&e, pool, &record);
+        }
+        let mut advisories = Self::get_advisories(e.clone());
+        advisories.push_back(Advisory { wasm_hash: wasm_hash.clone(), message, issued_at: e.ledger().timestamp() });
+        e.storage().instance().set(&DataKey::Advisories, &advisories);
+        snbfi_events::publish(&e, snbfi_events::QUARANTINED, wasm_hash, pools.len());
+    }
+
+    fn is_quarantined(e: Env, wasm_hash: BytesN<32>) -> bool {
+        Self::get_advisories(e)
+            .iter()
+            .any(|advisory| advisory.wasm_hash == wasm_hash)
+    }
+
+    fn get_advisories(e: Env) -> Vec<Advisory> {
+        e.storage()
+            .instance()
+            .get(&DataKey::Advisories)
+            .unwrap_or(Vec::new(&e))
+    }
+
+    fn add_template(e: Env, template: PoolTemplate) -> u32 {
+        get_admin(&e).require_auth();
+        if template.no_of_subs < 2 {
+            panic!("Template needs at least 2 subscribers");
+        }
+        if template.sub_amount == 0 {
+            panic!("Template amount can't be zero");
+        }
+        if template.collateral_amount < 0 {
+            panic!("Collateral amount can't be negative");
+        }
+        let template_id: u32 = e.storage().instance().get(&DataKey::TemplateCount).unwrap_or(0) + 1;
+        e.storage().instance().set(&DataKey::TemplateCount, &template_id);
+        let key = DataKey::Template(template_id);
+        e.storage().persistent().set(&key, &template);
+        e.storage().persistent().extend_ttl(&key, BALANCE_BUMP_THREASHOLD, BALANCE_BUMP_AMOUNT);
+        template_id
+    }
+
+    fn remove_template(e: Env, template_id: u32) {
+        get_admin(&e).require_auth();
+        if Self::get_template(e.clone(), template_id).is_none() {
+            panic!("Template not found");
+        }
+        e.storage().persistent().remove(&DataKey::Template(template_id));
+    }
+
+    fn get_template(e: Env, template_id: u32) -> Option<PoolTemplate> {
+        e.storage().persistent().get(&DataKey::Template(template_id))
+    }
+
+    fn create_from_template(e: Env, template_id: u32, owner: Address) -> Address {
+        let template = match Self::get_template(e.clone(), template_id) {
+            Some(template) => template,
+            None => panic!("Template not found"),
+        };
+        // every pool deployed from a template gets its own salt
+        let count: u32 = e.storage().instance().get(&DataKey::TemplatePoolCount).unwrap_or(0) + 1;
+        e.storage().instance().set(&DataKey::TemplatePoolCount, &count);
+        let salt: BytesN<32> = e.crypto().sha256(&(template_id, count).to_xdr(&e)).to_bytes();
+        let init_args = pool::init_args(&e, &template, owner.clone());
+        Self::deploy(e, owner, template.wasm_hash, salt, init_args)
+    }
+}
+
+fn get_admin(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .expect("Admin not initialized")
+}
+
+fn set_pool(e: &Env, pool: Address, record: &PoolRecord) {
+    let key = DataKey::Pool(pool);
+    e.storage().persistent().set(&key, record);
+    e.storage().persistent().extend_ttl(&key, BALANCE_BUMP_THREASHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+fn get_pools_by_wasm(e: &Env, wasm_hash: BytesN<32>) -> Vec<Address> {
+    e.storage()
+        .persistent()
+        .get(&DataKey::PoolsByWasm(wasm_hash))
+        .unwrap_or(Vec::new(e))
+}
+
+fn set_pools_by_wasm(e: &Env, wasm_hash: BytesN<32>, pools: &Vec<Address>) {
+    let key = DataKey::PoolsByWasm(wasm_hash);
+    e.storage().persistent().set(&key, pools);
+    e.storage().persistent().extend_ttl(&key, BALANCE_BUMP_THREASHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+mod pool;
+
+pub use crate::pool::{DividendPolicy, Frequency};
+mod test;
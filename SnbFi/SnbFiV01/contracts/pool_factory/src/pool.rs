@@ -0,0 +1,57 @@
+use soroban_sdk::{contracttype, Address, Env, IntoVal, String, Val, Vec};
+
+use crate::PoolTemplate;
+
+// the types of the pool initialize arguments, they encode exactly like the ones the pool contract declares
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum Frequency {
+    DAY,
+    WEEK,
+    MONTH
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum DividendPolicy {
+    EqualAmongNonWinners,
+    ProRataByContribution,
+    CarryToNextPot,
+    OwnerDiscretion
+}
+
+#[derive(Clone)]
+#[contracttype]
+struct PoolConfig {
+    name: String,
+    description: String,
+    no_of_subs: u32,
+    sub_amount: u32,
+    frequency: Frequency,
+    grace_period_secs: u64,
+    token: Address,
+    collateral_amount: i128,
+    join_deadline: Option<u64>,
+    dividend_policy: DividendPolicy,
+    attestation: Option<Address>
+}
+
+// arguments of the pool initialize call for a pool owned by `owner` on the terms of the template
+pub fn init_args(e: &Env, template: &PoolTemplate, owner: Address) -> Vec<Val> {
+    let config = PoolConfig {
+        name: template.name.clone(),
+        description: template.description.clone(),
+        no_of_subs: template.no_of_subs,
+        sub_amount: template.sub_amount,
+        frequency: template.frequency.clone(),
+        grace_period_secs: template.grace_period_secs,
+        token: template.token.clone(),
+        collateral_amount: template.collateral_amount,
+        join_deadline: None,
+        dividend_policy: template.dividend_policy.clone(),
+        attestation: None,
+    };
+    let protocol_fee: Option<Val> = None;
+    (owner, config, protocol_fee).into_val(e)
+}
@@ -0,0 +1,115 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Events},
+    Address, BytesN, Env, IntoVal, String,
+};
+
+// any contract with an initialize entry point will do, the token is the one at hand as wasm
+const POOL_WASM: &[u8] = include_bytes!("../testdata/soroban_token_contract.wasm");
+
+fn create_factory_contract(e: &Env) -> PoolFactoryClient<'_> {
+    PoolFactoryClient::new(e, &e.register_contract(None, PoolFactory {}))
+}
+
+fn init_args(e: &Env, admin: &Address) -> Vec<Val> {
+    (
+        admin.clone(),
+        7_u32,
+        String::from_str(e, "Pool"),
+        String::from_str(e, "POOL"),
+    )
+        .into_val(e)
+}
+
+#[test]
+fn test_quarantine_wasm() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let factory = create_factory_contract(&env);
+    let admin = Address::generate(&env);
+    let deployer = Address::generate(&env);
+    factory.initialize(&admin);
+
+    let wasm_hash = env.deployer().upload_contract_wasm(POOL_WASM);
+    let pool = factory.deploy(&deployer, &wasm_hash, &BytesN::from_array(&env, &[1; 32]), &init_args(&env, &deployer));
+    let record = factory.get_pool(&pool).unwrap();
+    assert_eq!(record.wasm_hash, wasm_hash);
+    assert!(!record.flagged);
+    let event = env.events().all().last().unwrap();
+    assert_eq!(event.1, snbfi_events::topics(snbfi_events::DEPLOYED, pool.clone()).into_val(&env));
+    let deployment: Deployment = event.2.into_val(&env);
+    assert_eq!(deployment, Deployment { wasm_hash: wasm_hash.clone(), deployer: deployer.clone() });
+
+    let message = String::from_str(&env, "Funds can be drained, move to a new pool");
+    factory.quarantine_wasm(&wasm_hash, &message);
+    assert!(factory.is_quarantined(&wasm_hash));
+    assert!(factory.get_pool(&pool).unwrap().flagged);
+    let event = env.events().all().last().unwrap();
+    assert_eq!(event.1, snbfi_events::topics(snbfi_events::QUARANTINED, wasm_hash.clone()).into_val(&env));
+    let advisory = factory.get_advisories().get(0).unwrap();
+    assert_eq!(advisory.wasm_hash, wasm_hash);
+    assert_eq!(advisory.message, message);
+
+    // the compromised wasm can't be deployed anymore
+    assert!(factory
+        .try_deploy(&deployer, &wasm_hash, &BytesN::from_array(&env, &[2; 32]), &init_args(&env, &deployer))
+        .is_err());
+}
+
+fn template(e: &Env, wasm_hash: BytesN<32>, token: Address) -> PoolTemplate {
+    PoolTemplate {
+        wasm_hash,
+        name: String::from_str(e, "10 people / weekly / 100 USDC"),
+        description: String::from_str(e, "Ten members saving 100 USDC a week"),
+        no_of_subs: 10,
+        sub_amount: 100,
+        frequency: Frequency::WEEK,
+        grace_period_secs: 3_600,
+        token,
+        collateral_amount: 0,
+        dividend_policy: DividendPolicy::EqualAmongNonWinners,
+    }
+}
+
+#[test]
+fn test_pool_templates() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let factory = create_factory_contract(&env);
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    factory.initialize(&admin);
+    let token = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    let wasm_hash = env.deployer().upload_contract_wasm(POOL_WASM);
+
+    let preset = template(&env, wasm_hash.clone(), token.clone());
+    let template_id = factory.add_template(&preset);
+    assert_eq!(template_id, 1);
+    assert_eq!(factory.get_template(&template_id), Some(preset.clone()));
+    let mut broken = preset.clone();
+    broken.sub_amount = 0;
+    assert!(factory.try_add_template(&broken).is_err());
+
+    // the pool contract accepts the arguments built from the template
+    let pool = env.register_contract(None, hello_world::HelloContract);
+    let _res: Val = env.invoke_contract(
+        &pool,
+        &Symbol::new(&env, "initialize"),
+        pool::init_args(&env, &preset, owner.clone()),
+    );
+    let params = hello_world::HelloContractClient::new(&env, &pool).get_state().pool_params;
+    assert_eq!(params.pool_owner, owner);
+    assert_eq!(params.name, preset.name);
+    assert_eq!(params.no_of_subs, 10);
+    assert_eq!(params.sub_amount, 100);
+    assert_eq!(params.frequency, hello_world::Frequency::WEEK);
+
+    factory.remove_template(&template_id);
+    assert_eq!(factory.get_template(&template_id), None);
+    assert!(factory.try_create_from_template(&template_id, &owner).is_err());
+    assert!(factory.try_remove_template(&template_id).is_err());
+}
@@ -0,0 +1,107 @@
+#![no_std]
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env};
+
+pub(crate) const BALANCE_BUMP_AMOUNT: u32 = 518400; // 30 days
+pub(crate) const BALANCE_BUMP_THREASHOLD: u32 = 120960; // 7 days
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    Admin,                 // Address
+    Pool(Address),         // bool
+    Reputation(Address)    // u32
+}
+
+pub trait ReputationLedgerTrait {
+
+    //Initialise the ledger with the admin allowed to register pools
+    fn initialize(e: Env, admin: Address);
+
+    //Allow a pool contract to write reputation
+    fn register_pool(e: Env, pool: Address);
+
+    //Revoke the write access of a pool contract
+    fn remove_pool(e: Env, pool: Address);
+
+    //Check if a pool contract can write reputation
+    fn is_pool_registered(e: Env, pool: Address) -> bool;
+
+    //Add reputation to a subscriber, called by a registered pool
+    fn add_reputation(e: Env, pool: Address, subscriber: Address, amount: u32);
+
+    //Remove reputation from a subscriber, called by a registered pool
+    fn remove_reputation(e: Env, pool: Address, subscriber: Address, amount: u32);
+
+    //Get the reputation of a subscriber across all pools
+    fn get_reputation(e: Env, subscriber: Address) -> u32;
+}
+
+#[contract]
+pub struct ReputationLedger;
+
+#[contractimpl]
+impl ReputationLedgerTrait for ReputationLedger {
+
+    fn initialize(e: Env, admin: Address) {
+        if e.storage().instance().has(&DataKey::Admin) {
+            panic!("Ledger is already initialized");
+        }
+        admin.require_auth();
+        e.storage().instance().set(&DataKey::Admin, &admin);
+    }
+
+    fn register_pool(e: Env, pool: Address) {
+        get_admin(&e).require_auth();
+        let key = DataKey::Pool(pool);
+        e.storage().persistent().set(&key, &true);
+        e.storage().persistent().extend_ttl(&key, BALANCE_BUMP_THREASHOLD, BALANCE_BUMP_AMOUNT);
+    }
+
+    fn remove_pool(e: Env, pool: Address) {
+        get_admin(&e).require_auth();
+        e.storage().persistent().remove(&DataKey::Pool(pool));
+    }
+
+    fn is_pool_registered(e: Env, pool: Address) -> bool {
+        e.storage().persistent().has(&DataKey::Pool(pool))
+    }
+
+    fn add_reputation(e: Env, pool: Address, subscriber: Address, amount: u32) {
+        require_registered_pool(&e, &pool);
+        let reputation = Self::get_reputation(e.clone(), subscriber.clone());
+        set_reputation(&e, subscriber, reputation.saturating_add(amount));
+    }
+
+    fn remove_reputation(e: Env, pool: Address, subscriber: Address, amount: u32) {
+        require_registered_pool(&e, &pool);
+        let reputation = Self::get_reputation(e.clone(), subscriber.clone());
+        set_reputation(&e, subscriber, reputation.saturating_sub(amount));
+    }
+
+    fn get_reputation(e: Env, subscriber: Address) -> u32 {
+        e.storage().persistent().get(&DataKey::Reputation(subscriber)).unwrap_or(0)
+    }
+}
+
+fn get_admin(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .expect("Admin not initialized")
+}
+
+// only pools registered by the admin can write, and only on their own behalf
+fn require_registered_pool(e: &Env, pool: &Address) {
+    pool.require_auth();
+    if !e.storage().persistent().has(&DataKey::Pool(pool.clone())) {
+        panic!("Pool is not registered");
+    }
+}
+
+fn set_reputation(e: &Env, subscriber: Address, reputation: u32) {
+    let key = DataKey::Reputation(subscriber);
+    e.storage().persistent().set(&key, &reputation);
+    e.storage().persistent().extend_ttl(&key, BALANCE_BUMP_THREASHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+mod test;
@@ -0,0 +1,35 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+fn create_ledger_contract(e: &Env) -> ReputationLedgerClient<'_> {
+    ReputationLedgerClient::new(e, &e.register_contract(None, ReputationLedger {}))
+}
+
+#[test]
+fn test_registered_pools_write_reputation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let ledger = create_ledger_contract(&env);
+    let admin = Address::generate(&env);
+    let pool = Address::generate(&env);
+    let subscriber = Address::generate(&env);
+    ledger.initialize(&admin);
+
+    // unregistered pools can't write
+    assert!(ledger.try_add_reputation(&pool, &subscriber, &3).is_err());
+
+    ledger.register_pool(&pool);
+    assert!(ledger.is_pool_registered(&pool));
+    ledger.add_reputation(&pool, &subscriber, &3);
+    ledger.remove_reputation(&pool, &subscriber, &5);
+    assert_eq!(ledger.get_reputation(&subscriber), 0);
+    ledger.add_reputation(&pool, &subscriber, &2);
+    assert_eq!(ledger.get_reputation(&subscriber), 2);
+
+    ledger.remove_pool(&pool);
+    assert!(!ledger.is_pool_registered(&pool));
+    assert!(ledger.try_add_reputation(&pool, &subscriber, &1).is_err());
+}